@@ -0,0 +1,30 @@
+use crate::db::{delete_budget, get_budgets};
+use rusqlite::Connection;
+use std::error::Error;
+
+/// Stores the budgets of the month that is selected on the Home page
+///
+/// all_budgets : `[[budget_type, name, budgeted, spent, remaining, carry_over],]`
+pub struct BudgetData {
+    pub all_budgets: Vec<Vec<String>>,
+}
+
+impl BudgetData {
+    /// Calls the db to fetch the budgets of the given month index and year
+    pub fn new(conn: &Connection, month: usize, year: usize) -> Self {
+        BudgetData {
+            all_budgets: get_budgets(conn, month, year),
+        }
+    }
+
+    /// Returns all the budgets inside a vector for the table
+    pub fn get_budgets(&self) -> Vec<Vec<String>> {
+        self.all_budgets.clone()
+    }
+
+    /// Deletes the budget of the given index
+    pub fn delete(&self, index: usize, conn: &Connection) -> Result<(), Box<dyn Error>> {
+        let target = &self.all_budgets[index];
+        delete_budget(conn, &target[0], &target[1])
+    }
+}
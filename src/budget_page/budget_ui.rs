@@ -0,0 +1,101 @@
+use crate::home_page::TableData;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+/// The UI function that draws the Budget page. Shows the budgeted, spent and remaining amount
+/// of every budget for the given month. Budgets that were overspent are colored red.
+pub fn budget_ui<B: Backend>(f: &mut Frame<B>, table: &mut TableData, month_title: &str) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Length(9), Constraint::Min(0)].as_ref())
+        .split(size);
+
+    let block = Block::default().style(
+        Style::default()
+            .bg(Color::Rgb(255, 255, 255))
+            .fg(Color::Rgb(50, 205, 50)),
+    );
+    f.render_widget(block, size);
+
+    let help_text = vec![
+        Spans::from("Only Expense transactions count as spent. Follows the month selected on the Home page."),
+        Spans::from("'Arrow Up/Down' : Select a budget"),
+        Spans::from("'Arrow Left/Right' : Previous or next month"),
+        Spans::from("'S' : Set the budget of a Category or a Transaction Method"),
+        Spans::from("'D' : Delete the selected budget"),
+        Spans::from("'F' : Home Page"),
+        Spans::from("Budgets with carry over add the unspent amount to the next month"),
+    ];
+
+    let help_sec = Paragraph::new(help_text)
+        .style(
+            Style::default()
+                .bg(Color::Rgb(255, 255, 255))
+                .fg(Color::Rgb(50, 205, 50)),
+        )
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(
+            "Help",
+            Style::default().add_modifier(Modifier::BOLD),
+        )))
+        .alignment(Alignment::Left);
+
+    let header_cells = [
+        "Type",
+        "Name",
+        "Budgeted",
+        "Spent",
+        "Remaining",
+        "Carry Over",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(Color::Rgb(255, 255, 255))));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::LightBlue))
+        .height(1)
+        .bottom_margin(0);
+
+    // overspent budgets have a negative remaining amount
+    let rows = table.items.iter().map(|item| {
+        let cells = item.iter().map(|c| Cell::from(c.to_string()));
+        let mut row = Row::new(cells).height(1).bottom_margin(0);
+        if item[4].starts_with('-') {
+            row = row.style(Style::default().fg(Color::Red));
+        }
+        row
+    });
+
+    let table_area = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Budgets ({month_title})")),
+        )
+        .widths(&[
+            Constraint::Percentage(15),
+            Constraint::Percentage(25),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+        ])
+        .highlight_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::REVERSED),
+        )
+        .highlight_symbol(">> ");
+
+    f.render_widget(help_sec, chunks[0]);
+    f.render_stateful_widget(table_area, chunks[1], &mut table.state)
+}
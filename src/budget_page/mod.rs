@@ -0,0 +1,5 @@
+mod budget_data;
+mod budget_ui;
+
+pub use budget_data::BudgetData;
+pub use budget_ui::budget_ui;
@@ -1,32 +1,46 @@
-use crate::db::get_all_txs;
+use crate::db::{get_all_tx_methods, get_all_txs, get_total_changes};
 use chrono::naive::NaiveDate;
 use rusqlite::Connection;
 
 /// Stores relevant data to create a chart from the transaction and balance changes
 /// all_txs contains all the transaction
-/// all_balance contains all the balance changes after each transaction happened
+/// all_balance contains all the balance changes in cents after each transaction happened
+/// all_tx_methods contains all the tx methods that are drawn on the chart
+///
+/// The year and the number of changes done on the connection are kept to know when the data is outdated
 pub struct ChartData {
     pub all_txs: Vec<Vec<String>>,
-    pub all_balance: Vec<Vec<String>>,
+    pub all_balance: Vec<Vec<i64>>,
+    pub all_tx_methods: Vec<String>,
+    year: usize,
+    total_changes: i64,
 }
 
 impl ChartData {
     /// Gets all the transaction of the given year and saves them in the struct
-    pub fn set(year: usize) -> Self {
+    pub fn set(year: usize, conn: &Connection) -> Self {
         let mut all_txs = vec![];
         let mut all_balance = vec![];
-        let conn = Connection::open("data.sqlite").expect("Could not connect to database");
         for month in 0..12 {
-            let (txs, balances, _id_num) = get_all_txs(&conn, month, year);
+            let (txs, balances, _id_num) = get_all_txs(conn, month, year);
             all_txs.extend(txs);
             all_balance.extend(balances);
         }
         ChartData {
             all_txs,
             all_balance,
+            all_tx_methods: get_all_tx_methods(conn),
+            year,
+            total_changes: get_total_changes(conn),
         }
     }
 
+    /// Checks whether the data was collected for a different year or anything was
+    /// changed in the database since it was collected
+    pub fn is_outdated(&self, year: usize, conn: &Connection) -> bool {
+        self.year != year || self.total_changes != get_total_changes(conn)
+    }
+
     /// Returns all dates of the transactions that were collected in the struct
     pub fn get_all_dates(&self) -> Vec<NaiveDate> {
         let mut to_return = vec![];
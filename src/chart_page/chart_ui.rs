@@ -1,7 +1,5 @@
 use crate::chart_page::ChartData;
-use crate::db::get_all_tx_methods;
 use chrono::{naive::NaiveDate, Duration};
-use rusqlite::Connection;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout},
@@ -13,7 +11,7 @@ use tui::{
 };
 
 /// Creates the balance chart from all the transactions in a given year
-pub fn chart_ui<B: Backend>(f: &mut Frame<B>, chart_data: ChartData) {
+pub fn chart_ui<B: Backend>(f: &mut Frame<B>, chart_data: &ChartData) {
     let size = f.size();
 
     // divide the terminal into various chunks to draw the interface. This is a vertical chunk
@@ -30,15 +28,14 @@ pub fn chart_ui<B: Backend>(f: &mut Frame<B>, chart_data: ChartData) {
     );
     f.render_widget(block, size);
 
-    // connect to the database and gather all the tx methods
-    let conn = Connection::open("data.sqlite").expect("Could not connect to database");
-    let all_tx_methods = get_all_tx_methods(&conn);
+    // the tx methods are collected along with the chart data
+    let all_tx_methods = &chart_data.all_tx_methods;
 
     let mut datasets: Vec<Vec<(f64, f64)>> = Vec::new();
     let mut last_balances = Vec::new();
 
     // adding default initial value
-    for _i in &all_tx_methods {
+    for _i in all_tx_methods {
         datasets.push(vec![(0.0, 0.0)]);
         last_balances.push(0.0);
     }
@@ -104,7 +101,7 @@ pub fn chart_ui<B: Backend>(f: &mut Frame<B>, chart_data: ChartData) {
 
                 for method_index in 0..all_tx_methods.len() {
                     // keep track of the highest and the lowest point of the balance
-                    let cu_bal = current_balances[method_index].parse::<f64>().unwrap();
+                    let cu_bal = current_balances[method_index] as f64 / 100.0;
                     if cu_bal > highest_balance {
                         highest_balance = cu_bal
                     } else if cu_bal < lowest_balance {
@@ -0,0 +1,360 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// This file decides which database file the app opens. The location can come from the CLI flags,
+// the environment variables or the config file, checked in that order. A ledger is a named database
+// kept inside the data directory as `<name>.sqlite` so multiple ledgers such as personal and business
+// can live side by side.
+
+/// The ledger that is created when the data directory does not have any ledger yet
+pub const DEFAULT_LEDGER: &str = "personal";
+
+/// The number of days a deleted transaction stays in the trash before it is purged on startup
+pub const DEFAULT_TRASH_DAYS: i64 = 30;
+
+/// The number of database backups that are kept before the oldest one is removed
+pub const DEFAULT_BACKUPS: usize = 5;
+
+/// The usage text shown when the CLI arguments could not be parsed
+pub const USAGE: &str = "Usage: rex [command] [--db <path>] [--ledger <name>] [--data-dir <path>] [--trash-days <days>] [--backups <count>]
+           [--encrypt]
+
+Commands:
+rebuild-balances   Recompute every balance from the saved transactions and show what was fixed
+verify             Check the database for problems without changing anything. Exits with 1 if any is found
+restore [number]   List the backups of the database or restore the backup with the given number
+encrypt            Encrypt the database with a passphrase that is asked every time it is opened
+decrypt            Remove the passphrase and save the database as a plain SQLite file again
+change-passphrase  Change the passphrase of an encrypted database
+export <range> [file] Write the transactions of a year, a month or a range of dates to a CSV file.
+                   A file ending with .qif is written as QIF. Example ranges: 2022, 2022-07 or 2022-07-01..2022-09-30
+export-journal <format> <range> [file] Write the transactions of the range as a double-entry journal with the
+                   opening balances. Formats: ledger, hledger or beancount. Beancount amounts are written in USD
+
+--db <path>        Open the database file at the given path
+--ledger <name>    Open the ledger with the given name inside the data directory
+--data-dir <path>  The directory where the ledgers are kept
+--trash-days <days> Purge the deleted transactions older than the given days on startup. 0 keeps them forever
+--backups <count>  The number of backups to keep. A backup is taken before the database is changed. 0 turns them off
+--encrypt          Ask for a passphrase and encrypt the database when a new one is created
+
+The same values can be set with the REX_DB, REX_LEDGER, REX_DATA_DIR, REX_TRASH_DAYS, REX_BACKUPS and REX_ENCRYPT
+environment variables or with the db, ledger, data_dir, trash_days, backups and encrypt keys of the config file. REX_CONFIG changes the config file location.";
+
+/// The commands that run on the selected database without opening the interface
+pub const COMMANDS: [&str; 8] = [
+    "rebuild-balances",
+    "verify",
+    "restore",
+    "encrypt",
+    "decrypt",
+    "change-passphrase",
+    "export",
+    "export-journal",
+];
+
+/// A command given in the CLI. args are the values given after the command name before the flags.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CliCommand {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+/// Separates the command and its values from the flags. The command must be the first argument.
+/// Returns None as the command if the arguments start with a flag.
+pub fn get_command(args: &[String]) -> Result<(Option<CliCommand>, &[String]), String> {
+    let name = match args.first() {
+        Some(first) if !first.starts_with("--") => first,
+        _ => return Ok((None, args)),
+    };
+
+    if !COMMANDS.contains(&name.as_str()) {
+        return Err(format!("Unknown command {name}\n\n{USAGE}"));
+    }
+
+    let total_values = args[1..]
+        .iter()
+        .take_while(|arg| !arg.starts_with("--"))
+        .count();
+    let command = CliCommand {
+        name: name.to_string(),
+        args: args[1..=total_values].to_vec(),
+    };
+    Ok((Some(command), &args[total_values + 1..]))
+}
+
+/// The settings that decide which database file is opened. Each field is optional
+/// and an empty field is filled from the next source.
+///
+/// db_path : the database file to open. Takes priority over the ledger
+///
+/// ledger : the name of the ledger to open inside the data directory
+///
+/// data_dir : the directory where the ledgers are kept
+///
+/// trash_days : the number of days a deleted transaction is kept in the trash
+///
+/// backups : the number of database backups to keep
+///
+/// encrypt : whether a new database is encrypted with a passphrase
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DbConfig {
+    pub db_path: Option<PathBuf>,
+    pub ledger: Option<String>,
+    pub data_dir: Option<PathBuf>,
+    pub trash_days: Option<i64>,
+    pub backups: Option<usize>,
+    pub encrypt: Option<bool>,
+}
+
+impl DbConfig {
+    /// Reads the `--db`, `--ledger`, `--data-dir`, `--trash-days`, `--backups` and `--encrypt` flags.
+    /// `--encrypt` is the only flag without a value. The binary name must not be included in the args.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut config = DbConfig::default();
+        let mut args = args.iter();
+
+        while let Some(flag) = args.next() {
+            if flag == "--encrypt" {
+                config.encrypt = Some(true);
+                continue;
+            }
+
+            let value = match flag.as_str() {
+                "--db" | "--ledger" | "--data-dir" | "--trash-days" | "--backups" => args
+                    .next()
+                    .ok_or(format!("{flag} requires a value\n\n{USAGE}"))?,
+                _ => return Err(format!("Unknown argument {flag}\n\n{USAGE}")),
+            };
+
+            match flag.as_str() {
+                "--db" => config.db_path = Some(PathBuf::from(value)),
+                "--ledger" => config.ledger = Some(value.to_string()),
+                "--trash-days" => config.trash_days = Some(parse_trash_days(value)?),
+                "--backups" => config.backups = Some(parse_backups(value)?),
+                _ => config.data_dir = Some(PathBuf::from(value)),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Reads the REX_DB, REX_LEDGER, REX_DATA_DIR, REX_TRASH_DAYS, REX_BACKUPS and REX_ENCRYPT environment
+    /// variables. Empty values are skipped while numbers and true or false values that are not valid
+    /// return the same error as the CLI arguments and the config file.
+    pub fn from_env() -> Result<Self, String> {
+        Ok(DbConfig {
+            db_path: get_env_var("REX_DB").map(PathBuf::from),
+            ledger: get_env_var("REX_LEDGER"),
+            data_dir: get_env_var("REX_DATA_DIR").map(PathBuf::from),
+            trash_days: parse_env_var("REX_TRASH_DAYS", parse_trash_days)?,
+            backups: parse_env_var("REX_BACKUPS", parse_backups)?,
+            encrypt: parse_env_var("REX_ENCRYPT", parse_bool)?,
+        })
+    }
+
+    /// Parses the text of a config file. Each line is a `key = value` pair with db, ledger, data_dir,
+    /// trash_days, backups or encrypt as the key. Empty lines and lines starting with # are skipped.
+    pub fn from_config_text(text: &str) -> Result<Self, String> {
+        let mut config = DbConfig::default();
+
+        for (line_num, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or(format!(
+                "Config line {} is not a key = value pair",
+                line_num + 1
+            ))?;
+            let value = value.trim().trim_matches('"').to_string();
+
+            match key.trim() {
+                "db" => config.db_path = Some(PathBuf::from(value)),
+                "ledger" => config.ledger = Some(value),
+                "data_dir" => config.data_dir = Some(PathBuf::from(value)),
+                "trash_days" => config.trash_days = Some(parse_trash_days(&value)?),
+                "backups" => config.backups = Some(parse_backups(&value)?),
+                "encrypt" => config.encrypt = Some(parse_bool(&value)?),
+                other => return Err(format!("Unknown config key {other}")),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Fills the empty fields with the fields of the given config
+    pub fn or(self, other: DbConfig) -> DbConfig {
+        DbConfig {
+            db_path: self.db_path.or(other.db_path),
+            ledger: self.ledger.or(other.ledger),
+            data_dir: self.data_dir.or(other.data_dir),
+            trash_days: self.trash_days.or(other.trash_days),
+            backups: self.backups.or(other.backups),
+            encrypt: self.encrypt.or(other.encrypt),
+        }
+    }
+
+    /// Returns the data directory that was set or the default one
+    pub fn get_data_dir(&self) -> PathBuf {
+        self.data_dir
+            .clone()
+            .or_else(get_default_data_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Returns the number of days a deleted transaction is kept in the trash that was set or the default one
+    pub fn get_trash_days(&self) -> i64 {
+        self.trash_days.unwrap_or(DEFAULT_TRASH_DAYS)
+    }
+
+    /// Returns the number of backups to keep that was set or the default one
+    pub fn get_backups(&self) -> usize {
+        self.backups.unwrap_or(DEFAULT_BACKUPS)
+    }
+
+    /// Returns whether a new database should be encrypted. Not encrypted by default.
+    pub fn get_encrypt(&self) -> bool {
+        self.encrypt.unwrap_or(false)
+    }
+}
+
+/// Parses the number of days to keep the deleted transactions. Must be 0 or more.
+fn parse_trash_days(value: &str) -> Result<i64, String> {
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|days| *days >= 0)
+        .ok_or(format!(
+            "Trash days must be 0 or a positive number, found {value}"
+        ))
+}
+
+/// Parses the number of backups to keep. Must be 0 or more.
+fn parse_backups(value: &str) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .map_err(|_| format!("Backups must be 0 or a positive number, found {value}"))
+}
+
+/// Returns the value of an environment variable. Missing and empty variables return None.
+fn get_env_var(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+/// Parses the value of an environment variable with the given parser. The name of the variable is
+/// added to the error so it can be found.
+fn parse_env_var<T>(key: &str, parse: fn(&str) -> Result<T, String>) -> Result<Option<T>, String> {
+    get_env_var(key)
+        .map(|value| parse(&value).map_err(|e| format!("{e} in {key}")))
+        .transpose()
+}
+
+/// Parses a true or false value
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(format!("Expected true or false, found {value}")),
+    }
+}
+
+/// Collects the database config from the CLI arguments, the environment variables and the config file
+pub fn load_config(args: &[String]) -> Result<DbConfig, String> {
+    let file_config = match get_config_path() {
+        Some(path) if path.exists() => {
+            let text = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            DbConfig::from_config_text(&text).map_err(|e| format!("{e} in {}", path.display()))?
+        }
+        _ => DbConfig::default(),
+    };
+
+    Ok(DbConfig::from_args(args)?
+        .or(DbConfig::from_env()?)
+        .or(file_config))
+}
+
+/// Returns the database file to open or None if the user has to select one of the ledgers.
+/// The order is the db path, the ledger, the data.sqlite of older versions in the current directory
+/// and finally the only ledger inside the data directory. The default ledger is used if there is none.
+pub fn resolve_db_path(config: &DbConfig, legacy_path: Option<&Path>) -> Option<PathBuf> {
+    if let Some(db_path) = &config.db_path {
+        return Some(db_path.clone());
+    }
+
+    let data_dir = config.get_data_dir();
+
+    if let Some(ledger) = &config.ledger {
+        return Some(get_ledger_path(&data_dir, ledger));
+    }
+
+    if let Some(legacy_path) = legacy_path.filter(|path| path.exists()) {
+        return Some(legacy_path.to_path_buf());
+    }
+
+    let all_ledgers = get_all_ledgers(&data_dir);
+    match all_ledgers.len() {
+        0 => Some(get_ledger_path(&data_dir, DEFAULT_LEDGER)),
+        1 => Some(get_ledger_path(&data_dir, &all_ledgers[0])),
+        _ => None,
+    }
+}
+
+/// Returns the names of all the ledgers inside the data directory in alphabetical order
+pub fn get_all_ledgers(data_dir: &Path) -> Vec<String> {
+    let mut all_ledgers = match fs::read_dir(data_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "sqlite"))
+            .filter_map(|path| path.file_stem().map(|a| a.to_string_lossy().to_string()))
+            .collect::<Vec<String>>(),
+        Err(_) => Vec::new(),
+    };
+    all_ledgers.sort();
+    all_ledgers
+}
+
+/// Returns the database file of the given ledger
+pub fn get_ledger_path(data_dir: &Path, ledger: &str) -> PathBuf {
+    data_dir.join(format!("{ledger}.sqlite"))
+}
+
+/// Checks whether the ledger name can be used as a file name. Only letters, numbers, - and _ are allowed.
+pub fn verify_ledger_name(ledger: &str) -> bool {
+    !ledger.is_empty()
+        && ledger
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Returns the location of the config file. REX_CONFIG is used if set, otherwise `rex/config` inside
+/// XDG_CONFIG_HOME, `~/.config` or APPDATA on Windows.
+pub fn get_config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("REX_CONFIG").filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    get_base_dir("XDG_CONFIG_HOME", ".config").map(|dir| dir.join("rex").join("config"))
+}
+
+/// Returns the default data directory which is `rex` inside XDG_DATA_HOME, `~/.local/share`
+/// or APPDATA on Windows
+pub fn get_default_data_dir() -> Option<PathBuf> {
+    get_base_dir("XDG_DATA_HOME", ".local/share").map(|dir| dir.join("rex"))
+}
+
+/// Returns the directory of the given XDG variable or the fallback inside the home directory
+fn get_base_dir(xdg_var: &str, home_fallback: &str) -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return env::var_os("APPDATA").map(PathBuf::from);
+    }
+
+    // the XDG spec says relative paths must be ignored
+    if let Some(dir) = env::var_os(xdg_var)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+    {
+        return Some(dir);
+    }
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(home_fallback))
+}
@@ -0,0 +1,162 @@
+use crate::db::unlock_db;
+use chrono::NaiveDateTime;
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags, Result as sqlResult};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The format of the time that is added to the name of each backup
+const BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// A backup of a database found in its backup directory
+///
+/// path : the backup file
+///
+/// created_at : the time the backup was taken, read from the file name
+///
+/// total_txs : the number of transactions saved in the backup. None if the backup could not be opened
+/// such as a backup taken before the passphrase was changed
+#[derive(Debug, PartialEq, Eq)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub created_at: NaiveDateTime,
+    pub total_txs: Option<i64>,
+}
+
+/// Returns the directory where the backups of the given database are kept. It is the backups
+/// directory next to the database file.
+pub fn get_backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .filter(|a| !a.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .join("backups")
+}
+
+/// Takes a consistent copy of the database with SQLite's online backup and saves it as
+/// `<db name>-<time>.sqlite` inside the backup directory. The backup of an encrypted database is
+/// encrypted with the same passphrase. Returns the path of the new backup.
+pub fn backup_db(
+    conn: &Connection,
+    db_path: &Path,
+    passphrase: Option<&str>,
+    now: NaiveDateTime,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let backup_dir = get_backup_dir(db_path);
+    fs::create_dir_all(&backup_dir)?;
+
+    let backup_path = backup_dir.join(format!(
+        "{}-{}.sqlite",
+        get_db_name(db_path),
+        now.format(BACKUP_TIME_FORMAT)
+    ));
+    let mut backup_conn = open_backup(&backup_path, passphrase, OpenFlags::default())?;
+    copy_db(conn, &mut backup_conn)?;
+    Ok(backup_path)
+}
+
+/// Removes the oldest backups of the given database so only the given number of backups are kept.
+/// Returns the number of backups that were removed.
+pub fn remove_old_backups(db_path: &Path, keep: usize) -> Result<usize, Box<dyn Error>> {
+    // the backups are sorted from the latest so everything after the limit is the oldest
+    let old_backups = get_backup_paths(db_path)?.into_iter().skip(keep);
+
+    let mut total_removed = 0;
+    for (path, _) in old_backups {
+        fs::remove_file(path)?;
+        total_removed += 1;
+    }
+    Ok(total_removed)
+}
+
+/// Returns all the backups of the given database from the latest along with the number of
+/// transactions each of them has. The backups are opened read only with the given passphrase.
+pub fn get_all_backups(
+    db_path: &Path,
+    passphrase: Option<&str>,
+) -> Result<Vec<BackupInfo>, Box<dyn Error>> {
+    let mut all_backups = Vec::new();
+
+    for (path, created_at) in get_backup_paths(db_path)? {
+        let total_txs = open_backup(&path, passphrase, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .and_then(|backup_conn| {
+                backup_conn.query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+            })
+            .ok();
+
+        all_backups.push(BackupInfo {
+            path,
+            created_at,
+            total_txs,
+        });
+    }
+    Ok(all_backups)
+}
+
+/// Replaces the content of the database with the given backup using SQLite's online backup.
+/// The backup must use the same passphrase as the database. The connection stays usable afterwards.
+pub fn restore_backup(
+    conn: &mut Connection,
+    backup_path: &Path,
+    passphrase: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    if !backup_path.exists() {
+        return Err(format!("No backup was found at {}", backup_path.display()).into());
+    }
+    let backup_conn = open_backup(backup_path, passphrase, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    copy_db(&backup_conn, conn)?;
+    Ok(())
+}
+
+/// Opens a backup file and unlocks it if a passphrase is given
+fn open_backup(path: &Path, passphrase: Option<&str>, flags: OpenFlags) -> sqlResult<Connection> {
+    let backup_conn = Connection::open_with_flags(path, flags)?;
+    if let Some(passphrase) = passphrase {
+        unlock_db(&backup_conn, passphrase)?;
+    }
+    Ok(backup_conn)
+}
+
+/// Copies every page of one database to the other with SQLite's online backup. Both databases must
+/// use the same passphrase or none.
+fn copy_db(from: &Connection, to: &mut Connection) -> sqlResult<()> {
+    Backup::new(from, to)?.run_to_completion(100, Duration::ZERO, None)
+}
+
+/// Returns the name of the database file without the extension which every backup name starts with
+fn get_db_name(db_path: &Path) -> String {
+    db_path
+        .file_stem()
+        .map(|a| a.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Collects the backup files of the given database with the time they were taken, the latest first.
+/// Files with a name that does not match the backup name of the database are skipped.
+fn get_backup_paths(db_path: &Path) -> Result<Vec<(PathBuf, NaiveDateTime)>, Box<dyn Error>> {
+    let backup_dir = get_backup_dir(db_path);
+    if !backup_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let prefix = format!("{}-", get_db_name(db_path));
+    let mut backup_paths = Vec::new();
+
+    for entry in fs::read_dir(&backup_dir)? {
+        let path = entry?.path();
+        let created_at = path
+            .file_name()
+            .and_then(|a| a.to_str())
+            .and_then(|a| a.strip_prefix(&prefix))
+            .and_then(|a| a.strip_suffix(".sqlite"))
+            .and_then(|a| NaiveDateTime::parse_from_str(a, BACKUP_TIME_FORMAT).ok());
+
+        if let Some(created_at) = created_at {
+            backup_paths.push((path, created_at));
+        }
+    }
+    backup_paths.sort_by_key(|a| std::cmp::Reverse(a.1));
+    Ok(backup_paths)
+}
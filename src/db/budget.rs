@@ -0,0 +1,208 @@
+use crate::db::{amount_to_cents, cents_to_amount, get_sql_dates};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension, Result as sqlResult};
+use std::error::Error;
+
+/// A monthly budget as saved in the budgets table. budget_type is either Category or Method
+/// and the amount is in cents.
+struct Budget {
+    budget_type: String,
+    name: String,
+    target_id: i32,
+    amount: i64,
+    rollover: bool,
+    start_month: NaiveDate,
+}
+
+impl Budget {
+    /// Returns the total expense in cents of the month the given date is in
+    /// for the category or the Transaction Method of the budget
+    fn get_spent(&self, conn: &Connection, month_date: NaiveDate) -> sqlResult<i64> {
+        let (datetime_1, datetime_2) =
+            get_sql_dates(month_date.month() as usize, month_date.year() as usize);
+
+        let target_filter = if self.budget_type == "Category" {
+            "category_id = ?3"
+        } else {
+            "method_id = ?3"
+        };
+
+        // split transactions are counted by their legs instead of the total amount
+        conn.query_row(
+            &format!(
+                "SELECT COALESCE(SUM(amount), 0) FROM (
+                    SELECT tx_all.amount, tx_all.category_id, tx_methods.id AS method_id FROM tx_all
+                    LEFT JOIN tx_methods ON tx_methods.name = tx_all.tx_method
+                    WHERE tx_all.tx_type = 'Expense' AND tx_all.date BETWEEN ?1 AND ?2
+                    AND tx_all.id_num NOT IN (SELECT id_num FROM tx_legs)
+                    UNION ALL
+                    SELECT tx_legs.amount, tx_legs.category_id, tx_legs.method_id FROM tx_legs
+                    INNER JOIN tx_all ON tx_all.id_num = tx_legs.id_num
+                    WHERE tx_all.tx_type = 'Expense' AND tx_all.date BETWEEN ?1 AND ?2
+                ) WHERE {target_filter}"
+            ),
+            params![datetime_1, datetime_2, self.target_id],
+            |row| row.get(0),
+        )
+    }
+}
+
+/// Sets the monthly budget of a category or a Transaction Method. budget_type is either Category or Method.
+/// A budget that already exists gets the new amount and carry over setting while keeping its start month.
+/// With carry over on, the unspent amount of each month is added to the budget of the next month
+/// starting from the start month which is in the `YYYY-MM` format.
+pub fn set_budget(
+    conn: &Connection,
+    budget_type: &str,
+    target: &str,
+    amount: &str,
+    rollover: bool,
+    start_month: &str,
+) -> Result<(), Box<dyn Error>> {
+    let int_amount = amount_to_cents(amount).map_err(|e| e as Box<dyn Error>)?;
+    if int_amount <= 0 {
+        return Err("The budget amount must be above 0".into());
+    }
+    parse_month(start_month).ok_or(format!("{start_month} is not a valid month"))?;
+
+    let column = get_target_column(budget_type)?;
+    let target_id = get_target_id(conn, budget_type, target)?
+        .ok_or(format!("{budget_type} {target} was not found"))?;
+
+    conn.execute(
+        &format!(
+            "INSERT INTO budgets ({column}, amount, rollover, start_month) VALUES (?, ?, ?, ?)
+            ON CONFLICT ({column}) DO UPDATE SET amount = excluded.amount, rollover = excluded.rollover"
+        ),
+        params![target_id, int_amount, rollover, start_month],
+    )?;
+    Ok(())
+}
+
+/// Deletes the budget of a category or a Transaction Method
+pub fn delete_budget(
+    conn: &Connection,
+    budget_type: &str,
+    target: &str,
+) -> Result<(), Box<dyn Error>> {
+    let column = get_target_column(budget_type)?;
+    let target_id = get_target_id(conn, budget_type, target)?
+        .ok_or(format!("{budget_type} {target} was not found"))?;
+
+    let deleted = conn.execute(
+        &format!("DELETE FROM budgets WHERE {column} = ?"),
+        [target_id],
+    )?;
+
+    if deleted == 0 {
+        return Err(format!("{budget_type} {target} does not have a budget").into());
+    }
+    Ok(())
+}
+
+/// Returns the budgets that have started by the given month index and year with the budgeted, spent
+/// and remaining amount of the month. Only Expense transactions are counted as spent. The budgeted
+/// amount includes the unspent amount carried over from the earlier months if carry over is on.
+/// return example: `[["Category", "Food", "300.00", "320.50", "-20.50", "No"]]`
+pub fn get_budgets(conn: &Connection, month: usize, year: usize) -> Vec<Vec<String>> {
+    let all_budgets = get_all_budgets(conn).expect("could not get the budgets");
+    let selected_month = NaiveDate::from_ymd(year as i32, month as u32 + 1, 1);
+
+    let mut all_rows = Vec::new();
+
+    for budget in all_budgets {
+        if budget.start_month > selected_month {
+            continue;
+        }
+
+        // go through every month from the start month and keep whatever was not spent.
+        // Nothing is carried without carry over so the earlier months are not looked at
+        let mut carry_over = 0;
+        let mut cu_month = budget.start_month;
+
+        while budget.rollover && cu_month < selected_month {
+            let spent = budget.get_spent(conn, cu_month).unwrap();
+            carry_over = (budget.amount + carry_over - spent).max(0);
+            cu_month = get_next_month(cu_month);
+        }
+
+        let budgeted = budget.amount + carry_over;
+        let spent = budget.get_spent(conn, selected_month).unwrap();
+        let rollover = if budget.rollover { "Yes" } else { "No" };
+
+        all_rows.push(vec![
+            budget.budget_type,
+            budget.name,
+            cents_to_amount(budgeted),
+            cents_to_amount(spent),
+            cents_to_amount(budgeted - spent),
+            rollover.to_string(),
+        ]);
+    }
+    all_rows
+}
+
+/// Collects all the budgets, the category budgets first, in the order they were added
+fn get_all_budgets(conn: &Connection) -> sqlResult<Vec<Budget>> {
+    let mut statement = conn.prepare(
+        "SELECT 'Category', categories.name, categories.id, amount, rollover, start_month, budgets.id
+        FROM budgets INNER JOIN categories ON categories.id = budgets.category_id
+        UNION ALL
+        SELECT 'Method', tx_methods.name, tx_methods.id, amount, rollover, start_month, budgets.id
+        FROM budgets INNER JOIN tx_methods ON tx_methods.id = budgets.method_id
+        ORDER BY 1, 7",
+    )?;
+
+    let all_budgets = statement
+        .query_map([], |row| {
+            let start_month: String = row.get(5)?;
+            Ok(Budget {
+                budget_type: row.get(0)?,
+                name: row.get(1)?,
+                target_id: row.get(2)?,
+                amount: row.get(3)?,
+                rollover: row.get(4)?,
+                start_month: parse_month(&start_month).unwrap(),
+            })
+        })?
+        .collect::<sqlResult<Vec<Budget>>>()?;
+    Ok(all_budgets)
+}
+
+/// Returns the budgets table column that keeps the id of the given budget type
+fn get_target_column(budget_type: &str) -> Result<&'static str, Box<dyn Error>> {
+    match budget_type {
+        "Category" => Ok("category_id"),
+        "Method" => Ok("method_id"),
+        _ => Err(format!("{budget_type} is not a budget type").into()),
+    }
+}
+
+/// Returns the id of the category or the Transaction Method a budget is for
+fn get_target_id(conn: &Connection, budget_type: &str, target: &str) -> sqlResult<Option<i32>> {
+    let table = if budget_type == "Category" {
+        "categories"
+    } else {
+        "tx_methods"
+    };
+    conn.query_row(
+        &format!("SELECT id FROM {table} WHERE name = ?"),
+        [target],
+        |row| row.get(0),
+    )
+    .optional()
+}
+
+/// Returns the first day of the month after the given date
+fn get_next_month(date: NaiveDate) -> NaiveDate {
+    if date.month() == 12 {
+        NaiveDate::from_ymd(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd(date.year(), date.month() + 1, 1)
+    }
+}
+
+/// Parses a `YYYY-MM` month into the first day of the month
+fn parse_month(month: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d").ok()
+}
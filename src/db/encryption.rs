@@ -0,0 +1,90 @@
+use crate::db::open_db;
+use rusqlite::{Connection, Result as sqlResult};
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// An encrypted database is a SQLCipher database. Every db function works the same way on it once the
+// connection is unlocked with the passphrase, so the passphrase is only needed while opening it.
+
+/// Every plain SQLite database file starts with this header. An encrypted database does not have it.
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Checks whether the database file at the given path is encrypted. A file that does not exist or
+/// is empty is not encrypted.
+pub fn is_db_encrypted(db_path: &Path) -> bool {
+    let mut header = [0; 16];
+    match File::open(db_path).and_then(|mut file| file.read_exact(&mut header)) {
+        Ok(_) => &header != SQLITE_HEADER,
+        Err(_) => false,
+    }
+}
+
+/// Gives the passphrase to the connection. Must be called before anything else is done with it.
+/// A new database becomes encrypted with the passphrase. Fails if the passphrase is wrong.
+pub fn unlock_db(conn: &Connection, passphrase: &str) -> sqlResult<()> {
+    conn.pragma_update(None, "key", passphrase)?;
+    // the passphrase is only checked once the database is read
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })?;
+    Ok(())
+}
+
+/// Changes the passphrase of an unlocked encrypted database
+pub fn change_passphrase(conn: &Connection, new_passphrase: &str) -> sqlResult<()> {
+    conn.pragma_update(None, "rekey", new_passphrase)
+}
+
+/// Replaces the plain database at the given path with an encrypted copy of it.
+/// Fails if the database is already encrypted.
+pub fn encrypt_db(db_path: &Path, passphrase: &str) -> Result<(), Box<dyn Error>> {
+    if is_db_encrypted(db_path) {
+        return Err(format!("{} is already encrypted", db_path.display()).into());
+    }
+    let conn = open_db(&db_path.to_string_lossy())?;
+    replace_with_export(conn, db_path, passphrase)
+}
+
+/// Replaces the encrypted database at the given path with a plain copy of it.
+/// Fails if the database is not encrypted or the passphrase is wrong.
+pub fn decrypt_db(db_path: &Path, passphrase: &str) -> Result<(), Box<dyn Error>> {
+    if !is_db_encrypted(db_path) {
+        return Err(format!("{} is not encrypted", db_path.display()).into());
+    }
+    let conn = open_db(&db_path.to_string_lossy())?;
+    unlock_db(&conn, passphrase)?;
+    replace_with_export(conn, db_path, "")
+}
+
+/// Copies everything in the database to a new file with the given passphrase and puts the new file
+/// in place of the database. An empty passphrase creates a plain database.
+fn replace_with_export(
+    conn: Connection,
+    db_path: &Path,
+    passphrase: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut export_path = PathBuf::from(db_path);
+    export_path.set_extension("export");
+    if export_path.exists() {
+        fs::remove_file(&export_path)?;
+    }
+
+    // sqlcipher_export does not copy the schema version so it is set separately
+    let schema_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    conn.execute(
+        "ATTACH DATABASE ? AS export KEY ?",
+        [export_path.to_string_lossy().as_ref(), passphrase],
+    )?;
+    conn.query_row("SELECT sqlcipher_export('export')", [], |_| Ok(()))?;
+    conn.execute_batch(&format!(
+        "PRAGMA export.user_version = {schema_version}; DETACH DATABASE export;"
+    ))?;
+
+    // the connection must be closed before the file can be replaced
+    conn.close().map_err(|(_, e)| e)?;
+    fs::rename(&export_path, db_path)?;
+    Ok(())
+}
@@ -0,0 +1,455 @@
+use crate::db::{
+    amount_to_cents, cents_to_amount, get_all_tx_methods, get_last_time_balance, get_tx_legs,
+};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{Connection, Result as sqlResult};
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Turns a month, a year or a range of dates into the first and the last date they cover.
+/// Accepted formats are `2022`, `2022-07`, `2022-07-19` and `2022-07-01..2022-09-30`.
+pub fn parse_date_range(text: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let error = || format!("{text} is not a year, a month, a date or a range of dates");
+
+    if let Some((start, end)) = text.split_once("..") {
+        let start = NaiveDate::parse_from_str(start, "%Y-%m-%d").map_err(|_| error())?;
+        let end = NaiveDate::parse_from_str(end, "%Y-%m-%d").map_err(|_| error())?;
+        if start > end {
+            return Err(format!("The range {text} ends before it starts"));
+        }
+        return Ok((start, end));
+    }
+
+    let parts = text
+        .split('-')
+        .map(|a| a.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()
+        .ok_or_else(error)?;
+
+    let (start, end) = match parts[..] {
+        [year] => (
+            NaiveDate::from_ymd_opt(year as i32, 1, 1),
+            NaiveDate::from_ymd_opt(year as i32, 12, 31),
+        ),
+        [year, month] => {
+            let start = NaiveDate::from_ymd_opt(year as i32, month, 1);
+            // the day before the first day of the next month
+            let end = start
+                .and_then(|a| match a.month() {
+                    12 => NaiveDate::from_ymd_opt(a.year() + 1, 1, 1),
+                    month => NaiveDate::from_ymd_opt(a.year(), month + 1, 1),
+                })
+                .and_then(|a| a.pred_opt());
+            (start, end)
+        }
+        [year, month, day] => {
+            let date = NaiveDate::from_ymd_opt(year as i32, month, day);
+            (date, date)
+        }
+        _ => (None, None),
+    };
+
+    match (start, end) {
+        (Some(start), Some(end)) => Ok((start, end)),
+        _ => Err(error()),
+    }
+}
+
+/// Writes every transaction between the two dates, both included, to a CSV file. Each row has the
+/// date, details, Transaction Method, amount, type, category and tags of the transaction followed by
+/// the balance change it made on every Transaction Method. Returns the number of transactions written.
+pub fn export_csv(
+    conn: &Connection,
+    path: &Path,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<usize, Box<dyn Error>> {
+    let all_tx_methods = get_all_tx_methods(conn);
+
+    let mut header = vec![
+        "Date",
+        "Details",
+        "TX Method",
+        "Amount",
+        "TX Type",
+        "Category",
+        "Tags",
+    ];
+    header.extend(all_tx_methods.iter().map(|a| a.as_str()));
+
+    let mut lines = vec![to_csv_line(&header)];
+    let all_txs = get_export_txs(conn, start, end)?;
+
+    for tx in &all_txs {
+        let mut fields = tx.fields.clone();
+        for method in &all_tx_methods {
+            let change = tx.changes.get(method).copied().unwrap_or(0);
+            fields.push(cents_to_amount(change));
+        }
+        lines.push(to_csv_line(&fields));
+    }
+
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(all_txs.len())
+}
+
+/// Writes every transaction between the two dates, both included, to a QIF file. Each Transaction Method
+/// gets a bank account section with the part of every transaction that changed its balance. A Transfer is
+/// written in both accounts with the other account as its `[Account]` category so the file can be read
+/// back by Rex or by other finance tools. Returns the number of transactions written.
+pub fn export_qif(
+    conn: &Connection,
+    path: &Path,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<usize, Box<dyn Error>> {
+    let all_txs = get_export_txs(conn, start, end)?;
+    let mut lines = Vec::new();
+
+    for method in get_all_tx_methods(conn) {
+        let method_txs = all_txs
+            .iter()
+            .filter_map(|tx| tx.changes.get(&method).map(|change| (tx, *change)))
+            .filter(|(_, change)| *change != 0)
+            .collect::<Vec<(&ExportTx, i64)>>();
+        if method_txs.is_empty() {
+            continue;
+        }
+
+        lines.extend([
+            "!Account".to_string(),
+            format!("N{method}"),
+            "TBank".to_string(),
+            "^".to_string(),
+            "!Type:Bank".to_string(),
+        ]);
+
+        for (tx, change) in method_txs {
+            // fields : [date, details, tx_method, amount, tx_type, category, tags]
+            let date = NaiveDate::parse_from_str(&tx.fields[0], "%Y-%m-%d")?;
+            lines.push(format!("D{}", date.format("%m/%d/%Y")));
+            lines.push(format!("T{}", cents_to_amount(change)));
+            lines.push(format!("P{}", tx.fields[1].replace(['\n', '\r'], " ")));
+
+            if tx.fields[4] == "Transfer" {
+                let other_method = tx.fields[2]
+                    .split(" to ")
+                    .find(|a| a != &method)
+                    .unwrap_or_default();
+                lines.push(format!("L[{other_method}]"));
+            } else if !tx.fields[5].is_empty() {
+                lines.push(format!("L{}", tx.fields[5]));
+            }
+            lines.push("^".to_string());
+        }
+    }
+
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(all_txs.len())
+}
+
+/// The commodity written after every amount of a beancount journal. Beancount does not accept an
+/// amount without one while Rex does not keep a currency.
+pub const BEANCOUNT_COMMODITY: &str = "USD";
+
+/// The plain text accounting tools a journal can be written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalFormat {
+    Ledger,
+    Hledger,
+    Beancount,
+}
+
+impl JournalFormat {
+    /// Finds the format from its name. Accepted names are `ledger`, `hledger` and `beancount`.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "ledger" => Ok(JournalFormat::Ledger),
+            "hledger" => Ok(JournalFormat::Hledger),
+            "beancount" => Ok(JournalFormat::Beancount),
+            _ => Err(format!(
+                "{name} is not a journal format. Values: ledger/hledger/beancount"
+            )),
+        }
+    }
+
+    /// Returns the file extension the tool uses for its journals
+    pub fn get_extension(&self) -> &str {
+        match self {
+            JournalFormat::Ledger => "ledger",
+            JournalFormat::Hledger => "journal",
+            JournalFormat::Beancount => "beancount",
+        }
+    }
+
+    /// Turns the parts of an account name into a valid account for the format. Ledger and hledger
+    /// end an account at 2 spaces while beancount only accepts letters, numbers and dashes in each
+    /// part and every part must start with a capital letter or a number.
+    /// return example: `["Expenses", "eating out"]` -> `Expenses:Eating-out` for beancount
+    fn get_account(&self, parts: &[&str]) -> String {
+        parts
+            .iter()
+            .map(|part| match self {
+                JournalFormat::Ledger | JournalFormat::Hledger => {
+                    let part = part.replace(':', "-");
+                    part.split_whitespace().collect::<Vec<&str>>().join(" ")
+                }
+                JournalFormat::Beancount => {
+                    let part = part
+                        .chars()
+                        .map(|a| if a.is_alphanumeric() { a } else { '-' })
+                        .collect::<String>();
+                    let part = part
+                        .split('-')
+                        .filter(|a| !a.is_empty())
+                        .collect::<Vec<&str>>()
+                        .join("-");
+                    let mut chars = part.chars();
+                    match chars.next() {
+                        Some(first) if first.is_alphabetic() => {
+                            first.to_uppercase().chain(chars).collect()
+                        }
+                        Some(_) => part,
+                        None => "Unnamed".to_string(),
+                    }
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(":")
+    }
+
+    /// Returns the first line of an entry with its date and description
+    fn get_entry_title(&self, date: &str, details: &str) -> String {
+        let details = details.replace(['\n', '\r'], " ");
+        match self {
+            JournalFormat::Ledger | JournalFormat::Hledger => format!("{date} {details}"),
+            JournalFormat::Beancount => format!(
+                "{date} * \"{}\"",
+                details.replace('\\', "\\\\").replace('"', "\\\"")
+            ),
+        }
+    }
+
+    /// Returns a posting line of an entry that moves the amount in cents to the account
+    fn get_posting(&self, account: &str, amount: i64) -> String {
+        let amount = cents_to_amount(amount);
+        match self {
+            JournalFormat::Ledger | JournalFormat::Hledger => format!("    {account}  {amount}"),
+            JournalFormat::Beancount => format!("  {account}  {amount} {BEANCOUNT_COMMODITY}"),
+        }
+    }
+}
+
+/// Writes every transaction between the two dates, both included, as a double-entry journal for ledger,
+/// hledger or beancount. Each Transaction Method is an `Assets:` account and the category of an Income or
+/// an Expense is used for its `Income:` or `Expenses:` account. A Transfer moves the amount between the
+/// two `Assets:` accounts. The balances of the methods before the first date are added as an opening
+/// balance entry. Returns the number of transactions written.
+pub fn export_journal(
+    conn: &Connection,
+    path: &Path,
+    start: NaiveDate,
+    end: NaiveDate,
+    format: JournalFormat,
+) -> Result<usize, Box<dyn Error>> {
+    let all_tx_methods = get_all_tx_methods(conn);
+    let all_txs = get_export_txs(conn, start, end)?;
+    let opening_balances = get_opening_balances(conn, &all_tx_methods, start)?;
+
+    let opening_account = match format {
+        JournalFormat::Beancount => format.get_account(&["Equity", "Opening Balances"]),
+        _ => "Equity:Opening Balances".to_string(),
+    };
+    let mut entries = Vec::new();
+
+    let mut opening_entry = vec![format.get_entry_title(&start.to_string(), "Opening Balances")];
+    for method in &all_tx_methods {
+        let balance = opening_balances.get(method).copied().unwrap_or(0);
+        if balance != 0 {
+            let account = format.get_account(&["Assets", method]);
+            opening_entry.push(format.get_posting(&account, balance));
+        }
+    }
+    if opening_entry.len() > 1 {
+        let total = opening_balances.values().sum::<i64>();
+        opening_entry.push(format.get_posting(&opening_account, -total));
+        entries.push(opening_entry);
+    }
+
+    for tx in &all_txs {
+        // fields : [date, details, tx_method, amount, tx_type, category, tags]
+        let mut entry = vec![format.get_entry_title(&tx.fields[0], &tx.fields[1])];
+
+        for method in &all_tx_methods {
+            let change = tx.changes.get(method).copied().unwrap_or(0);
+            if change != 0 {
+                let account = format.get_account(&["Assets", method]);
+                entry.push(format.get_posting(&account, change));
+            }
+        }
+
+        let root_account = match tx.fields[4].as_str() {
+            "Expense" => "Expenses",
+            "Income" => "Income",
+            _ => "",
+        };
+
+        if !root_account.is_empty() {
+            // each leg of a split transaction has its own category
+            let mut all_legs = get_tx_legs(conn, tx.id_num)
+                .into_iter()
+                .map(|leg| (leg[1].clone(), leg[2].clone()))
+                .collect::<Vec<(String, String)>>();
+            if all_legs.is_empty() {
+                all_legs.push((tx.fields[3].clone(), tx.fields[5].clone()));
+            }
+
+            for (amount, category) in all_legs {
+                let category = if category.is_empty() {
+                    "Uncategorized".to_string()
+                } else {
+                    category
+                };
+                let account = format.get_account(&[root_account, &category]);
+                let cents = amount_to_cents(&amount).map_err(|e| e.to_string())?;
+                let cents = if root_account == "Income" {
+                    -cents
+                } else {
+                    cents
+                };
+                entry.push(format.get_posting(&account, cents));
+            }
+        }
+        entries.push(entry);
+    }
+
+    let mut text = String::new();
+    if format == JournalFormat::Beancount {
+        // beancount needs every account to be opened before it is used
+        let all_accounts = entries
+            .iter()
+            .flat_map(|entry| entry[1..].iter())
+            .filter_map(|posting| posting.split_whitespace().next())
+            .map(|a| a.to_string())
+            .collect::<BTreeSet<String>>();
+        for account in all_accounts {
+            text.push_str(&format!("{start} open {account}\n"));
+        }
+        text.push('\n');
+    }
+
+    let entries = entries
+        .iter()
+        .map(|entry| entry.join("\n"))
+        .collect::<Vec<String>>();
+    text.push_str(&entries.join("\n\n"));
+    text.push('\n');
+
+    fs::write(path, text)?;
+    Ok(all_txs.len())
+}
+
+/// Returns the balance of every Transaction Method right before the given date in cents. The balance
+/// saved for the month before is taken from balance_all and the changes of the transactions of the same
+/// month before the date are added to it.
+fn get_opening_balances(
+    conn: &Connection,
+    all_tx_methods: &Vec<String>,
+    start: NaiveDate,
+) -> sqlResult<HashMap<String, i64>> {
+    let mut balances = get_last_time_balance(
+        conn,
+        start.month0() as usize,
+        start.year() as usize,
+        all_tx_methods,
+    );
+
+    let month_start = start.with_day(1).unwrap_or(start);
+    let mut statement = conn.prepare(
+        "SELECT tx_methods.name, SUM(changes_all.amount) FROM changes_all
+        INNER JOIN tx_all ON tx_all.id_num = changes_all.id_num
+        INNER JOIN tx_methods ON tx_methods.id = changes_all.method_id
+        WHERE tx_all.date >= ? AND tx_all.date < ? GROUP BY tx_methods.name",
+    )?;
+    let rows = statement.query_map([month_start.to_string(), start.to_string()], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+
+    for row in rows {
+        let (method, change) = row?;
+        *balances.entry(method).or_insert(0) += change;
+    }
+    Ok(balances)
+}
+
+/// A transaction to export along with the balance change of each Transaction Method it touched
+struct ExportTx {
+    id_num: i32,
+    fields: Vec<String>,
+    changes: HashMap<String, i64>,
+}
+
+/// Collects the transactions between the two dates in the order they are shown in the app
+fn get_export_txs(conn: &Connection, start: NaiveDate, end: NaiveDate) -> sqlResult<Vec<ExportTx>> {
+    let mut statement = conn.prepare(
+        "SELECT tx_all.id_num, tx_all.date, tx_all.details, tx_all.tx_method, tx_all.amount, tx_all.tx_type,
+        categories.name, (SELECT group_concat(tag, ', ') FROM tx_tags WHERE tx_tags.id_num = tx_all.id_num)
+        FROM tx_all LEFT JOIN categories ON categories.id = tx_all.category_id
+        WHERE tx_all.date BETWEEN ? AND ? ORDER BY tx_all.date, tx_all.id_num",
+    )?;
+    let mut changes_statement = conn.prepare(
+        "SELECT tx_methods.name, changes_all.amount FROM changes_all
+        INNER JOIN tx_methods ON tx_methods.id = changes_all.method_id WHERE changes_all.id_num = ?",
+    )?;
+
+    let rows = statement.query_map([start.to_string(), end.to_string()], |row| {
+        let category: Option<String> = row.get(6)?;
+        let tags: Option<String> = row.get(7)?;
+        Ok((
+            row.get::<_, i32>(0)?,
+            vec![
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                cents_to_amount(row.get(4)?),
+                row.get(5)?,
+                category.unwrap_or_default(),
+                tags.unwrap_or_default(),
+            ],
+        ))
+    })?;
+
+    let mut all_txs = Vec::new();
+    for row in rows {
+        let (id_num, fields) = row?;
+        let changes = changes_statement
+            .query_map([id_num], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<sqlResult<HashMap<String, i64>>>()?;
+        all_txs.push(ExportTx {
+            id_num,
+            fields,
+            changes,
+        });
+    }
+    Ok(all_txs)
+}
+
+/// Joins the fields with commas. A field with a comma, a quote or a new line is put inside quotes
+/// and the quotes inside it are doubled.
+/// return example: `["Lunch", "Food, Drinks"]` -> `Lunch,"Food, Drinks"`
+fn to_csv_line<T: AsRef<str>>(fields: &[T]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
@@ -0,0 +1,564 @@
+use crate::db::{
+    add_new_tx, get_all_categories, get_last_tx_id, parse_ofx, parse_qif, DbSavepoint,
+    StatusChecker,
+};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Result as sqlResult};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+/// Describes how the columns of a bank CSV file turn into transactions. Column numbers start from 1.
+///
+/// name : the name the mapping is saved with. Empty if it is not saved
+///
+/// has_header : whether the first row has the column names and should be skipped
+///
+/// sign_column : the column that tells if a row is an expense or an income. If None, negative
+/// amounts are expenses and positive amounts are incomes
+///
+/// expense_value : the value of the sign column that marks an expense. Example: `Debit`
+///
+/// date_format : the chrono format of the dates in the file. Example: `%d/%m/%Y`
+///
+/// decimal_separator : `.` or `,`. The other one is taken as the thousands separator
+///
+/// tx_method : the Transaction Method every row is added to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportProfile {
+    pub name: String,
+    pub has_header: bool,
+    pub date_column: usize,
+    pub details_column: usize,
+    pub amount_column: usize,
+    pub sign_column: Option<usize>,
+    pub expense_value: String,
+    pub date_format: String,
+    pub decimal_separator: char,
+    pub tx_method: String,
+}
+
+/// The kind of statement file to import along with what is needed to read it
+pub enum ImportSource {
+    /// A CSV file read with the column mapping
+    Csv(ImportProfile),
+    /// An OFX or QFX file added to the given Transaction Method
+    Ofx(String),
+    /// A QIF file where the records without an account are added to the given Transaction Method
+    /// along with the date format of the file
+    Qif(String, String),
+}
+
+/// A row of a statement after it was turned into a transaction. The row is only imported if
+/// problems is empty.
+///
+/// line : the line number of the row in the file
+///
+/// category : the category of a QIF record if it exists in the database. Empty otherwise
+///
+/// fitid : the id the bank gave to an OFX entry. None for the other files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRow {
+    pub line: usize,
+    pub date: String,
+    pub details: String,
+    pub tx_method: String,
+    pub amount: String,
+    pub tx_type: String,
+    pub category: String,
+    pub fitid: Option<String>,
+    pub problems: Vec<String>,
+}
+
+/// The values of a row exactly as they were found in the statement
+///
+/// expense : whether the row was marked as an expense. If None, a negative amount is an expense
+struct RawRow {
+    line: usize,
+    date: String,
+    details: String,
+    tx_method: String,
+    amount: String,
+    expense: Option<bool>,
+}
+
+/// The rows of a statement file checked against the database and ready to be imported
+pub struct StatementImport {
+    pub rows: Vec<ImportRow>,
+}
+
+impl StatusChecker for StatementImport {}
+
+impl StatementImport {
+    /// Parses the CSV text with the given mapping and checks every row through the same verifications
+    /// used on the Add Transaction page. Nothing is saved in the database.
+    pub fn new(
+        conn: &Connection,
+        text: &str,
+        profile: &ImportProfile,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut statement = StatementImport { rows: Vec::new() };
+        let method_problem = statement.check_tx_method(conn, &profile.tx_method)?;
+        let skip_rows = if profile.has_header { 1 } else { 0 };
+
+        for (line, fields) in parse_csv(text).into_iter().skip(skip_rows) {
+            // empty lines are skipped
+            if fields.iter().all(|a| a.trim().is_empty()) {
+                continue;
+            }
+            let mut row = statement.check_csv_row(line, &fields, profile);
+            row.problems.extend(method_problem.clone());
+            statement.rows.push(row);
+        }
+        Ok(statement)
+    }
+
+    /// Parses the STMTTRN entries of an OFX or QFX file and checks every entry the same way as a
+    /// CSV row. Entries with a FITID that was already imported to the method are marked so they
+    /// are never added twice. Nothing is saved in the database.
+    pub fn from_ofx(
+        conn: &Connection,
+        text: &str,
+        tx_method: &str,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut statement = StatementImport { rows: Vec::new() };
+        let method_problem = statement.check_tx_method(conn, tx_method)?;
+        let mut seen_fitids = HashSet::new();
+
+        for entry in parse_ofx(text)? {
+            // OFX amounts are always signed so a negative amount is an expense
+            let raw_row = RawRow {
+                line: entry.line,
+                date: entry.date_posted.chars().take(8).collect(),
+                details: entry.details,
+                tx_method: tx_method.to_string(),
+                amount: entry.amount,
+                expense: None,
+            };
+            let mut row = statement.check_row(raw_row, "%Y%m%d", '.', Vec::new());
+
+            if entry.fitid.is_empty() {
+                row.problems
+                    .push("FITID: The entry does not have a FITID".to_string());
+            } else if !seen_fitids.insert(entry.fitid.clone()) {
+                row.problems
+                    .push(format!("FITID: {} is repeated in the file", entry.fitid));
+            } else if is_imported(conn, tx_method, &entry.fitid)? {
+                row.problems
+                    .push(format!("FITID: {} was already imported", entry.fitid));
+            }
+            row.fitid = Some(entry.fitid);
+            row.problems.extend(method_problem.clone());
+            statement.rows.push(row);
+        }
+        Ok(statement)
+    }
+
+    /// Parses the bank and cash records of a QIF file and checks every record the same way as a CSV
+    /// row. The account of each record is used as its Transaction Method and the records without an
+    /// account are added to the given method. A record with a `[Account]` category becomes a Transfer
+    /// between the two methods. When both accounts of a transfer are in the file, the transfer is only
+    /// added once. Categories that do not exist in the database are left empty.
+    pub fn from_qif(
+        conn: &Connection,
+        text: &str,
+        tx_method: &str,
+        date_format: &str,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut statement = StatementImport { rows: Vec::new() };
+        let all_categories = get_all_categories(conn);
+
+        // every account is checked once
+        let mut method_problems: HashMap<String, Option<String>> = HashMap::new();
+        // the transfers added so far with the account they were written in and their line
+        let mut added_transfers: HashMap<(String, String, String), Vec<(String, usize)>> =
+            HashMap::new();
+
+        for entry in parse_qif(text)? {
+            let account = entry.account.unwrap_or_else(|| tx_method.to_string());
+            let raw_row = RawRow {
+                line: entry.line,
+                date: parse_qif_date(&entry.date, date_format),
+                details: entry.details,
+                tx_method: account.clone(),
+                amount: entry.amount,
+                expense: None,
+            };
+            let mut row = statement.check_row(raw_row, date_format, '.', Vec::new());
+
+            let mut accounts = vec![account.clone()];
+            accounts.extend(entry.transfer.clone());
+            for method in accounts.iter() {
+                if !method_problems.contains_key(method) {
+                    let problem = statement.check_tx_method(conn, method)?;
+                    method_problems.insert(method.clone(), problem);
+                }
+                row.problems.extend(method_problems[method].clone());
+            }
+
+            if all_categories.contains(&entry.category) {
+                row.category = entry.category;
+            }
+
+            if let Some(other_account) = entry.transfer {
+                // money going out of the account moves to the other account
+                row.tx_method = if row.tx_type == "Expense" {
+                    format!("{account} to {other_account}")
+                } else {
+                    format!("{other_account} to {account}")
+                };
+                row.tx_type = "Transfer".to_string();
+                row.category = String::new();
+
+                if account == other_account {
+                    row.problems
+                        .push(format!("Transfer: {account} cannot transfer to itself"));
+                }
+
+                if row.problems.is_empty() {
+                    let key = (row.date.clone(), row.tx_method.clone(), row.amount.clone());
+                    let same_transfers = added_transfers.entry(key).or_default();
+
+                    // the other side of a transfer is written in the other account
+                    match same_transfers.iter().position(|(a, _)| a != &account) {
+                        Some(index) => {
+                            let (_, line) = same_transfers.remove(index);
+                            row.problems
+                                .push(format!("Transfer: Already added from line {line}"));
+                        }
+                        None => same_transfers.push((account, row.line)),
+                    }
+                }
+            }
+            statement.rows.push(row);
+        }
+        Ok(statement)
+    }
+
+    /// Returns the number of rows that can be imported
+    pub fn total_valid(&self) -> usize {
+        self.rows.iter().filter(|a| a.problems.is_empty()).count()
+    }
+
+    /// Checks the Transaction Method rows are added to. A method that was corrected to the best
+    /// match is not accepted.
+    fn check_tx_method(
+        &self,
+        conn: &Connection,
+        tx_method: &str,
+    ) -> Result<Option<String>, Box<dyn Error>> {
+        let mut checked_method = tx_method.to_string();
+        let method_status = self.verify_tx_method(&mut checked_method, conn)?;
+        Ok((!method_status.ends_with("Accepted")).then(|| format!("{method_status}: {tx_method}")))
+    }
+
+    /// Picks the mapped fields of a CSV row and checks them
+    fn check_csv_row(&self, line: usize, fields: &[String], profile: &ImportProfile) -> ImportRow {
+        let mut problems = Vec::new();
+        let mut get_field = |column: usize| match fields.get(column.wrapping_sub(1)) {
+            Some(field) => field.trim().to_string(),
+            None => {
+                problems.push(format!("Column {column} is missing"));
+                String::new()
+            }
+        };
+
+        let raw_row = RawRow {
+            line,
+            date: get_field(profile.date_column),
+            details: get_field(profile.details_column),
+            tx_method: profile.tx_method.clone(),
+            amount: get_field(profile.amount_column),
+            expense: profile
+                .sign_column
+                .map(&mut get_field)
+                .map(|sign| sign.eq_ignore_ascii_case(&profile.expense_value)),
+        };
+        self.check_row(
+            raw_row,
+            &profile.date_format,
+            profile.decimal_separator,
+            problems,
+        )
+    }
+
+    /// Turns the values of a row into a transaction and collects everything that is wrong with it
+    fn check_row(
+        &self,
+        raw_row: RawRow,
+        date_format: &str,
+        decimal_separator: char,
+        mut problems: Vec<String>,
+    ) -> ImportRow {
+        let raw_date = raw_row.date;
+        let raw_amount = raw_row.amount;
+        let details = raw_row.details.trim().to_string();
+
+        let mut date = match NaiveDate::parse_from_str(&raw_date, date_format) {
+            Ok(date) => date.format("%Y-%m-%d").to_string(),
+            Err(_) => {
+                problems.push(format!(
+                    "Date: {raw_date} does not match the format {date_format}"
+                ));
+                String::new()
+            }
+        };
+
+        // spaces and currency symbols are removed and amounts inside brackets are negative
+        let mut amount: String = raw_amount
+            .chars()
+            .filter(|a| a.is_ascii_digit() || ['.', ',', '-', '(', ')'].contains(a))
+            .collect();
+        if amount.starts_with('(') && amount.ends_with(')') {
+            amount = format!("-{}", amount.trim_matches(['(', ')']));
+        }
+        let negative = amount.starts_with('-');
+        let mut amount = amount.trim_start_matches('-').to_string();
+
+        let mut tx_type = match raw_row.expense {
+            Some(true) => "Expense",
+            Some(false) => "Income",
+            None if negative => "Expense",
+            None => "Income",
+        }
+        .to_string();
+
+        if details.is_empty() {
+            problems.push("Details: Details cannot be empty".to_string());
+        }
+        if raw_amount.is_empty() {
+            problems.push("Amount: Amount cannot be empty".to_string());
+        } else if amount.is_empty() {
+            problems.push(format!("Amount: {raw_amount} is not an amount"));
+        } else {
+            match normalize_amount(&amount, decimal_separator) {
+                Some(normalized) => amount = normalized,
+                None => {
+                    problems.push(format!(
+                        "Amount: {raw_amount} does not use {decimal_separator} as the decimal separator"
+                    ));
+                    amount.clear();
+                }
+            }
+        }
+
+        // the verifications correct the values they can so only the accepted ones are kept
+        if !date.is_empty() {
+            let status = self.verify_date(&mut date);
+            add_problem(&mut problems, status);
+        }
+        if !amount.is_empty() {
+            let status = self.verify_amount(&mut amount);
+            add_problem(&mut problems, status);
+        }
+        let status = self.verify_tx_type(&mut tx_type);
+        add_problem(&mut problems, status);
+
+        ImportRow {
+            line: raw_row.line,
+            date,
+            details,
+            tx_method: raw_row.tx_method,
+            amount,
+            tx_type,
+            category: String::new(),
+            fitid: None,
+            problems,
+        }
+    }
+}
+
+/// Writes a QIF date in the given format with the full year. QIF files often use an apostrophe before
+/// the year and only 2 digits for it such as `7/19'22`. The date is returned as it is if it does not
+/// match the format so the problem can be shown.
+fn parse_qif_date(date: &str, date_format: &str) -> String {
+    let date = date.replace('\'', "/").replace(' ', "");
+    let short_format = date_format.replace("%Y", "%y");
+
+    // %Y also reads 2 digits as a year in the first century so the short year is tried first
+    NaiveDate::parse_from_str(&date, &short_format)
+        .or_else(|_| NaiveDate::parse_from_str(&date, date_format))
+        .map(|a| a.format(date_format).to_string())
+        .unwrap_or(date)
+}
+
+/// Removes the thousands separators of an amount and writes it with `.` as the decimal separator. The
+/// thousands separator is the other one of `.` and `,` and must be followed by exactly 3 digits. Returns
+/// None if the separators do not fit, such as `12,50` when `.` is the decimal separator.
+/// return example: `1.234,56` with `,` → `Some("1234.56")`
+fn normalize_amount(amount: &str, decimal_separator: char) -> Option<String> {
+    let thousands_separator = if decimal_separator == ',' { '.' } else { ',' };
+
+    let (whole, fraction) = match amount.split_once(decimal_separator) {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (amount, None),
+    };
+    if fraction.is_some_and(|a| a.contains(['.', ','])) {
+        return None;
+    }
+
+    let groups: Vec<&str> = whole.split(thousands_separator).collect();
+    if groups.len() > 1
+        && (groups[0].is_empty() || groups[0].len() > 3 || groups[1..].iter().any(|a| a.len() != 3))
+    {
+        return None;
+    }
+
+    let whole = groups.concat();
+    match fraction {
+        Some(fraction) => Some(format!("{whole}.{fraction}")),
+        None => Some(whole),
+    }
+}
+
+/// Adds the status of a verification to the problems unless it was accepted
+fn add_problem(problems: &mut Vec<String>, status: Result<String, Box<dyn Error>>) {
+    match status {
+        Ok(status) if status.ends_with("Accepted") => {}
+        Ok(status) => problems.push(status),
+        Err(e) => problems.push(format!("Not a valid value. Error: {e}")),
+    }
+}
+
+/// Adds every row of the import without a problem through add_new_tx. Everything is committed together
+/// or not at all. Each transaction can be undone on its own. The FITID of every added OFX entry is saved
+/// and an entry that was imported in the meantime is skipped. Returns the number of transactions added.
+pub fn import_rows(conn: &Connection, statement: &StatementImport) -> sqlResult<usize> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let mut total_added = 0;
+    for row in statement.rows.iter().filter(|a| a.problems.is_empty()) {
+        if let Some(fitid) = &row.fitid {
+            if is_imported(&sp, &row.tx_method, fitid)? {
+                continue;
+            }
+        }
+
+        add_new_tx(
+            &sp,
+            &row.date,
+            &row.details,
+            &row.tx_method,
+            &row.amount,
+            &row.tx_type,
+            &row.category,
+            "",
+            None,
+        )?;
+
+        if let Some(fitid) = &row.fitid {
+            let id_num = get_last_tx_id(&sp)?;
+            sp.execute(
+                "INSERT INTO import_ids (method_id, fitid, id_num)
+                VALUES ((SELECT id FROM tx_methods WHERE name = ?), ?, ?)",
+                params![row.tx_method, fitid, id_num],
+            )?;
+        }
+        total_added += 1;
+    }
+
+    sp.commit()?;
+    Ok(total_added)
+}
+
+/// Checks whether an OFX entry with the FITID was imported to the Transaction Method before. The FITID
+/// is kept after the transaction is deleted so a removed entry does not come back on the next import.
+pub fn is_imported(conn: &Connection, tx_method: &str, fitid: &str) -> sqlResult<bool> {
+    let found = conn
+        .query_row(
+            "SELECT 1 FROM import_ids
+            WHERE method_id = (SELECT id FROM tx_methods WHERE name = ?) AND fitid = ?",
+            [tx_method, fitid],
+            |_| Ok(()),
+        )
+        .optional()?;
+    Ok(found.is_some())
+}
+
+/// Saves the mapping with its name. A mapping with the same name is replaced.
+pub fn save_import_profile(conn: &Connection, profile: &ImportProfile) -> sqlResult<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO import_profiles (name, has_header, date_column, details_column,
+        amount_column, sign_column, expense_value, date_format, decimal_separator, tx_method)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        params![
+            profile.name,
+            profile.has_header,
+            profile.date_column,
+            profile.details_column,
+            profile.amount_column,
+            profile.sign_column,
+            profile.expense_value,
+            profile.date_format,
+            profile.decimal_separator.to_string(),
+            profile.tx_method,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Returns all the saved mappings sorted by their name
+pub fn get_import_profiles(conn: &Connection) -> sqlResult<Vec<ImportProfile>> {
+    let mut statement = conn.prepare(
+        "SELECT name, has_header, date_column, details_column, amount_column, sign_column,
+        expense_value, date_format, decimal_separator, tx_method FROM import_profiles ORDER BY name",
+    )?;
+
+    let rows = statement.query_map([], |row| {
+        Ok(ImportProfile {
+            name: row.get(0)?,
+            has_header: row.get(1)?,
+            date_column: row.get(2)?,
+            details_column: row.get(3)?,
+            amount_column: row.get(4)?,
+            sign_column: row.get(5)?,
+            expense_value: row.get(6)?,
+            date_format: row.get(7)?,
+            decimal_separator: row.get::<_, String>(8)?.chars().next().unwrap_or('.'),
+            tx_method: row.get(9)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Splits CSV text into rows of fields along with the line number each row starts on. Fields can be
+/// inside quotes to contain commas, new lines or doubled quotes.
+/// return example: `Lunch,"Food, Drinks"` -> `[(1, ["Lunch", "Food, Drinks"])]`
+pub fn parse_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    let mut all_rows = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                fields.push(std::mem::take(&mut field));
+                all_rows.push((row_line, std::mem::take(&mut fields)));
+                line += 1;
+                row_line = line;
+            }
+            '\n' => {
+                field.push(c);
+                line += 1;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        all_rows.push((row_line, fields));
+    }
+    all_rows
+}
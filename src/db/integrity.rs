@@ -0,0 +1,205 @@
+use crate::db::{
+    amount_to_cents, cents_to_amount, get_all_tx_methods, get_balance_id, get_schema_version,
+    SCHEMA_VERSION,
+};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{Connection, Result as sqlResult};
+use std::collections::HashSet;
+
+/// The tables and their columns that keep amounts as INTEGER cents
+const AMOUNT_COLUMNS: [(&str, &str); 6] = [
+    ("tx_all", "amount"),
+    ("changes_all", "amount"),
+    ("balance_all", "balance"),
+    ("tx_legs", "amount"),
+    ("recurring_txs", "amount"),
+    ("budgets", "amount"),
+];
+
+/// Checks the database for problems without changing anything. Runs the SQLite integrity and foreign key
+/// checks and goes through every transaction to find unknown Transaction Methods, transfers that do not
+/// have two known methods, split transactions that do not match their legs and dates that are invalid or
+/// do not have a monthly balance row. Changes without a transaction, amounts that are not saved as
+/// cents and a Transaction Method named Split are reported as well. A database that is not on the schema
+/// version of this app is reported without the checks that depend on the schema. Returns a line for every
+/// problem found.
+/// return example: `["Transaction 5 uses Bank which is not a Transaction Method"]`
+pub fn verify_db(conn: &Connection) -> sqlResult<Vec<String>> {
+    let mut problems = Vec::new();
+
+    let mut statement = conn.prepare("PRAGMA integrity_check")?;
+    for row in statement.query_map([], |row| row.get::<_, String>(0))? {
+        let result = row?;
+        if result != "ok" {
+            problems.push(format!("SQLite integrity check: {result}"));
+        }
+    }
+
+    // the tables may not match the queries below on any other version
+    let db_version = get_schema_version(conn)?;
+    if db_version < SCHEMA_VERSION {
+        problems.push(format!(
+            "The database schema version is {db_version} but this version of the app expects {SCHEMA_VERSION}. Open the database once to migrate it and verify again"
+        ));
+        return Ok(problems);
+    } else if db_version > SCHEMA_VERSION {
+        problems.push(format!(
+            "The database schema version is {db_version} but this version of the app supports up to {SCHEMA_VERSION}. Update the app to verify this database"
+        ));
+        return Ok(problems);
+    }
+
+    let mut statement = conn.prepare("PRAGMA foreign_key_check")?;
+    for row in statement.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })? {
+        let (table, row_id, parent) = row?;
+        // changes without a transaction are reported with the transaction id_num below
+        if table == "changes_all" && parent == "tx_all" {
+            continue;
+        }
+        problems.push(format!(
+            "Row {row_id} of {table} refers to a missing row in {parent}"
+        ));
+    }
+
+    for (table, column) in AMOUNT_COLUMNS {
+        let mut statement = conn.prepare(&format!(
+            "SELECT rowid, CAST({column} AS TEXT) FROM {table} WHERE typeof({column}) != 'integer'"
+        ))?;
+        for row in statement.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            ))
+        })? {
+            let (row_id, amount) = row?;
+            let problem = match amount_to_cents(&amount) {
+                Ok(cents) => format!(
+                    "Row {row_id} of {table} has {column} {amount} that is not saved as {} in cents",
+                    cents_to_amount(cents)
+                ),
+                Err(_) => {
+                    format!("Row {row_id} of {table} has {column} {amount:?} that is not a number")
+                }
+            };
+            problems.push(problem);
+        }
+    }
+
+    // older versions allowed a Transaction Method with the name split transactions are saved with
+    if get_all_tx_methods(conn).contains(&"Split".to_string()) {
+        problems.push(
+            "Split is a Transaction Method but the name is used by split transactions. Rename it"
+                .to_string(),
+        );
+    }
+
+    problems.extend(check_txs(conn)?);
+
+    let mut statement = conn.prepare(
+        "SELECT DISTINCT changes_all.id_num FROM changes_all
+        LEFT JOIN tx_all ON tx_all.id_num = changes_all.id_num
+        WHERE tx_all.id_num IS NULL ORDER BY changes_all.id_num",
+    )?;
+    for row in statement.query_map([], |row| row.get::<_, i32>(0))? {
+        problems.push(format!(
+            "Changes of transaction {} were found but the transaction does not exist",
+            row?
+        ));
+    }
+
+    Ok(problems)
+}
+
+/// Goes through every transaction and returns the problems of their Transaction Method, type and date
+fn check_txs(conn: &Connection) -> sqlResult<Vec<String>> {
+    let all_tx_methods: HashSet<String> = get_all_tx_methods(conn).into_iter().collect();
+    let balance_ids = get_balance_ids(conn)?;
+
+    let mut problems = Vec::new();
+
+    let mut statement = conn.prepare(
+        "SELECT tx_all.id_num, tx_all.date, tx_all.tx_method, tx_all.tx_type, tx_all.amount,
+        COUNT(tx_legs.id_num), COALESCE(SUM(tx_legs.amount), 0)
+        FROM tx_all LEFT JOIN tx_legs ON tx_legs.id_num = tx_all.id_num
+        GROUP BY tx_all.id_num ORDER BY tx_all.id_num",
+    )?;
+    let rows = statement.query_map([], |row| {
+        Ok((
+            row.get::<_, i32>(0)?,
+            row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+            row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            row.get::<_, Option<i64>>(4).ok().flatten(),
+            row.get::<_, i32>(5)?,
+            row.get::<_, i64>(6)?,
+        ))
+    })?;
+
+    for row in rows {
+        let (id_num, date, tx_method, tx_type, amount, total_legs, legs_amount) = row?;
+
+        match tx_type.as_str() {
+            "Transfer" => {
+                let from_to = tx_method.split(" to ").collect::<Vec<&str>>();
+                if from_to.len() != 2 || from_to[0] == from_to[1] {
+                    problems.push(format!(
+                        "Transfer {id_num} has {tx_method} which is not in the `From to To` form"
+                    ));
+                } else {
+                    for method in from_to.iter().filter(|a| !all_tx_methods.contains(**a)) {
+                        problems.push(format!(
+                            "Transfer {id_num} uses {method} which is not a Transaction Method"
+                        ));
+                    }
+                }
+            }
+            // split transactions are the ones with legs whatever their Transaction Method is
+            "Expense" | "Income" if total_legs > 0 => {
+                if amount.is_some_and(|amount| amount != legs_amount) {
+                    problems.push(format!(
+                        "Split transaction {id_num} does not match the total of its legs {}",
+                        cents_to_amount(legs_amount)
+                    ));
+                }
+            }
+            "Expense" | "Income" => {
+                if !all_tx_methods.contains(&tx_method) {
+                    problems.push(format!(
+                        "Transaction {id_num} uses {tx_method} which is not a Transaction Method"
+                    ));
+                }
+            }
+            _ => problems.push(format!(
+                "Transaction {id_num} has {tx_type:?} which is not a Transaction Type"
+            )),
+        }
+
+        match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+            Ok(parsed) => {
+                let balance_id = get_balance_id(parsed.month() as usize, parsed.year() as usize);
+                if !balance_ids.contains(&balance_id) {
+                    problems.push(format!(
+                        "Transaction {id_num} is on {date} which does not have a monthly balance row"
+                    ));
+                }
+            }
+            Err(_) => problems.push(format!(
+                "Transaction {id_num} has {date:?} which is not a valid date"
+            )),
+        }
+    }
+    Ok(problems)
+}
+
+/// Returns every monthly balance row id_num
+fn get_balance_ids(conn: &Connection) -> sqlResult<HashSet<i32>> {
+    let mut statement = conn.prepare("SELECT DISTINCT id_num FROM balance_all WHERE id_num > 0")?;
+    let balance_ids = statement.query_map([], |row| row.get(0))?.collect();
+    balance_ids
+}
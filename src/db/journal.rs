@@ -0,0 +1,401 @@
+use crate::db::manage_db::{
+    archive_method, insert_tx_methods, remove_tx_method, rename_method, restore_method,
+};
+use crate::db::trash::{trash_tx, untrash_tx};
+use crate::db::tx_manager::{insert_split_tx, insert_tx, remove_tx};
+use crate::db::{cents_to_amount, get_all_tx_methods, get_tx_legs, DbSavepoint};
+use chrono::Local;
+use rusqlite::{params, Connection, Error as sqlError, OptionalExtension, Result as sqlResult};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// The number of changes kept in the journal. The oldest changes are removed once this is passed.
+const JOURNAL_LIMIT: i32 = 500;
+
+/// A transaction the way it was saved in the database. The amounts are kept the same way they are
+/// given to add_new_tx and each leg of a split transaction is `[tx_method, amount, category]`.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct TxSnapshot {
+    pub(crate) date: String,
+    pub(crate) details: String,
+    pub(crate) tx_method: String,
+    pub(crate) amount: String,
+    pub(crate) tx_type: String,
+    category: String,
+    tags: String,
+    legs: Vec<Vec<String>>,
+}
+
+impl TxSnapshot {
+    /// Collects the transaction with the given id_num. Returns None if it does not exist.
+    pub(crate) fn get(conn: &Connection, id_num: i32) -> sqlResult<Option<Self>> {
+        let tx = conn
+            .query_row(
+                "SELECT date, details, tx_method, amount, tx_type, categories.name FROM tx_all
+                LEFT JOIN categories ON categories.id = tx_all.category_id WHERE id_num = ?",
+                [id_num],
+                |row| {
+                    let category: Option<String> = row.get(5)?;
+                    Ok(TxSnapshot {
+                        date: row.get(0)?,
+                        details: row.get(1)?,
+                        tx_method: row.get(2)?,
+                        amount: cents_to_amount(row.get(3)?),
+                        tx_type: row.get(4)?,
+                        category: category.unwrap_or_default(),
+                        tags: String::new(),
+                        legs: Vec::new(),
+                    })
+                },
+            )
+            .optional()?;
+
+        match tx {
+            Some(mut tx) => {
+                let mut statement =
+                    conn.prepare("SELECT tag FROM tx_tags WHERE id_num = ? ORDER BY tag")?;
+                tx.tags = statement
+                    .query_map([id_num], |row| row.get(0))?
+                    .collect::<sqlResult<Vec<String>>>()?
+                    .join(", ");
+                tx.legs = get_tx_legs(conn, id_num);
+                Ok(Some(tx))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Saves the transaction again with the given id_num through the same functions that added it.
+    /// Fails if any Transaction Method it used no longer exists.
+    pub(crate) fn insert(&self, conn: &Connection, id_num: i32) -> Result<(), Box<dyn Error>> {
+        let all_tx_methods = get_all_tx_methods(conn);
+
+        if let Some(method) = self
+            .get_used_methods()
+            .iter()
+            .find(|method| !all_tx_methods.iter().any(|a| a == *method))
+        {
+            return Err(format!(
+                "{method} is no longer a Transaction Method so transaction {id_num} cannot be saved again"
+            )
+            .into());
+        }
+
+        let id_num = id_num.to_string();
+        if self.legs.is_empty() {
+            insert_tx(
+                conn,
+                &self.date,
+                &self.details,
+                &self.tx_method,
+                &self.amount,
+                &self.tx_type,
+                &self.category,
+                &self.tags,
+                Some(&id_num),
+            )?;
+        } else {
+            insert_split_tx(
+                conn,
+                &self.date,
+                &self.details,
+                &self.tx_type,
+                &self.legs,
+                &self.tags,
+                Some(&id_num),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the Transaction Methods the transaction uses. Both methods of a transfer and the
+    /// method of every leg of a split transaction are included.
+    pub(crate) fn get_used_methods(&self) -> Vec<&str> {
+        if self.legs.is_empty() {
+            self.tx_method.split(" to ").collect()
+        } else {
+            self.legs.iter().map(|leg| leg[0].as_str()).collect()
+        }
+    }
+
+    /// Replaces the Transaction Method on either side of a transfer, as the method of the transaction
+    /// and in the legs. Returns whether anything was replaced.
+    pub(crate) fn rename_method(&mut self, old_method: &str, new_method: &str) -> bool {
+        let mut renamed = false;
+
+        if self.tx_type == "Transfer" {
+            let tx_method = self
+                .tx_method
+                .split(" to ")
+                .map(|method| {
+                    if method == old_method {
+                        new_method
+                    } else {
+                        method
+                    }
+                })
+                .collect::<Vec<&str>>()
+                .join(" to ");
+            renamed = tx_method != self.tx_method;
+            self.tx_method = tx_method;
+        } else if self.legs.is_empty() && self.tx_method == old_method {
+            self.tx_method = new_method.to_string();
+            renamed = true;
+        }
+
+        for leg in self.legs.iter_mut().filter(|leg| leg[0] == old_method) {
+            leg[0] = new_method.to_string();
+            renamed = true;
+        }
+        renamed
+    }
+
+    /// Replaces the category of the transaction and of the legs. An empty new category removes it.
+    /// Returns whether anything was replaced.
+    pub(crate) fn rename_category(&mut self, old_category: &str, new_category: &str) -> bool {
+        let mut renamed = false;
+
+        if self.category == old_category {
+            self.category = new_category.to_string();
+            renamed = true;
+        }
+
+        for leg in self.legs.iter_mut().filter(|leg| leg[2] == old_category) {
+            leg[2] = new_category.to_string();
+            renamed = true;
+        }
+        renamed
+    }
+}
+
+/// A change saved in the journal along with the data that is needed to reverse it
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum JournalChange {
+    /// A transaction that was added or edited. before is None for an added transaction.
+    Tx {
+        id_num: i32,
+        before: Option<Box<TxSnapshot>>,
+        after: Box<TxSnapshot>,
+    },
+    /// A transaction that was moved to the trash
+    DeleteTx {
+        id_num: i32,
+    },
+    /// A transaction that was restored from the trash
+    RestoreTx {
+        id_num: i32,
+    },
+    AddMethods {
+        names: Vec<String>,
+    },
+    RenameMethod {
+        old_name: String,
+        new_name: String,
+    },
+    ArchiveMethod {
+        name: String,
+    },
+    RestoreMethod {
+        name: String,
+    },
+    DeleteMethod {
+        name: String,
+        archived: bool,
+    },
+}
+
+impl JournalChange {
+    /// Does the change again or reverses it through the same functions that did it the first time
+    fn apply(&self, conn: &Connection, reverse: bool) -> Result<(), Box<dyn Error>> {
+        match self {
+            JournalChange::Tx { id_num, before, .. } if reverse => {
+                remove_tx(conn, *id_num as usize)?;
+                if let Some(tx) = before {
+                    tx.insert(conn, *id_num)?;
+                }
+            }
+            JournalChange::Tx {
+                id_num,
+                before,
+                after,
+            } => {
+                if before.is_some() {
+                    remove_tx(conn, *id_num as usize)?;
+                }
+                after.insert(conn, *id_num)?;
+            }
+            JournalChange::DeleteTx { id_num } if reverse => untrash_tx(conn, *id_num)?,
+            JournalChange::DeleteTx { id_num } => {
+                trash_tx(conn, *id_num, Local::today().naive_local())?
+            }
+            JournalChange::RestoreTx { id_num } if reverse => {
+                trash_tx(conn, *id_num, Local::today().naive_local())?
+            }
+            JournalChange::RestoreTx { id_num } => untrash_tx(conn, *id_num)?,
+            JournalChange::AddMethods { names } if reverse => {
+                for name in names.iter().rev() {
+                    remove_tx_method(conn, name)?;
+                }
+            }
+            JournalChange::AddMethods { names } => insert_tx_methods(conn, names)?,
+            JournalChange::RenameMethod { old_name, new_name } if reverse => {
+                rename_method(conn, new_name, old_name)?
+            }
+            JournalChange::RenameMethod { old_name, new_name } => {
+                rename_method(conn, old_name, new_name)?
+            }
+            JournalChange::ArchiveMethod { name } if reverse => restore_method(conn, name)?,
+            JournalChange::ArchiveMethod { name } => archive_method(conn, name)?,
+            JournalChange::RestoreMethod { name } if reverse => archive_method(conn, name)?,
+            JournalChange::RestoreMethod { name } => restore_method(conn, name)?,
+            JournalChange::DeleteMethod { name, archived } if reverse => {
+                insert_tx_methods(conn, &[name.to_string()])?;
+                if *archived {
+                    archive_method(conn, name)?;
+                }
+            }
+            JournalChange::DeleteMethod { name, .. } => remove_tx_method(conn, name)?,
+        }
+        Ok(())
+    }
+
+    /// Describes the change to show after it was undone or redone
+    /// return example: `deleting transaction 5`
+    fn describe(&self) -> String {
+        match self {
+            JournalChange::Tx {
+                id_num,
+                before: None,
+                ..
+            } => format!("adding transaction {id_num}"),
+            JournalChange::Tx { id_num, .. } => format!("editing transaction {id_num}"),
+            JournalChange::DeleteTx { id_num } => format!("deleting transaction {id_num}"),
+            JournalChange::RestoreTx { id_num } => format!("restoring transaction {id_num}"),
+            JournalChange::AddMethods { names } => {
+                format!("adding Transaction Methods {}", names.join(", "))
+            }
+            JournalChange::RenameMethod { old_name, new_name } => {
+                format!("renaming Transaction Method {old_name} to {new_name}")
+            }
+            JournalChange::ArchiveMethod { name } => format!("archiving Transaction Method {name}"),
+            JournalChange::RestoreMethod { name } => format!("restoring Transaction Method {name}"),
+            JournalChange::DeleteMethod { name, .. } => {
+                format!("deleting Transaction Method {name}")
+            }
+        }
+    }
+}
+
+/// Saves a change in the journal. The changes that were undone can no longer be redone after a new
+/// change so they are removed along with the oldest changes past the journal limit.
+pub(crate) fn record_change(conn: &Connection, change: &JournalChange) -> sqlResult<()> {
+    let change =
+        serde_json::to_string(change).map_err(|e| sqlError::ToSqlConversionFailure(Box::new(e)))?;
+
+    conn.execute("DELETE FROM journal WHERE undone = 1", [])?;
+    conn.execute("INSERT INTO journal (change) VALUES (?)", [change])?;
+    conn.execute(
+        "DELETE FROM journal WHERE id <= last_insert_rowid() - ?",
+        [JOURNAL_LIMIT],
+    )?;
+    Ok(())
+}
+
+/// Saves the change of a transaction in the journal. before is the transaction before the change
+/// and the transaction that is currently saved with the id_num is taken as the result.
+pub(crate) fn record_tx_change(
+    conn: &Connection,
+    id_num: i32,
+    before: Option<TxSnapshot>,
+) -> sqlResult<()> {
+    let after = TxSnapshot::get(conn, id_num)?.ok_or(sqlError::QueryReturnedNoRows)?;
+    record_change(
+        conn,
+        &JournalChange::Tx {
+            id_num,
+            before: before.map(Box::new),
+            after: Box::new(after),
+        },
+    )
+}
+
+/// Runs the update on every transaction saved in the journal and saves the entries it changed. Keeps the
+/// journal on the current category names as renaming or deleting a category is not part of the journal.
+/// Entries that cannot be read are left as they are.
+pub(crate) fn update_journal_txs<F>(conn: &Connection, update: F) -> sqlResult<()>
+where
+    F: Fn(&mut TxSnapshot) -> bool,
+{
+    let mut statement = conn.prepare("SELECT id, change FROM journal")?;
+    let all_entries = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<sqlResult<Vec<(i32, String)>>>()?;
+
+    for (id, change) in all_entries {
+        let mut change: JournalChange = match serde_json::from_str(&change) {
+            Ok(change) => change,
+            Err(_) => continue,
+        };
+
+        if let JournalChange::Tx { before, after, .. } = &mut change {
+            let before_updated = before.as_deref_mut().is_some_and(&update);
+            let after_updated = update(after);
+
+            if before_updated || after_updated {
+                let change = serde_json::to_string(&change)
+                    .map_err(|e| sqlError::ToSqlConversionFailure(Box::new(e)))?;
+                conn.execute(
+                    "UPDATE journal SET change = ? WHERE id = ?",
+                    params![change, id],
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reverses the latest change in the journal that was not undone yet. Everything is committed together
+/// or not at all. Returns what was undone or None if there is nothing to undo.
+/// return example: `Some("Undid deleting transaction 5")`
+pub fn undo_last_change(conn: &Connection) -> Result<Option<String>, Box<dyn Error>> {
+    replay_change(conn, true)
+}
+
+/// Does the latest undone change again. Only possible until a new change is made.
+/// Returns what was redone or None if there is nothing to redo.
+/// return example: `Some("Redid deleting transaction 5")`
+pub fn redo_last_change(conn: &Connection) -> Result<Option<String>, Box<dyn Error>> {
+    replay_change(conn, false)
+}
+
+/// Takes the change to undo or redo from the journal, applies it and marks it
+fn replay_change(conn: &Connection, undo: bool) -> Result<Option<String>, Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let query = if undo {
+        "SELECT id, change FROM journal WHERE undone = 0 ORDER BY id DESC LIMIT 1"
+    } else {
+        "SELECT id, change FROM journal WHERE undone = 1 ORDER BY id LIMIT 1"
+    };
+
+    let entry: Option<(i32, String)> = sp
+        .query_row(query, [], |row| Ok((row.get(0)?, row.get(1)?)))
+        .optional()?;
+
+    let (id, change) = match entry {
+        Some(entry) => entry,
+        None => return Ok(None),
+    };
+
+    let change: JournalChange = serde_json::from_str(&change)?;
+    change.apply(&sp, undo)?;
+
+    sp.execute(
+        "UPDATE journal SET undone = ? WHERE id = ?",
+        [undo as i32, id],
+    )?;
+    sp.commit()?;
+
+    let action = if undo { "Undid" } else { "Redid" };
+    Ok(Some(format!("{action} {}", change.describe())))
+}
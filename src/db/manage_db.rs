@@ -1,121 +1,566 @@
-use rusqlite::{Connection, Result};
+use crate::db::journal::{record_change, update_journal_txs, JournalChange};
+use crate::db::trash::{count_trash_txs_using, update_trash_txs};
+use crate::db::{
+    add_new_year, cents_to_amount, get_active_tx_methods, get_all_categories, get_all_tx_methods,
+    get_archived_tx_methods, get_last_balances, set_schema_version, DbSavepoint,
+};
+use chrono::prelude::{Datelike, Local};
+use rusqlite::{params, Connection, Result};
+use std::error::Error;
+
+/// Opens the database at the given path with the foreign keys turned on. The app keeps this
+/// connection open and shares it with every database function.
+pub fn open_db(file_name: &str) -> Result<Connection> {
+    let conn = Connection::open(file_name)?;
+    conn.execute_batch("PRAGMA foreign_keys = ON")?;
+    Ok(conn)
+}
 
 /// If the local database is not found, this is executed to create the initial database
 /// with the provided transaction methods.
-pub fn create_db(file_name: &str, tx_methods: Vec<String>) -> Result<()> {
-    let months = vec![
-        "January",
-        "February",
-        "March",
-        "April",
-        "May",
-        "June",
-        "July",
-        "August",
-        "September",
-        "October",
-        "November",
-        "December",
-    ];
-    let years = vec!["2022", "2023", "2024", "2025"];
-
+pub fn create_db(conn: &Connection, tx_methods: Vec<String>) -> Result<()> {
     // add a save point to reverse commits if failed
-    let mut conn = Connection::open(file_name)?;
-    let sp = conn.savepoint().unwrap();
+    let sp = DbSavepoint::new(conn)?;
 
-    sp.execute(
-        "CREATE TABLE tx_all (
+    create_tables(&sp)?;
+
+    // id_num 0 is the absolute final balance. Monthly rows are added once a year is needed,
+    // starting with the current one.
+    for i in &tx_methods {
+        sp.execute("INSERT INTO tx_methods (name) VALUES (?)", [i])?;
+        let method_id = sp.last_insert_rowid();
+        sp.execute(
+            "INSERT INTO balance_all (id_num, method_id) VALUES (0, ?)",
+            [method_id],
+        )?;
+    }
+
+    add_new_year(&sp, Local::today().year() as usize)?;
+    set_schema_version(&sp)?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// Creates the tx_methods, categories, tx_all, tx_tags, changes_all, balance_all, recurring_txs, budgets,
+/// tx_legs, journal, trash, import_profiles and import_ids tables along with their indexes.
+/// Balances and changes are saved as one row per Transaction Method keyed by the method id.
+/// All amounts are saved as INTEGER cents.
+fn create_tables(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE tx_methods (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        archived INTEGER NOT NULL DEFAULT 0
+    );
+
+    CREATE TABLE categories (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE
+    );
+
+    CREATE TABLE tx_all (
         date TEXT,
         details TEXT,
         tx_method TEXT,
-        amount TEXT,
+        amount INTEGER,
         tx_type TEXT,
-        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT
-    );",
-        [],
-    )?;
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL
+    );
 
-    let mut query = "CREATE TABLE changes_all (
-        date TEXT,
-        id_num INTEGER NOT NULL PRIMARY KEY,"
-        .to_string();
-    // we don't know how many tx methods there are, so we have to loop through them
-    for i in &tx_methods {
-        query.push_str(&format!(r#""{i}" TEXT DEFAULT 0.00,"#))
-    }
-    query.push_str(
-        "CONSTRAINT changes_all_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
-);",
+    CREATE TABLE tx_tags (
+        id_num INTEGER NOT NULL,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (id_num, tag),
+        CONSTRAINT tx_tags_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
     );
 
-    sp.execute(&query, [])?;
+    CREATE TABLE changes_all (
+        id_num INTEGER NOT NULL,
+        method_id INTEGER NOT NULL,
+        amount INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (id_num, method_id),
+        CONSTRAINT changes_all_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE,
+        CONSTRAINT changes_all_method_FK FOREIGN KEY (method_id) REFERENCES tx_methods(id) ON DELETE CASCADE
+    );
 
-    let mut query = "CREATE TABLE balance_all (
-        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT"
-        .to_string();
-    for i in &tx_methods {
-        query.push_str(&format!(r#","{i}" TEXT DEFAULT 0.00"#))
+    CREATE TABLE balance_all (
+        id_num INTEGER NOT NULL,
+        method_id INTEGER NOT NULL,
+        balance INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (id_num, method_id),
+        CONSTRAINT balance_all_method_FK FOREIGN KEY (method_id) REFERENCES tx_methods(id) ON DELETE CASCADE
+    );
+
+    CREATE TABLE recurring_txs (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        details TEXT NOT NULL,
+        tx_method TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        tx_type TEXT NOT NULL,
+        category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL,
+        tags TEXT NOT NULL DEFAULT '',
+        rule TEXT NOT NULL CHECK (rule IN ('Monthly', 'Weekly', 'Yearly')),
+        start_date TEXT NOT NULL,
+        end_date TEXT,
+        last_date TEXT,
+        paused INTEGER NOT NULL DEFAULT 0
+    );
+
+    CREATE TABLE budgets (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        category_id INTEGER UNIQUE REFERENCES categories(id) ON DELETE CASCADE,
+        method_id INTEGER UNIQUE REFERENCES tx_methods(id) ON DELETE CASCADE,
+        amount INTEGER NOT NULL,
+        rollover INTEGER NOT NULL DEFAULT 0,
+        start_month TEXT NOT NULL,
+        CHECK ((category_id IS NULL) != (method_id IS NULL))
+    );
+
+    CREATE TABLE tx_legs (
+        id_num INTEGER NOT NULL,
+        leg_num INTEGER NOT NULL,
+        method_id INTEGER NOT NULL REFERENCES tx_methods(id),
+        amount INTEGER NOT NULL,
+        category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL,
+        PRIMARY KEY (id_num, leg_num),
+        CONSTRAINT tx_legs_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );
+
+    CREATE TABLE journal (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        change TEXT NOT NULL,
+        undone INTEGER NOT NULL DEFAULT 0
+    );
+
+    CREATE TABLE trash (
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        deleted_at TEXT NOT NULL,
+        tx TEXT NOT NULL
+    );
+
+    CREATE TABLE import_profiles (
+        name TEXT NOT NULL PRIMARY KEY,
+        has_header INTEGER NOT NULL,
+        date_column INTEGER NOT NULL,
+        details_column INTEGER NOT NULL,
+        amount_column INTEGER NOT NULL,
+        sign_column INTEGER,
+        expense_value TEXT NOT NULL,
+        date_format TEXT NOT NULL,
+        tx_method TEXT NOT NULL,
+        decimal_separator TEXT NOT NULL DEFAULT '.'
+    );
+
+    CREATE TABLE import_ids (
+        method_id INTEGER NOT NULL REFERENCES tx_methods(id) ON DELETE CASCADE,
+        fitid TEXT NOT NULL,
+        id_num INTEGER NOT NULL,
+        PRIMARY KEY (method_id, fitid)
+    );
+
+    CREATE UNIQUE INDEX all_tx_date_IDX ON tx_all (id_num);
+    CREATE INDEX tx_all_date_IDX ON tx_all (date);",
+    )?;
+    Ok(())
+}
+
+/// This function is used for adding new Transaction Methods to the database. Each new method
+/// gets a zero balance row for every month that has been added so far and for the final balance.
+/// Takes vector with transaction method names and commits them. Refused if any of the names cannot be
+/// a Transaction Method name.
+pub fn add_new_tx_methods(
+    conn: &Connection,
+    tx_methods: Vec<String>,
+) -> Result<(), Box<dyn Error>> {
+    // add a save point to reverse commits if failed
+    let sp = DbSavepoint::new(conn)?;
+
+    insert_tx_methods(&sp, &tx_methods)?;
+    record_change(&sp, &JournalChange::AddMethods { names: tx_methods })?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Does the actual work of add_new_tx_methods inside the given connection or savepoint without committing
+pub(crate) fn insert_tx_methods(
+    conn: &Connection,
+    tx_methods: &[String],
+) -> Result<(), Box<dyn Error>> {
+    for i in tx_methods {
+        check_method_name(i, &get_all_tx_methods(conn))?;
+        conn.execute("INSERT INTO tx_methods (name) VALUES (?)", [i])?;
+        let method_id = conn.last_insert_rowid();
+        conn.execute(
+            "INSERT INTO balance_all (id_num, method_id)
+            SELECT DISTINCT id_num, ? FROM balance_all",
+            [method_id],
+        )?;
     }
-    query.push_str(");");
+    Ok(())
+}
 
-    sp.execute(&query, [])?;
+/// Renames a Transaction Method. The method is renamed in the tx_methods table and every
+/// transaction and recurring transaction saved with the method is updated, including the `A to B`
+/// form of the transfers, the transactions in the trash and the saved import mappings. Refused if the
+/// new name cannot be a Transaction Method name. Everything is committed together or not at all.
+pub fn rename_tx_method(
+    conn: &Connection,
+    old_method: &str,
+    new_method: &str,
+) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
 
-    sp.execute(
-        "CREATE UNIQUE INDEX all_tx_date_IDX ON tx_all (id_num);",
-        [],
-    )
-    .unwrap();
+    rename_method(&sp, old_method, new_method)?;
+    record_change(
+        &sp,
+        &JournalChange::RenameMethod {
+            old_name: old_method.to_string(),
+            new_name: new_method.to_string(),
+        },
+    )?;
 
-    sp.execute(
-        "CREATE UNIQUE INDEX changes_all_date_IDX ON changes_all (id_num);",
-        [],
-    )
-    .unwrap();
+    sp.commit()?;
+    Ok(())
+}
 
-    sp.execute(
-        "CREATE UNIQUE INDEX balance_all_id_num_IDX ON balance_all (id_num);",
-        [],
-    )
-    .unwrap();
+/// Does the actual work of rename_tx_method inside the given connection or savepoint without committing
+pub(crate) fn rename_method(
+    conn: &Connection,
+    old_method: &str,
+    new_method: &str,
+) -> Result<(), Box<dyn Error>> {
+    let all_methods = get_all_tx_methods(conn);
+    if !all_methods.contains(&old_method.to_string()) {
+        return Err(format!("{old_method} is not a Transaction Method").into());
+    }
+    check_method_name(new_method, &all_methods)?;
+
+    conn.execute(
+        "UPDATE tx_methods SET name = ? WHERE name = ?",
+        [new_method, old_method],
+    )?;
+
+    // transactions and recurring transactions save the method name the same way
+    rename_saved_methods(conn, "tx_all", "id_num", old_method, new_method)?;
+    rename_saved_methods(conn, "recurring_txs", "id", old_method, new_method)?;
+    conn.execute(
+        "UPDATE import_profiles SET tx_method = ? WHERE tx_method = ?",
+        [new_method, old_method],
+    )?;
+    update_trash_txs(conn, |tx| tx.rename_method(old_method, new_method))?;
+    Ok(())
+}
 
-    let mut q_marks = vec![];
-    for _i in &tx_methods {
-        q_marks.push("0.00")
+/// Checks whether the name can be given to a Transaction Method. Transfers save both methods as
+/// `A to B` and split transactions are saved with `Split` as their method so neither can be used.
+pub(crate) fn check_method_name(name: &str, all_methods: &[String]) -> Result<(), Box<dyn Error>> {
+    if name.trim().is_empty() {
+        return Err("The Transaction Method name cannot be empty".into());
+    } else if name.contains(" to ") {
+        return Err(format!("{name} contains ' to ' and cannot be a Transaction Method").into());
+    } else if name == "Split" {
+        return Err(
+            "Split is used by split transactions and cannot be a Transaction Method".into(),
+        );
+    } else if all_methods.contains(&name.to_string()) {
+        return Err(format!("{name} is already a Transaction Method").into());
     }
+    Ok(())
+}
 
-    let mut query = format!(
-        "INSERT INTO balance_all ({:?}) VALUES ({:?})",
-        tx_methods, q_marks
-    );
-    // We are using :? to keep the commas inside the string and remove the other unnecessary characters
-    query = query.replace('[', "").replace(']', "");
+/// Renames the Transaction Method saved in the tx_method column of the given table. Only the exact
+/// method on either side of the `A to B` form of the transfers gets replaced.
+fn rename_saved_methods(
+    conn: &Connection,
+    table: &str,
+    id_column: &str,
+    old_method: &str,
+    new_method: &str,
+) -> Result<()> {
+    conn.execute(
+        &format!("UPDATE {table} SET tx_method = ? WHERE tx_method = ? AND tx_type != 'Transfer'"),
+        [new_method, old_method],
+    )?;
+
+    let mut statement = conn.prepare(&format!(
+        "SELECT {id_column}, tx_method FROM {table} WHERE tx_type = 'Transfer'"
+    ))?;
+    let all_transfers = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(i32, String)>>>()?;
+
+    for (id_num, tx_method) in all_transfers {
+        let from_to = tx_method
+            .split(" to ")
+            .map(|method| {
+                if method == old_method {
+                    new_method
+                } else {
+                    method
+                }
+            })
+            .collect::<Vec<&str>>();
 
-    for _i in years {
-        for _a in 0..months.len() {
-            sp.execute(&query, [])?;
+        let new_tx_method = from_to.join(" to ");
+        if new_tx_method != tx_method {
+            conn.execute(
+                &format!("UPDATE {table} SET tx_method = ? WHERE {id_column} = ?"),
+                params![new_tx_method, id_num],
+            )?;
         }
     }
-    sp.execute(&query, [])?;
+    Ok(())
+}
+
+/// Archives a Transaction Method. The method keeps all of its balances and transactions but is hidden
+/// from the Balance widget and can no longer be used for new transactions. At least one method must
+/// stay active.
+pub fn archive_tx_method(conn: &Connection, tx_method: &str) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    archive_method(&sp, tx_method)?;
+    record_change(
+        &sp,
+        &JournalChange::ArchiveMethod {
+            name: tx_method.to_string(),
+        },
+    )?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Does the actual work of archive_tx_method inside the given connection or savepoint without committing
+pub(crate) fn archive_method(conn: &Connection, tx_method: &str) -> Result<(), Box<dyn Error>> {
+    let active_methods = get_active_tx_methods(conn);
+
+    if !active_methods.contains(&tx_method.to_string()) {
+        return Err(format!("{tx_method} is not an active Transaction Method").into());
+    } else if active_methods.len() == 1 {
+        return Err("At least one Transaction Method must stay active".into());
+    }
+
+    conn.execute(
+        "UPDATE tx_methods SET archived = 1 WHERE name = ?",
+        [tx_method],
+    )?;
+    Ok(())
+}
+
+/// Brings back an archived Transaction Method so it shows up and can be used again
+pub fn restore_tx_method(conn: &Connection, tx_method: &str) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    restore_method(&sp, tx_method)?;
+    record_change(
+        &sp,
+        &JournalChange::RestoreMethod {
+            name: tx_method.to_string(),
+        },
+    )?;
+
     sp.commit()?;
     Ok(())
 }
 
-/// This function is used for adding new column to the database when adding new
-/// Transaction Methods. Takes vector with transaction method names and commits them.
-pub fn add_new_tx_methods(file_name: &str, tx_methods: Vec<String>) -> Result<()> {
-    // add a save point to reverse commits if failed
-    let mut conn = Connection::open(file_name)?;
-    let sp = conn.savepoint().unwrap();
+/// Does the actual work of restore_tx_method inside the given connection or savepoint without committing
+pub(crate) fn restore_method(conn: &Connection, tx_method: &str) -> Result<(), Box<dyn Error>> {
+    let restored = conn.execute(
+        "UPDATE tx_methods SET archived = 0 WHERE name = ? AND archived = 1",
+        [tx_method],
+    )?;
+    if restored == 0 {
+        return Err(format!("{tx_method} is not an archived Transaction Method").into());
+    }
+    Ok(())
+}
 
-    for i in &tx_methods {
-        let query = format!(r#"ALTER TABLE balance_all ADD COLUMN "{i}" TEXT DEFAULT 0.00"#);
-        sp.execute(&query, [])?;
+/// Removes a Transaction Method completely along with its balance and changes rows. Refused if the
+/// method was ever used in a transaction, including transfers, split transaction legs and the transactions
+/// in the trash, if a recurring transaction or a saved import mapping uses it or if its final balance is
+/// not zero.
+pub fn delete_tx_method(conn: &Connection, tx_method: &str) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let archived = get_archived_tx_methods(&sp).contains(&tx_method.to_string());
+    remove_tx_method(&sp, tx_method)?;
+    record_change(
+        &sp,
+        &JournalChange::DeleteMethod {
+            name: tx_method.to_string(),
+            archived,
+        },
+    )?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Does the actual work of delete_tx_method inside the given connection or savepoint without committing
+pub(crate) fn remove_tx_method(conn: &Connection, tx_method: &str) -> Result<(), Box<dyn Error>> {
+    let all_methods = get_all_tx_methods(conn);
+
+    if !all_methods.contains(&tx_method.to_string()) {
+        return Err(format!("{tx_method} is not a Transaction Method").into());
+    } else if all_methods.len() == 1 {
+        return Err("The last Transaction Method cannot be deleted".into());
     }
 
-    for i in &tx_methods {
-        let query = format!(r#"ALTER TABLE changes_all ADD COLUMN "{i}" TEXT DEFAULT 0.00"#);
-        sp.execute(&query, [])?;
+    let method_filter = "WHERE tx_method = ?1
+            OR substr(tx_method, 1, length(?1) + 4) = ?1 || ' to '
+            OR substr(tx_method, -(length(?1) + 4)) = ' to ' || ?1";
+
+    let total_tx: i32 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM tx_all {method_filter}"),
+        [tx_method],
+        |row| row.get(0),
+    )?;
+
+    if total_tx != 0 {
+        return Err(
+            format!("{tx_method} has {total_tx} transactions and cannot be deleted").into(),
+        );
+    }
+
+    let total_trash = count_trash_txs_using(conn, tx_method)?;
+
+    if total_trash != 0 {
+        return Err(format!(
+            "{tx_method} is used by {total_trash} transactions in the trash and cannot be deleted"
+        )
+        .into());
+    }
+
+    let total_recurring: i32 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM recurring_txs {method_filter}"),
+        [tx_method],
+        |row| row.get(0),
+    )?;
+
+    if total_recurring != 0 {
+        return Err(format!(
+            "{tx_method} is used by {total_recurring} recurring transactions and cannot be deleted"
+        )
+        .into());
+    }
+
+    let total_profiles: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM import_profiles WHERE tx_method = ?",
+        [tx_method],
+        |row| row.get(0),
+    )?;
+
+    if total_profiles != 0 {
+        return Err(format!(
+            "{tx_method} is used by {total_profiles} saved import mappings and cannot be deleted"
+        )
+        .into());
+    }
+
+    let total_legs: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM tx_legs WHERE method_id = (SELECT id FROM tx_methods WHERE name = ?)",
+        [tx_method],
+        |row| row.get(0),
+    )?;
+
+    if total_legs != 0 {
+        return Err(format!(
+            "{tx_method} is used by {total_legs} split transaction legs and cannot be deleted"
+        )
+        .into());
+    }
+
+    let method_index = all_methods
+        .iter()
+        .position(|method| method == tx_method)
+        .unwrap();
+    let final_balance = get_last_balances(conn, &all_methods)[method_index];
+
+    if final_balance != 0 {
+        return Err(format!(
+            "{tx_method} has a balance of {} and cannot be deleted",
+            cents_to_amount(final_balance)
+        )
+        .into());
+    }
+
+    // foreign key cascade takes care of the balance and changes rows
+    conn.execute("DELETE FROM tx_methods WHERE name = ?", [tx_method])?;
+    Ok(())
+}
+
+/// Adds new categories that transactions can be saved with. Refused if any of the names
+/// cannot be a category name.
+pub fn add_new_categories(
+    conn: &Connection,
+    categories: Vec<String>,
+) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    for i in &categories {
+        check_category_name(i, &get_all_categories(&sp))?;
+        sp.execute("INSERT INTO categories (name) VALUES (?)", [i])?;
     }
     sp.commit()?;
     Ok(())
 }
+
+/// Renames a category. Transactions refer to the category by its id so they follow the new name
+/// while the transactions kept in the journal and the trash are renamed by name. Refused if the
+/// new name cannot be a category name.
+pub fn rename_category(
+    conn: &Connection,
+    old_category: &str,
+    new_category: &str,
+) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let all_categories = get_all_categories(&sp);
+    if !all_categories.contains(&old_category.to_string()) {
+        return Err(format!("{old_category} is not a category").into());
+    }
+    check_category_name(new_category, &all_categories)?;
+
+    sp.execute(
+        "UPDATE categories SET name = ? WHERE name = ?",
+        [new_category, old_category],
+    )?;
+    update_journal_txs(&sp, |tx| tx.rename_category(old_category, new_category))?;
+    update_trash_txs(&sp, |tx| tx.rename_category(old_category, new_category))?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Deletes a category. The transactions that used it are kept without a category, including the
+/// ones in the journal and the trash.
+pub fn delete_category(conn: &Connection, category: &str) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let deleted = sp.execute("DELETE FROM categories WHERE name = ?", [category])?;
+
+    if deleted == 0 {
+        return Err(format!("{category} is not a category").into());
+    }
+    update_journal_txs(&sp, |tx| tx.rename_category(category, ""))?;
+    update_trash_txs(&sp, |tx| tx.rename_category(category, ""))?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Checks whether the given name can be a category. The name cannot be empty
+/// or the name of another category.
+pub(crate) fn check_category_name(
+    name: &str,
+    all_categories: &[String],
+) -> Result<(), Box<dyn Error>> {
+    if name.trim().is_empty() {
+        return Err("The category name cannot be empty".into());
+    } else if all_categories.contains(&name.to_string()) {
+        return Err(format!("{name} is already a category").into());
+    }
+    Ok(())
+}
@@ -0,0 +1,512 @@
+use crate::db::{add_new_year, DbSavepoint};
+use chrono::prelude::{Datelike, Local};
+use rusqlite::{params, Connection, Result as sqlResult};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+/// A single schema change. Takes the open savepoint so every migration of a run
+/// is committed or reversed together.
+type Migration = fn(&Connection) -> sqlResult<()>;
+
+/// All migrations in the order they must be applied. The schema version of a database
+/// is the number of migrations that have been applied to it so a new migration must
+/// only ever be pushed to the end of this list.
+const MIGRATIONS: [Migration; 15] = [
+    update_balance_ids,
+    update_amounts_to_cents,
+    create_archive_table,
+    normalize_tables,
+    add_categories,
+    add_tags,
+    add_recurring_txs,
+    add_budgets,
+    add_split_txs,
+    add_journal,
+    add_trash,
+    add_import_profiles,
+    add_import_ids,
+    carry_month_balances,
+    add_decimal_separator,
+];
+
+/// The schema version this binary creates and expects
+pub const SCHEMA_VERSION: i32 = MIGRATIONS.len() as i32;
+
+/// Returns the schema version saved in the database. Databases created before versioning
+/// was added return 0.
+pub fn get_schema_version(conn: &Connection) -> sqlResult<i32> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Marks the database as being on the latest schema version
+pub fn set_schema_version(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(&format!("PRAGMA user_version = {SCHEMA_VERSION}"))
+}
+
+/// Checks the schema version of the database and runs every migration that has not been
+/// applied yet inside a single savepoint. Refuses to touch a database that was created by
+/// a newer version of the app. Finally, adds the monthly rows of the current year if they are missing.
+pub fn migrate_db(conn: &Connection) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let db_version = get_schema_version(&sp)?;
+
+    if db_version > SCHEMA_VERSION {
+        return Err(format!(
+            "The database schema version is {db_version} but this version of the app supports up to {SCHEMA_VERSION}. Update the app to open this database"
+        )
+        .into());
+    }
+
+    for migration in &MIGRATIONS[db_version as usize..] {
+        migration(&sp)?;
+    }
+
+    set_schema_version(&sp)?;
+    add_new_year(&sp, Local::today().year() as usize)?;
+    sp.commit()?;
+    Ok(())
+}
+
+/// Version 1. Databases created before the year range was dynamic have 48 monthly balance rows for
+/// 2022 to 2025 saved at id_num 1 to 48 and the absolute final balance at id_num 49. This moves them
+/// to the current layout where the final balance is at id_num 0 and each month is saved as `YYYYMM`.
+fn update_balance_ids(conn: &Connection) -> sqlResult<()> {
+    // some unversioned databases already use the new layout
+    let final_balance_found: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM balance_all WHERE id_num = 0",
+        [],
+        |row| row.get(0),
+    )?;
+
+    if final_balance_found == 0 {
+        conn.execute(
+            "UPDATE balance_all SET id_num = CASE
+                WHEN id_num = 49 THEN 0
+                ELSE (2022 + (id_num - 1) / 12) * 100 + (id_num - 1) % 12 + 1
+            END",
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Version 2. Older databases saved every amount as TEXT such as `159.00` in tx_all and balance_all
+/// and `↓159.00` in changes_all. This rebuilds the tables with INTEGER columns and converts
+/// all the existing amounts into cents.
+fn update_amounts_to_cents(conn: &Connection) -> sqlResult<()> {
+    let amount_type: String = conn.query_row(
+        "SELECT type FROM pragma_table_info('tx_all') WHERE name = 'amount'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    // some unversioned databases already save the amounts in cents
+    if amount_type != "TEXT" {
+        return Ok(());
+    }
+
+    let tx_methods = get_column_tx_methods(conn)?;
+
+    // the indexes are dropped so the new tables can use the same index names
+    conn.execute_batch(
+        "DROP INDEX IF EXISTS all_tx_date_IDX;
+        DROP INDEX IF EXISTS changes_all_date_IDX;
+        DROP INDEX IF EXISTS balance_all_id_num_IDX;
+        ALTER TABLE changes_all RENAME TO changes_all_old;
+        ALTER TABLE balance_all RENAME TO balance_all_old;
+        ALTER TABLE tx_all RENAME TO tx_all_old;",
+    )?;
+
+    create_column_tables(conn, &tx_methods)?;
+
+    conn.execute(
+        "INSERT INTO tx_all (date, details, tx_method, amount, tx_type, id_num)
+        SELECT date, details, tx_method, CAST(ROUND(amount * 100) AS INTEGER), tx_type, id_num FROM tx_all_old",
+        [],
+    )?;
+
+    let mut balance_columns = vec![];
+    let mut changes_columns = vec![];
+    for i in &tx_methods {
+        balance_columns.push(format!(r#"CAST(ROUND("{i}" * 100) AS INTEGER)"#));
+
+        // changes start with ↑ or ↓ unless there was no change
+        changes_columns.push(format!(
+            r#"CASE
+                WHEN "{i}" LIKE '↓%' THEN -CAST(ROUND(substr("{i}", 2) * 100) AS INTEGER)
+                WHEN "{i}" LIKE '↑%' THEN CAST(ROUND(substr("{i}", 2) * 100) AS INTEGER)
+                ELSE CAST(ROUND("{i}" * 100) AS INTEGER)
+            END"#
+        ));
+    }
+
+    let mut method_columns = format!("{:?}", tx_methods);
+    method_columns = method_columns.replace(['[', ']'], "");
+
+    conn.execute(
+        &format!(
+            "INSERT INTO balance_all (id_num, {method_columns}) SELECT id_num, {} FROM balance_all_old",
+            balance_columns.join(", ")
+        ),
+        [],
+    )?;
+
+    conn.execute(
+        &format!(
+            "INSERT INTO changes_all (date, id_num, {method_columns}) SELECT date, id_num, {} FROM changes_all_old",
+            changes_columns.join(", ")
+        ),
+        [],
+    )?;
+
+    conn.execute_batch(
+        "DROP TABLE changes_all_old;
+        DROP TABLE balance_all_old;
+        DROP TABLE tx_all_old;",
+    )?;
+    Ok(())
+}
+
+/// Version 3. Creates the table that keeps the names of the archived Transaction Methods.
+fn create_archive_table(conn: &Connection) -> sqlResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS archived_methods (method TEXT NOT NULL PRIMARY KEY);",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Version 4. Before this version balance_all and changes_all had one column per Transaction Method.
+/// This moves the methods to the tx_methods table, keeps the archived status and turns every balance
+/// and change column into rows keyed by the method id. Only the changes that are not zero are kept.
+fn normalize_tables(conn: &Connection) -> sqlResult<()> {
+    let tx_methods = get_column_tx_methods(conn)?;
+
+    conn.execute_batch(
+        "DROP INDEX IF EXISTS all_tx_date_IDX;
+        DROP INDEX IF EXISTS changes_all_date_IDX;
+        DROP INDEX IF EXISTS balance_all_id_num_IDX;
+        ALTER TABLE changes_all RENAME TO changes_all_old;
+        ALTER TABLE balance_all RENAME TO balance_all_old;
+        ALTER TABLE tx_all RENAME TO tx_all_old;",
+    )?;
+
+    conn.execute_batch(
+        "CREATE TABLE tx_methods (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        archived INTEGER NOT NULL DEFAULT 0
+    );
+
+    CREATE TABLE tx_all (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount INTEGER,
+        tx_type TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT
+    );
+
+    CREATE TABLE changes_all (
+        id_num INTEGER NOT NULL,
+        method_id INTEGER NOT NULL,
+        amount INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (id_num, method_id),
+        CONSTRAINT changes_all_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE,
+        CONSTRAINT changes_all_method_FK FOREIGN KEY (method_id) REFERENCES tx_methods(id) ON DELETE CASCADE
+    );
+
+    CREATE TABLE balance_all (
+        id_num INTEGER NOT NULL,
+        method_id INTEGER NOT NULL,
+        balance INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (id_num, method_id),
+        CONSTRAINT balance_all_method_FK FOREIGN KEY (method_id) REFERENCES tx_methods(id) ON DELETE CASCADE
+    );
+
+    CREATE UNIQUE INDEX all_tx_date_IDX ON tx_all (id_num);
+    CREATE INDEX tx_all_date_IDX ON tx_all (date);",
+    )?;
+
+    conn.execute(
+        "INSERT INTO tx_all (date, details, tx_method, amount, tx_type, id_num)
+        SELECT date, details, tx_method, amount, tx_type, id_num FROM tx_all_old",
+        [],
+    )?;
+
+    for method in &tx_methods {
+        conn.execute(
+            "INSERT INTO tx_methods (name, archived)
+            SELECT ?1, EXISTS (SELECT 1 FROM archived_methods WHERE method = ?1)",
+            [method],
+        )?;
+        let method_id = conn.last_insert_rowid();
+
+        // column names cannot be passed as parameters so the quotes inside the name are escaped
+        let column = format!(r#""{}""#, method.replace('"', r#""""#));
+
+        conn.execute(
+            &format!("INSERT INTO balance_all (id_num, method_id, balance) SELECT id_num, ?, {column} FROM balance_all_old"),
+            [method_id],
+        )?;
+        conn.execute(
+            &format!("INSERT INTO changes_all (id_num, method_id, amount) SELECT id_num, ?, {column} FROM changes_all_old WHERE {column} != 0"),
+            [method_id],
+        )?;
+    }
+
+    conn.execute_batch(
+        "DROP TABLE changes_all_old;
+        DROP TABLE balance_all_old;
+        DROP TABLE tx_all_old;
+        DROP TABLE archived_methods;",
+    )?;
+    Ok(())
+}
+
+/// Version 5. Adds the categories table and the optional category of each transaction.
+/// Deleting a category leaves its transactions without a category.
+fn add_categories(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE categories (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE
+    );
+    ALTER TABLE tx_all ADD COLUMN category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL;",
+    )?;
+    Ok(())
+}
+
+/// Version 6. Adds the tags of the transactions. A transaction can have any number of tags
+/// which are removed along with the transaction.
+fn add_tags(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE tx_tags (
+        id_num INTEGER NOT NULL,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (id_num, tag),
+        CONSTRAINT tx_tags_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+    )?;
+    Ok(())
+}
+
+/// Version 7. Adds the recurring transaction templates. last_date is the date of the latest
+/// transaction that was added from the template.
+fn add_recurring_txs(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE recurring_txs (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        details TEXT NOT NULL,
+        tx_method TEXT NOT NULL,
+        amount INTEGER NOT NULL,
+        tx_type TEXT NOT NULL,
+        category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL,
+        tags TEXT NOT NULL DEFAULT '',
+        rule TEXT NOT NULL CHECK (rule IN ('Monthly', 'Weekly', 'Yearly')),
+        start_date TEXT NOT NULL,
+        end_date TEXT,
+        last_date TEXT,
+        paused INTEGER NOT NULL DEFAULT 0
+    );",
+    )?;
+    Ok(())
+}
+
+/// Version 8. Adds the budgets table. A budget belongs to either a category or a Transaction Method
+/// and is removed along with it.
+fn add_budgets(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE budgets (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        category_id INTEGER UNIQUE REFERENCES categories(id) ON DELETE CASCADE,
+        method_id INTEGER UNIQUE REFERENCES tx_methods(id) ON DELETE CASCADE,
+        amount INTEGER NOT NULL,
+        rollover INTEGER NOT NULL DEFAULT 0,
+        start_month TEXT NOT NULL,
+        CHECK ((category_id IS NULL) != (method_id IS NULL))
+    );",
+    )?;
+    Ok(())
+}
+
+/// Version 9. Adds the legs of split transactions. The tx_all row of a split transaction keeps the total
+/// amount while each leg has its own Transaction Method, amount and category.
+fn add_split_txs(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE tx_legs (
+        id_num INTEGER NOT NULL,
+        leg_num INTEGER NOT NULL,
+        method_id INTEGER NOT NULL REFERENCES tx_methods(id),
+        amount INTEGER NOT NULL,
+        category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL,
+        PRIMARY KEY (id_num, leg_num),
+        CONSTRAINT tx_legs_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+    );",
+    )?;
+    Ok(())
+}
+
+/// Version 10. Adds the journal of the changes done to the transactions and the Transaction Methods
+/// that can be undone and redone.
+fn add_journal(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE journal (
+        id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+        change TEXT NOT NULL,
+        undone INTEGER NOT NULL DEFAULT 0
+    );",
+    )?;
+    Ok(())
+}
+
+/// Version 11. Adds the trash where the deleted transactions are kept until they are restored or purged
+fn add_trash(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE trash (
+        id_num INTEGER NOT NULL PRIMARY KEY,
+        deleted_at TEXT NOT NULL,
+        tx TEXT NOT NULL
+    );",
+    )?;
+    Ok(())
+}
+
+/// Version 12. Adds the saved column mappings used to import bank CSV files
+fn add_import_profiles(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE import_profiles (
+        name TEXT NOT NULL PRIMARY KEY,
+        has_header INTEGER NOT NULL,
+        date_column INTEGER NOT NULL,
+        details_column INTEGER NOT NULL,
+        amount_column INTEGER NOT NULL,
+        sign_column INTEGER,
+        expense_value TEXT NOT NULL,
+        date_format TEXT NOT NULL,
+        tx_method TEXT NOT NULL
+    );",
+    )?;
+    Ok(())
+}
+
+/// Version 13. Adds the FITIDs of the imported OFX entries so the same entry is never imported twice
+fn add_import_ids(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "CREATE TABLE import_ids (
+        method_id INTEGER NOT NULL REFERENCES tx_methods(id) ON DELETE CASCADE,
+        fitid TEXT NOT NULL,
+        id_num INTEGER NOT NULL,
+        PRIMARY KEY (method_id, fitid)
+    );",
+    )?;
+    Ok(())
+}
+
+/// Returns the Transaction Methods of a database from before version 4 where each method
+/// was a column in balance_all after the id_num column.
+fn get_column_tx_methods(conn: &Connection) -> sqlResult<Vec<String>> {
+    let mut statement = conn.prepare(
+        "SELECT name FROM pragma_table_info('balance_all') WHERE name != 'id_num' ORDER BY cid",
+    )?;
+    let tx_methods = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<sqlResult<Vec<String>>>()?;
+    Ok(tx_methods)
+}
+
+/// Creates the tx_all, changes_all and balance_all tables the way they were before version 4
+/// with one INTEGER column per Transaction Method. Only used by the earlier migrations.
+fn create_column_tables(conn: &Connection, tx_methods: &[String]) -> sqlResult<()> {
+    conn.execute(
+        "CREATE TABLE tx_all (
+        date TEXT,
+        details TEXT,
+        tx_method TEXT,
+        amount INTEGER,
+        tx_type TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT
+    );",
+        [],
+    )?;
+
+    let mut query = "CREATE TABLE changes_all (
+        date TEXT,
+        id_num INTEGER NOT NULL PRIMARY KEY,"
+        .to_string();
+    for i in tx_methods {
+        query.push_str(&format!(r#""{i}" INTEGER DEFAULT 0,"#))
+    }
+    query.push_str(
+        "CONSTRAINT changes_all_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE
+);",
+    );
+    conn.execute(&query, [])?;
+
+    let mut query = "CREATE TABLE balance_all (
+        id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT"
+        .to_string();
+    for i in tx_methods {
+        query.push_str(&format!(r#","{i}" INTEGER DEFAULT 0"#))
+    }
+    query.push_str(");");
+    conn.execute(&query, [])?;
+
+    conn.execute_batch(
+        "CREATE UNIQUE INDEX all_tx_date_IDX ON tx_all (id_num);
+        CREATE UNIQUE INDEX changes_all_date_IDX ON changes_all (id_num);
+        CREATE UNIQUE INDEX balance_all_id_num_IDX ON balance_all (id_num);",
+    )?;
+    Ok(())
+}
+
+/// Version 14. Months without any transaction used to keep a 0 balance and were skipped when the
+/// balances were looked up or a change was carried to the later months, which went wrong once the
+/// balance of a month added up to exactly 0. Every month without a transaction now gets the balance
+/// carried from the month before it.
+fn carry_month_balances(conn: &Connection) -> sqlResult<()> {
+    let mut statement = conn.prepare(
+        "SELECT DISTINCT CAST(substr(date, 1, 4) AS INTEGER) * 100 + CAST(substr(date, 6, 2) AS INTEGER)
+        FROM tx_all",
+    )?;
+    let tx_months = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<sqlResult<HashSet<i32>>>()?;
+
+    let mut statement = conn.prepare(
+        "SELECT id_num, method_id, balance FROM balance_all WHERE id_num > 0 ORDER BY method_id, id_num",
+    )?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                row.get::<_, i32>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?
+        .collect::<sqlResult<Vec<(i32, i32, i64)>>>()?;
+
+    let mut carried_balances: HashMap<i32, i64> = HashMap::new();
+    for (id_num, method_id, balance) in rows {
+        let carried_balance = carried_balances.entry(method_id).or_insert(0);
+        if tx_months.contains(&id_num) {
+            *carried_balance = balance;
+        } else if balance != *carried_balance {
+            conn.execute(
+                "UPDATE balance_all SET balance = ? WHERE id_num = ? AND method_id = ?",
+                params![*carried_balance, id_num, method_id],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Version 15. Saved CSV mappings keep the decimal separator of the amounts. The existing mappings
+/// were read with `.` as the decimal separator.
+fn add_decimal_separator(conn: &Connection) -> sqlResult<()> {
+    conn.execute_batch(
+        "ALTER TABLE import_profiles ADD COLUMN decimal_separator TEXT NOT NULL DEFAULT '.';",
+    )?;
+    Ok(())
+}
@@ -1,9 +1,40 @@
+mod backup;
+mod budget;
+mod encryption;
+mod export;
+mod import;
+mod integrity;
+mod journal;
 mod manage_db;
+mod migration;
+mod ofx;
+mod qif;
+mod recurring;
+mod repair;
+mod savepoint;
 mod sub_func;
+mod trash;
 mod tx_manager;
 mod verifier;
 
-pub use manage_db::{add_new_tx_methods, create_db};
+pub use backup::*;
+pub use budget::*;
+pub use encryption::*;
+pub use export::*;
+pub use import::*;
+pub use integrity::*;
+pub use journal::{redo_last_change, undo_last_change};
+pub use manage_db::{
+    add_new_categories, add_new_tx_methods, archive_tx_method, create_db, delete_category,
+    delete_tx_method, open_db, rename_category, rename_tx_method, restore_tx_method,
+};
+pub use migration::*;
+pub use ofx::*;
+pub use qif::*;
+pub use recurring::*;
+pub use repair::*;
+pub use savepoint::*;
 pub use sub_func::*;
+pub use trash::{get_trash_txs, purge_old_trash, purge_tx, restore_tx};
 pub use tx_manager::*;
 pub use verifier::*;
@@ -0,0 +1,106 @@
+use std::path::Path;
+
+// OFX 1.x files are SGML where the elements that hold a value usually do not have a closing tag
+// while OFX 2.x files are XML where every element is closed. The aggregates such as STMTTRN are
+// closed in both versions so each entry is read from its opening tag to its closing tag and every
+// value is read until the next tag starts, which works the same way for both versions.
+
+/// A STMTTRN entry of an OFX file with its values as they were written in the file
+///
+/// line : the line number where the entry starts
+///
+/// date_posted : the DTPOSTED value. Example: `20220719120000.000[-5:EST]`
+///
+/// amount : the signed TRNAMT value. Negative for money going out of the account
+///
+/// details : the NAME of the entry or the MEMO if there is no name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfxEntry {
+    pub line: usize,
+    pub fitid: String,
+    pub date_posted: String,
+    pub amount: String,
+    pub details: String,
+}
+
+/// Checks whether the file is an OFX or a QFX file from its extension
+pub fn is_ofx_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|a| a.to_str())
+        .is_some_and(|a| a.eq_ignore_ascii_case("ofx") || a.eq_ignore_ascii_case("qfx"))
+}
+
+/// Collects every STMTTRN entry of an OFX 1.x SGML or 2.x XML file in the order they were written.
+/// Fails if the text is not an OFX file.
+pub fn parse_ofx(text: &str) -> Result<Vec<OfxEntry>, String> {
+    // tags are searched in uppercase. It keeps the same byte positions as the original text
+    let upper_text = text.to_ascii_uppercase();
+    if !upper_text.contains("<OFX>") {
+        return Err("The file is not an OFX file".to_string());
+    }
+
+    let mut all_entries = Vec::new();
+    let mut position = 0;
+
+    while let Some(start) = upper_text[position..].find("<STMTTRN>") {
+        let start = position + start;
+        let end = match upper_text[start..].find("</STMTTRN>") {
+            Some(end) => start + end,
+            None => {
+                return Err(format!(
+                    "The entry on line {} is not closed",
+                    get_line(text, start)
+                ))
+            }
+        };
+
+        let entry_text = &text[start..end];
+        let upper_entry = &upper_text[start..end];
+        let get_value = |tag: &str| get_tag_value(entry_text, upper_entry, tag).unwrap_or_default();
+
+        let mut details = get_value("NAME");
+        if details.is_empty() {
+            details = get_value("MEMO");
+        }
+
+        all_entries.push(OfxEntry {
+            line: get_line(text, start),
+            fitid: get_value("FITID"),
+            date_posted: get_value("DTPOSTED"),
+            // some banks write the amount with a decimal comma
+            amount: get_value("TRNAMT").replace(',', "."),
+            details,
+        });
+        position = end;
+    }
+    Ok(all_entries)
+}
+
+/// Returns the value of the first element with the tag inside the entry. The value ends where the
+/// next tag starts so it works with or without a closing tag.
+fn get_tag_value(entry_text: &str, upper_entry: &str, tag: &str) -> Option<String> {
+    let opening_tag = format!("<{tag}>");
+    let start = upper_entry.find(&opening_tag)? + opening_tag.len();
+    let end = entry_text[start..]
+        .find('<')
+        .map(|a| start + a)
+        .unwrap_or(entry_text.len());
+
+    Some(decode_entities(entry_text[start..end].trim()))
+}
+
+/// Turns the escaped characters of SGML and XML back into the characters
+fn decode_entities(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Returns the line number of the byte position in the text
+fn get_line(text: &str, position: usize) -> usize {
+    text[..position].matches('\n').count() + 1
+}
@@ -0,0 +1,153 @@
+use std::path::Path;
+
+// A QIF file is a list of sections. Each section starts with a `!` header line such as `!Type:Bank`
+// and has records made of lines that start with a one letter code. Every record ends with a `^` line.
+// The account the records of a section belong to is set by the `!Account` section before it.
+
+/// A record of a bank or cash section of a QIF file with its values as they were written in the file
+///
+/// line : the line number where the record starts
+///
+/// account : the name of the account the record belongs to. None if the file did not name the account
+///
+/// amount : the signed T value. Negative for money going out of the account
+///
+/// details : the payee of the record or the memo if there is no payee
+///
+/// category : the L value if it is a category
+///
+/// transfer : the account inside the brackets if the L value is a transfer such as `[Savings]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QifEntry {
+    pub line: usize,
+    pub account: Option<String>,
+    pub date: String,
+    pub amount: String,
+    pub details: String,
+    pub category: String,
+    pub transfer: Option<String>,
+}
+
+/// The section of a QIF file that is being read
+enum QifSection {
+    Account,
+    Transactions,
+    Other,
+}
+
+/// Checks whether the file is a QIF file from its extension
+pub fn is_qif_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|a| a.to_str())
+        .is_some_and(|a| a.eq_ignore_ascii_case("qif"))
+}
+
+/// Collects every record of the bank and cash sections of a QIF file in the order they were written.
+/// The records of the other sections such as investments, categories or memorized transactions are
+/// skipped. The lines of a split are skipped as the total amount of the record is used. Fails if the
+/// text is not a QIF file.
+pub fn parse_qif(text: &str) -> Result<Vec<QifEntry>, String> {
+    if !text
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .starts_with('!')
+    {
+        return Err("The file is not a QIF file".to_string());
+    }
+
+    let mut all_entries = Vec::new();
+    let mut section = QifSection::Other;
+    let mut account = None;
+    let mut entry = new_entry(None);
+    let mut memo = String::new();
+    let mut has_values = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_num = index + 1;
+        let line = line.trim_start_matches('\u{feff}').trim_end();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('!') {
+            let header = line.to_lowercase();
+            section = if header == "!account" {
+                QifSection::Account
+            } else if header == "!type:bank" || header == "!type:cash" {
+                QifSection::Transactions
+            } else {
+                QifSection::Other
+            };
+            entry = new_entry(account.clone());
+            memo.clear();
+            has_values = false;
+            continue;
+        }
+
+        let (code, value) = line.split_at(line.chars().next().map_or(0, |a| a.len_utf8()));
+        let value = value.trim().to_string();
+
+        match section {
+            QifSection::Account => {
+                if code == "N" {
+                    account = Some(value);
+                }
+            }
+            QifSection::Transactions if code != "^" && !has_values => {
+                // the record starts on its first value
+                entry.line = line_num;
+                has_values = true;
+                set_value(&mut entry, &mut memo, code, value);
+            }
+            QifSection::Transactions => match code {
+                "^" => {
+                    if has_values {
+                        if entry.details.is_empty() {
+                            entry.details = std::mem::take(&mut memo);
+                        }
+                        all_entries.push(entry);
+                    }
+                    entry = new_entry(account.clone());
+                    memo.clear();
+                    has_values = false;
+                }
+                _ => set_value(&mut entry, &mut memo, code, value),
+            },
+            QifSection::Other => {}
+        }
+    }
+    Ok(all_entries)
+}
+
+/// Saves the value of a line of a record in the field its code belongs to. The memo is kept
+/// separately as it is only used when the record has no payee.
+fn set_value(entry: &mut QifEntry, memo: &mut String, code: &str, value: String) {
+    match code {
+        "D" => entry.date = value,
+        "T" | "U" => entry.amount = value,
+        "P" => entry.details = value,
+        "M" => *memo = value,
+        "L" => {
+            // a class can be added after the category with a slash
+            let category = value.split('/').next().unwrap_or_default();
+            match category.strip_prefix('[').and_then(|a| a.strip_suffix(']')) {
+                Some(transfer) => entry.transfer = Some(transfer.to_string()),
+                None => entry.category = category.to_string(),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Creates an empty record of the given account
+fn new_entry(account: Option<String>) -> QifEntry {
+    QifEntry {
+        line: 0,
+        account,
+        date: String::new(),
+        amount: String::new(),
+        details: String::new(),
+        category: String::new(),
+        transfer: None,
+    }
+}
@@ -0,0 +1,365 @@
+use crate::db::tx_manager::insert_tx;
+use crate::db::{
+    amount_to_cents, cents_to_amount, get_category_id, split_tags, DbSavepoint, StatusChecker,
+};
+use chrono::{Datelike, Duration, NaiveDate};
+use rusqlite::{params, Connection, Result as sqlResult};
+use std::error::Error;
+
+/// A recurring transaction template as saved in the recurring_txs table. Amount is in cents
+/// and dates are in the `YYYY-MM-DD` format.
+struct RecurringTx {
+    id: i32,
+    details: String,
+    tx_method: String,
+    amount: i64,
+    tx_type: String,
+    category: String,
+    tags: String,
+    rule: String,
+    start_date: String,
+    end_date: Option<String>,
+    last_date: Option<String>,
+    paused: bool,
+}
+
+impl RecurringTx {
+    /// Returns every date the template came due after the last added transaction up to the given date.
+    /// Nothing is due after the end date.
+    fn get_due_dates(&self, until: NaiveDate) -> Vec<NaiveDate> {
+        let start_date = parse_date(&self.start_date);
+        let last_date = self.last_date.as_deref().and_then(parse_date);
+        let mut until = until;
+
+        if let Some(end_date) = self.end_date.as_deref().and_then(parse_date) {
+            until = until.min(end_date);
+        }
+
+        let mut due_dates = Vec::new();
+
+        if let Some(start_date) = start_date {
+            let mut occurrence_num = 0;
+            while let Some(date) = get_occurrence(start_date, &self.rule, occurrence_num) {
+                if date > until {
+                    break;
+                }
+                if last_date.is_none_or(|last_date| date > last_date) {
+                    due_dates.push(date);
+                }
+                occurrence_num += 1;
+            }
+        }
+        due_dates
+    }
+
+    /// Returns the date the template will come due next after the last added transaction if it has not ended
+    fn get_next_date(&self) -> Option<NaiveDate> {
+        let start_date = parse_date(&self.start_date)?;
+        let last_date = self.last_date.as_deref().and_then(parse_date);
+        let end_date = self.end_date.as_deref().and_then(parse_date);
+
+        let mut occurrence_num = 0;
+        while let Some(date) = get_occurrence(start_date, &self.rule, occurrence_num) {
+            if end_date.is_some_and(|end_date| date > end_date) {
+                return None;
+            }
+            if last_date.is_none_or(|last_date| date > last_date) {
+                return Some(date);
+            }
+            occurrence_num += 1;
+        }
+        None
+    }
+
+    /// Checks the template can be added as a transaction the same way the Add Transaction page checks
+    /// a transaction. The Transaction Methods must exist and be active and the category must exist.
+    fn check(&self, conn: &Connection) -> Result<(), Box<dyn Error>> {
+        let all_methods = match self.tx_type.as_str() {
+            "Expense" | "Income" => vec![self.tx_method.as_str()],
+            "Transfer" => {
+                let from_to = self.tx_method.split(" to ").collect::<Vec<&str>>();
+                if from_to.len() != 2 || from_to[0] == from_to[1] {
+                    return Err(format!(
+                        "TX Method: {} is not a transfer between two Transaction Methods",
+                        self.tx_method
+                    )
+                    .into());
+                }
+                from_to
+            }
+            _ => {
+                return Err(format!(
+                    "TX Type: {} is not acceptable. Values: Expense/Income/Transfer",
+                    self.tx_type
+                )
+                .into())
+            }
+        };
+
+        for tx_method in all_methods {
+            let mut checked_method = tx_method.to_string();
+            let status = self.verify_tx_method(&mut checked_method, conn)?;
+            if !status.ends_with("Accepted") {
+                return Err(format!("{status}: {tx_method}").into());
+            }
+        }
+
+        let mut checked_category = self.category.clone();
+        let status = self.verify_category(&mut checked_category, conn)?;
+        if !self.category.is_empty() && !status.ends_with("Accepted") {
+            return Err(format!("{status}: {}", self.category).into());
+        }
+
+        if !["Monthly", "Weekly", "Yearly"].contains(&self.rule.as_str()) {
+            return Err(format!(
+                "Rule: {} is not acceptable. Values: Monthly/Weekly/Yearly",
+                self.rule
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl StatusChecker for RecurringTx {}
+
+/// Adds a new recurring transaction template. The template repeats Monthly, Weekly or Yearly
+/// starting from the start date. Monthly templates come due on the day of the start date and on the
+/// last day of the month if the month is shorter. The end date is optional and the template stops after it.
+/// Refused if the type, the Transaction Method or the category could not be used by a new transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn add_recurring_tx(
+    conn: &Connection,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tx_type: &str,
+    category: &str,
+    tags: &str,
+    rule: &str,
+    start_date: &str,
+    end_date: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let int_amount = amount_to_cents(amount).map_err(|e| e as Box<dyn Error>)?;
+    let start = parse_date(start_date).ok_or(format!("{start_date} is not a valid date"))?;
+
+    if let Some(end_date) = end_date {
+        let end = parse_date(end_date).ok_or(format!("{end_date} is not a valid date"))?;
+        if end < start {
+            return Err("The end date cannot be before the start date".into());
+        }
+    }
+
+    let template = RecurringTx {
+        id: 0,
+        details: details.to_string(),
+        tx_method: tx_method.to_string(),
+        amount: int_amount,
+        tx_type: tx_type.to_string(),
+        category: category.to_string(),
+        tags: tags.to_string(),
+        rule: rule.to_string(),
+        start_date: start_date.to_string(),
+        end_date: end_date.map(|a| a.to_string()),
+        last_date: None,
+        paused: false,
+    };
+    template.check(conn)?;
+
+    let category_id = get_category_id(conn, category)?;
+
+    conn.execute(
+        "INSERT INTO recurring_txs (details, tx_method, amount, tx_type, category_id, tags, rule, start_date, end_date)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        params![
+            details,
+            tx_method,
+            int_amount,
+            tx_type,
+            category_id,
+            split_tags(tags).join(", "),
+            rule,
+            start_date,
+            end_date
+        ],
+    )?;
+    Ok(())
+}
+
+/// Returns all the recurring transaction templates in the order they were added along with their id.
+/// The date the template comes due next is `-` if it has ended.
+/// return example: `([["Rent", "Bank", "500.00", "Expense", "Monthly", "2022-01-01", "-", "2022-08-01", "Active"]], [1])`
+pub fn get_all_recurring_txs(conn: &Connection) -> (Vec<Vec<String>>, Vec<i32>) {
+    let all_templates = get_templates(conn, "").expect("could not get the recurring transactions");
+
+    let mut all_rows = Vec::new();
+    let mut all_id = Vec::new();
+
+    for template in all_templates {
+        let next_date = match template.get_next_date() {
+            Some(date) => date.to_string(),
+            None => "-".to_string(),
+        };
+        let status = if template.paused { "Paused" } else { "Active" };
+
+        all_rows.push(vec![
+            template.details.clone(),
+            template.tx_method.clone(),
+            cents_to_amount(template.amount),
+            template.tx_type.clone(),
+            template.rule.clone(),
+            template.start_date.clone(),
+            template.end_date.clone().unwrap_or_else(|| "-".to_string()),
+            next_date,
+            status.to_string(),
+        ]);
+        all_id.push(template.id);
+    }
+    (all_rows, all_id)
+}
+
+/// Pauses a recurring transaction template. Nothing is added from a paused template.
+pub fn pause_recurring_tx(conn: &Connection, id: i32) -> sqlResult<()> {
+    conn.execute("UPDATE recurring_txs SET paused = 1 WHERE id = ?", [id])?;
+    Ok(())
+}
+
+/// Resumes a paused recurring transaction template. The dates that came due while it was
+/// paused up to the given date are skipped.
+pub fn resume_recurring_tx(conn: &Connection, id: i32, today: NaiveDate) -> sqlResult<()> {
+    let yesterday = (today - Duration::days(1)).to_string();
+    conn.execute(
+        "UPDATE recurring_txs SET paused = 0,
+        last_date = CASE WHEN last_date IS NULL OR last_date < ?1 THEN ?1 ELSE last_date END
+        WHERE id = ?2 AND paused = 1",
+        params![yesterday, id],
+    )?;
+    Ok(())
+}
+
+/// Deletes a recurring transaction template. The transactions that were already added from it are kept.
+pub fn delete_recurring_tx(conn: &Connection, id: i32) -> sqlResult<()> {
+    conn.execute("DELETE FROM recurring_txs WHERE id = ?", [id])?;
+    Ok(())
+}
+
+/// Adds a transaction for every date a template came due since the last time it was checked up to the
+/// given date. The transactions go through the same balance calculation as add_new_tx. A template whose
+/// Transaction Method was archived or whose values cannot be used anymore is skipped and keeps its due
+/// dates until it is fixed. Everything is committed together or not at all. Returns the number of
+/// transactions that were added and why each skipped template was skipped.
+/// return example: `(3, ["Rent was skipped. TX Method: Transaction Method is archived: Bank"])`
+pub fn add_due_recurring_txs(
+    conn: &Connection,
+    today: NaiveDate,
+) -> Result<(usize, Vec<String>), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let mut total_added = 0;
+    let mut skipped = Vec::new();
+
+    for template in get_templates(&sp, "WHERE recurring_txs.paused = 0")? {
+        let due_dates = template.get_due_dates(today);
+        if due_dates.is_empty() {
+            continue;
+        }
+
+        if let Err(e) = template.check(&sp) {
+            skipped.push(format!("{} was skipped. {e}", template.details));
+            continue;
+        }
+
+        for date in &due_dates {
+            insert_tx(
+                &sp,
+                &date.to_string(),
+                &template.details,
+                &template.tx_method,
+                &cents_to_amount(template.amount),
+                &template.tx_type,
+                &template.category,
+                &template.tags,
+                None,
+            )?;
+        }
+
+        if let Some(last_date) = due_dates.last() {
+            sp.execute(
+                "UPDATE recurring_txs SET last_date = ? WHERE id = ?",
+                params![last_date.to_string(), template.id],
+            )?;
+            total_added += due_dates.len();
+        }
+    }
+
+    sp.commit()?;
+    Ok((total_added, skipped))
+}
+
+/// Collects the recurring transaction templates that match the given WHERE statement
+fn get_templates(conn: &Connection, where_statement: &str) -> sqlResult<Vec<RecurringTx>> {
+    let query = format!(
+        "SELECT recurring_txs.id, details, tx_method, amount, tx_type, categories.name, tags, rule,
+        start_date, end_date, last_date, paused
+        FROM recurring_txs LEFT JOIN categories ON categories.id = recurring_txs.category_id
+        {where_statement} ORDER BY recurring_txs.id"
+    );
+    let mut statement = conn.prepare(&query)?;
+
+    let all_templates = statement
+        .query_map([], |row| {
+            let category: Option<String> = row.get(5)?;
+            let paused: i32 = row.get(11)?;
+            Ok(RecurringTx {
+                id: row.get(0)?,
+                details: row.get(1)?,
+                tx_method: row.get(2)?,
+                amount: row.get(3)?,
+                tx_type: row.get(4)?,
+                category: category.unwrap_or_default(),
+                tags: row.get(6)?,
+                rule: row.get(7)?,
+                start_date: row.get(8)?,
+                end_date: row.get(9)?,
+                last_date: row.get(10)?,
+                paused: paused == 1,
+            })
+        })?
+        .collect::<sqlResult<Vec<RecurringTx>>>()?;
+    Ok(all_templates)
+}
+
+/// Returns the date of the given occurrence of a rule counting from the start date which is occurrence 0.
+/// Months and years that do not have the day of the start date use their last day instead.
+fn get_occurrence(start_date: NaiveDate, rule: &str, occurrence_num: u32) -> Option<NaiveDate> {
+    match rule {
+        "Weekly" => start_date.checked_add_signed(Duration::weeks(occurrence_num as i64)),
+        "Monthly" => {
+            let total_months = start_date.year() * 12 + start_date.month0() as i32;
+            let target_month = total_months + occurrence_num as i32;
+            get_clamped_date(
+                target_month / 12,
+                target_month as u32 % 12 + 1,
+                start_date.day(),
+            )
+        }
+        "Yearly" => get_clamped_date(
+            start_date.year() + occurrence_num as i32,
+            start_date.month(),
+            start_date.day(),
+        ),
+        _ => None,
+    }
+}
+
+/// Returns the given date or the last day of the month if the month does not have the day
+fn get_clamped_date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    (1..=day)
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+}
+
+/// Parses a `YYYY-MM-DD` date
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
@@ -0,0 +1,239 @@
+use crate::db::tx_manager::get_tx_changes;
+use crate::db::{add_new_year, cents_to_amount, get_balance_id, get_last_balance_id, DbSavepoint};
+use rusqlite::{params, Connection, Result as sqlResult};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+
+/// The balance changes of a single transaction keyed by the Transaction Method id
+type TxChanges = BTreeMap<i32, i64>;
+
+/// The fields of a tx_all row that decide the balances. Amount is in cents.
+struct SavedTx {
+    id_num: i32,
+    date: String,
+    tx_method: String,
+    amount: i64,
+    tx_type: String,
+}
+
+/// Recomputes every monthly balance, the absolute final balance and all the changes_all rows from the
+/// transactions saved in tx_all and the legs of the split transactions. Only the rows that are different
+/// from the recomputed values are rewritten and everything is committed together or not at all.
+/// A month without any transaction must have the balance carried from the earlier months. Returns a line for every difference that was fixed.
+/// return example: `["Balance of Bank on 2022-08 was 100.00, fixed to 90.00"]`
+pub fn rebuild_balances(conn: &Connection) -> Result<Vec<String>, Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let method_names = get_method_names(&sp)?;
+    let method_ids: HashMap<&str, i32> = method_names
+        .iter()
+        .map(|(id, name)| (name.as_str(), *id))
+        .collect();
+
+    let mut differences = Vec::new();
+
+    let all_txs = get_all_txs(&sp)?;
+    let all_legs = get_all_legs(&sp)?;
+    let saved_changes = get_saved_changes(&sp)?;
+
+    // the balance of each method after the last transaction of every month that has any transaction
+    let mut running_balance: BTreeMap<i32, i64> = method_names.keys().map(|id| (*id, 0)).collect();
+    let mut month_balances: BTreeMap<i32, BTreeMap<i32, i64>> = BTreeMap::new();
+
+    for tx in &all_txs {
+        let id_num = &tx.id_num;
+        let mut tx_changes = TxChanges::new();
+
+        let method_changes: Vec<(String, i64)> = match all_legs.get(id_num) {
+            Some(legs) => legs
+                .iter()
+                .flat_map(|(method, leg_amount)| get_tx_changes(method, &tx.tx_type, *leg_amount))
+                .collect(),
+            None => get_tx_changes(&tx.tx_method, &tx.tx_type, tx.amount),
+        };
+
+        for (method, change) in method_changes {
+            match method_ids.get(method.as_str()) {
+                Some(method_id) => *tx_changes.entry(*method_id).or_insert(0) += change,
+                None => {
+                    return Err(format!(
+                        "Transaction {id_num} uses {method} which is not a Transaction Method"
+                    )
+                    .into())
+                }
+            }
+        }
+
+        for (method_id, change) in &tx_changes {
+            *running_balance.get_mut(method_id).unwrap() += change;
+        }
+
+        let empty_changes = TxChanges::new();
+        let saved = saved_changes.get(id_num).unwrap_or(&empty_changes);
+
+        // zero changes are the same as no change at all
+        if without_zero(saved) != without_zero(&tx_changes) {
+            differences.push(format!(
+                "Changes of transaction {id_num} were {}, fixed to {}",
+                format_changes(saved, &method_names),
+                format_changes(&tx_changes, &method_names),
+            ));
+            sp.execute("DELETE FROM changes_all WHERE id_num = ?", [id_num])?;
+            for (method_id, change) in &tx_changes {
+                sp.execute(
+                    "INSERT INTO changes_all (id_num, method_id, amount) VALUES (?, ?, ?)",
+                    params![id_num, method_id, change],
+                )?;
+            }
+        }
+
+        let splitted_date = tx.date.split('-').collect::<Vec<&str>>();
+        let year = splitted_date[0].parse::<usize>()?;
+        let month = splitted_date[1].parse::<usize>()?;
+
+        // the balance rows of the year might be missing if the year was never added
+        add_new_year(&sp, year)?;
+        month_balances.insert(get_balance_id(month, year), running_balance.clone());
+    }
+
+    let final_balance_id = get_last_balance_id(&sp)?;
+    let saved_balances = get_saved_balances(&sp)?;
+
+    // the balance carried from the earlier months for the months that do not have any transaction
+    let mut carried_balance: BTreeMap<i32, i64> = method_names.keys().map(|id| (*id, 0)).collect();
+
+    for ((balance_id, method_id), saved_balance) in &saved_balances {
+        let (expected, label) = if *balance_id == final_balance_id {
+            (
+                running_balance[method_id],
+                format!("Final balance of {}", method_names[method_id]),
+            )
+        } else {
+            let month = format!("{}-{:02}", balance_id / 100, balance_id % 100);
+            match month_balances.get(balance_id) {
+                Some(balances) => {
+                    carried_balance = balances.clone();
+                    (
+                        balances[method_id],
+                        format!("Balance of {} on {month}", method_names[method_id]),
+                    )
+                }
+                None => (
+                    carried_balance[method_id],
+                    format!("Balance of {} on {month}", method_names[method_id]),
+                ),
+            }
+        };
+
+        if *saved_balance != expected {
+            differences.push(format!(
+                "{label} was {}, fixed to {}",
+                cents_to_amount(*saved_balance),
+                cents_to_amount(expected)
+            ));
+            sp.execute(
+                "UPDATE balance_all SET balance = ? WHERE id_num = ? AND method_id = ?",
+                params![expected, balance_id, method_id],
+            )?;
+        }
+    }
+
+    sp.commit()?;
+    Ok(differences)
+}
+
+/// Returns every transaction in the order the balances are calculated
+fn get_all_txs(conn: &Connection) -> sqlResult<Vec<SavedTx>> {
+    let mut statement = conn.prepare(
+        "SELECT id_num, date, tx_method, amount, tx_type FROM tx_all ORDER BY date, id_num",
+    )?;
+    let all_txs = statement
+        .query_map([], |row| {
+            Ok(SavedTx {
+                id_num: row.get(0)?,
+                date: row.get(1)?,
+                tx_method: row.get(2)?,
+                amount: row.get(3)?,
+                tx_type: row.get(4)?,
+            })
+        })?
+        .collect();
+    all_txs
+}
+
+/// Returns the name of every Transaction Method keyed by the method id
+fn get_method_names(conn: &Connection) -> sqlResult<BTreeMap<i32, String>> {
+    let mut statement = conn.prepare("SELECT id, name FROM tx_methods")?;
+    let method_names = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect();
+    method_names
+}
+
+/// Returns the Transaction Method and the amount of every leg of the split transactions keyed by the id_num
+fn get_all_legs(conn: &Connection) -> sqlResult<HashMap<i32, Vec<(String, i64)>>> {
+    let mut statement = conn.prepare(
+        "SELECT tx_legs.id_num, tx_methods.name, tx_legs.amount FROM tx_legs
+        INNER JOIN tx_methods ON tx_methods.id = tx_legs.method_id
+        ORDER BY tx_legs.id_num, tx_legs.leg_num",
+    )?;
+    let rows = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+
+    let mut all_legs: HashMap<i32, Vec<(String, i64)>> = HashMap::new();
+    for row in rows {
+        let (id_num, method, amount) = row?;
+        all_legs.entry(id_num).or_default().push((method, amount));
+    }
+    Ok(all_legs)
+}
+
+/// Returns the saved changes_all rows keyed by the id_num
+fn get_saved_changes(conn: &Connection) -> sqlResult<HashMap<i32, TxChanges>> {
+    let mut statement = conn.prepare("SELECT id_num, method_id, amount FROM changes_all")?;
+    let rows = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+
+    let mut saved_changes: HashMap<i32, TxChanges> = HashMap::new();
+    for row in rows {
+        let (id_num, method_id, amount) = row?;
+        saved_changes
+            .entry(id_num)
+            .or_default()
+            .insert(method_id, amount);
+    }
+    Ok(saved_changes)
+}
+
+/// Returns every saved balance_all row keyed by the id_num and the method id. The absolute final
+/// balance comes first and the months follow in order.
+fn get_saved_balances(conn: &Connection) -> sqlResult<BTreeMap<(i32, i32), i64>> {
+    let mut statement = conn.prepare("SELECT id_num, method_id, balance FROM balance_all")?;
+    let saved_balances = statement
+        .query_map([], |row| Ok(((row.get(0)?, row.get(1)?), row.get(2)?)))?
+        .collect();
+    saved_balances
+}
+
+/// Removes the methods that did not change
+fn without_zero(tx_changes: &TxChanges) -> TxChanges {
+    tx_changes
+        .iter()
+        .filter(|(_, change)| **change != 0)
+        .map(|(method_id, change)| (*method_id, *change))
+        .collect()
+}
+
+/// Turns the changes into a readable text
+/// return example: `Bank -10.00, Cash 10.00`
+fn format_changes(tx_changes: &TxChanges, method_names: &BTreeMap<i32, String>) -> String {
+    let changes = without_zero(tx_changes);
+    if changes.is_empty() {
+        return "nothing".to_string();
+    }
+    changes
+        .iter()
+        .map(|(method_id, change)| {
+            format!("{} {}", method_names[method_id], cents_to_amount(*change))
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,47 @@
+use rusqlite::{Connection, Result as sqlResult};
+use std::ops::Deref;
+
+/// A savepoint that is started from a shared connection. Unlike the rusqlite savepoint it does not need
+/// a mutable connection so it can be started on the connection of the app, inside a transaction or
+/// inside another savepoint. Everything done through it is rolled back if it is dropped without committing.
+pub struct DbSavepoint<'conn> {
+    conn: &'conn Connection,
+    committed: bool,
+}
+
+impl<'conn> DbSavepoint<'conn> {
+    /// Starts a new savepoint on the given connection
+    pub fn new(conn: &'conn Connection) -> sqlResult<Self> {
+        conn.execute_batch("SAVEPOINT rex_savepoint")?;
+        Ok(DbSavepoint {
+            conn,
+            committed: false,
+        })
+    }
+
+    /// Keeps everything that was done inside the savepoint. Outside of a transaction this commits to the database.
+    pub fn commit(mut self) -> sqlResult<()> {
+        self.conn.execute_batch("RELEASE rex_savepoint")?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Deref for DbSavepoint<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl Drop for DbSavepoint<'_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            // nothing else can be done if the rollback fails while dropping
+            let _ = self
+                .conn
+                .execute_batch("ROLLBACK TO rex_savepoint; RELEASE rex_savepoint");
+        }
+    }
+}
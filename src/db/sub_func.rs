@@ -1,10 +1,20 @@
+use crate::config::verify_ledger_name;
+use crate::db::manage_db::check_method_name;
+use crate::db::{
+    get_import_profiles, is_ofx_file, is_qif_file, parse_csv, ImportProfile, ImportSource,
+};
+use chrono::NaiveDate;
 use crossterm::{
+    event::{self, Event, KeyCode, KeyModifiers},
     execute,
-    terminal::{Clear, ClearType},
+    terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
 };
-use rusqlite::{Connection, Result as sqlResult};
+use rusqlite::{params, Connection, Result as sqlResult};
 use std::collections::{HashMap, HashSet};
+use std::error::Error;
 use std::io;
+use std::num::IntErrorKind;
+use std::path::Path;
 
 // This file contains a number of functions that makes calls to the database
 // to fetch relevant data which is later used in various structs. I didn't
@@ -13,23 +23,79 @@ use std::io;
 // All the DB calls are created keeping in mind that the program does not know the amount of
 // Transaction Methods that will be added by the user.
 
-/// Makes a call to the database to find out all the columns in the balance_all section
-/// so we can determine the number of TX Methods that has been added.
+/// Makes a call to the database to find out all the Transaction Methods that have been added,
+/// including the archived ones, in the order they were added.
 /// return example: `["source_1", "source_2", "source_3"]`
 pub fn get_all_tx_methods(conn: &Connection) -> Vec<String> {
-    // returns all transaction methods added to the database
-    let column_names = conn
-        .prepare("SELECT * FROM balance_all")
-        .expect("could not prepare statement");
-    let mut tx_methods = vec![];
-    for i in 1..99 {
-        let column = column_names.column_name(i);
-        match column {
-            Ok(c) => tx_methods.push(c.to_string()),
-            Err(_) => break,
+    get_names_where(conn, "SELECT name FROM tx_methods ORDER BY id")
+}
+
+/// Returns all the Transaction Methods that have been archived. Archived methods keep
+/// their balances and history but are hidden from the Balance widget.
+/// return example: `["source_2"]`
+pub fn get_archived_tx_methods(conn: &Connection) -> Vec<String> {
+    get_names_where(
+        conn,
+        "SELECT name FROM tx_methods WHERE archived = 1 ORDER BY id",
+    )
+}
+
+/// Returns all the Transaction Methods that are not archived, in the same order
+/// as get_all_tx_methods. These are the only methods new transactions can use.
+/// return example: `["source_1", "source_3"]`
+pub fn get_active_tx_methods(conn: &Connection) -> Vec<String> {
+    get_names_where(
+        conn,
+        "SELECT name FROM tx_methods WHERE archived = 0 ORDER BY id",
+    )
+}
+
+/// Returns all the categories that have been added in the order they were added
+/// return example: `["Food", "Rent", "Salary"]`
+pub fn get_all_categories(conn: &Connection) -> Vec<String> {
+    get_names_where(conn, "SELECT name FROM categories ORDER BY id")
+}
+
+/// Returns every tag that is used by at least one transaction sorted by name
+pub fn get_all_tags(conn: &Connection) -> Vec<String> {
+    get_names_where(conn, "SELECT DISTINCT tag FROM tx_tags ORDER BY tag")
+}
+
+/// Splits comma separated tags, removes the extra spaces, empty and repeated tags.
+/// example: `"work, trip,,work "` → `["work", "trip"]`
+pub fn split_tags(tags: &str) -> Vec<String> {
+    let mut all_tags: Vec<String> = Vec::new();
+    for tag in tags.split(',') {
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !all_tags.contains(&tag) {
+            all_tags.push(tag);
         }
     }
-    tx_methods
+    all_tags
+}
+
+/// Returns the id of the given category. An empty category returns None and a category that
+/// does not exist returns an error.
+pub fn get_category_id(conn: &Connection, category: &str) -> sqlResult<Option<i32>> {
+    if category.is_empty() {
+        return Ok(None);
+    }
+    conn.query_row(
+        "SELECT id FROM categories WHERE name = ?",
+        [category],
+        |row| row.get(0),
+    )
+    .map(Some)
+}
+
+/// Runs the given query that selects a single name column and collects the names
+fn get_names_where(conn: &Connection, query: &str) -> Vec<String> {
+    let mut statement = conn.prepare(query).expect("could not prepare statement");
+    statement
+        .query_map([], |row| row.get(0))
+        .expect("Error")
+        .map(|method| method.expect("Error"))
+        .collect()
 }
 
 /// The function is used to create dates in the form of strings to use the WHERE statement
@@ -39,135 +105,251 @@ pub fn get_all_tx_methods(conn: &Connection) -> Vec<String> {
 pub fn get_sql_dates(month: usize, year: usize) -> (String, String) {
     // returns dates from month and year to a format that is suitable for
     // database WHERE statement.
+    let datetime_1 = format!("{year}-{month:02}-01");
+    let datetime_2 = format!("{year}-{month:02}-31");
+    (datetime_1, datetime_2)
+}
 
-    let new_month: String = if month < 10 {
-        format!("0{}", month)
-    } else {
-        format!("{}", month)
+/// Turns an amount text with up to 2 decimal places into an integer amount of cents.
+/// All amounts are saved and calculated in cents in the database to avoid floating point errors.
+/// return example: `"159.19"` -> `15919`
+pub fn amount_to_cents(amount: &str) -> Result<i64, Box<dyn Error + Send + Sync>> {
+    let amount = amount.trim();
+    let (negative, amount) = match amount.strip_prefix('-') {
+        Some(a) => (true, a),
+        None => (false, amount),
     };
-    let mut new_year = year.to_string();
 
-    if year + 1 < 10 {
-        new_year = format!("202{}", year + 2);
+    let splitted = amount.split('.').collect::<Vec<&str>>();
+    if splitted.len() > 2 || splitted[0].is_empty() {
+        return Err(format!("Invalid amount {amount}").into());
+    }
+
+    let too_large = || format!("Amount {amount} is too large");
+    let whole = splitted[0].parse::<i64>().map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow => too_large(),
+        _ => e.to_string(),
+    })?;
+    if whole < 0 {
+        return Err(format!("Invalid amount {amount}").into());
+    }
+    let mut cents = 0;
+
+    if splitted.len() == 2 && !splitted[1].is_empty() {
+        let fraction = splitted[1];
+        if fraction.len() > 2 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("Invalid amount {amount}").into());
+        }
+        cents = format!("{fraction:0<2}").parse::<i64>()?;
+    }
+
+    let total = whole
+        .checked_mul(100)
+        .and_then(|a| a.checked_add(cents))
+        .ok_or_else(too_large)?;
+    if negative {
+        Ok(-total)
+    } else {
+        Ok(total)
+    }
+}
+
+/// Turns an amount of cents into a text with 2 decimal places to show in the interface.
+/// return example: `-15919` -> `"-159.19"`
+pub fn cents_to_amount(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.unsigned_abs();
+    format!("{sign}{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Turns a balance change saved in cents into the text shown in the Changes row.
+/// Positive changes start with ↑ and negative changes with ↓.
+/// return example: `-15900` -> `"↓159.00"`
+pub fn cents_to_change(cents: i64) -> String {
+    if cents > 0 {
+        format!("↑{}", cents_to_amount(cents))
+    } else if cents < 0 {
+        format!("↓{}", cents_to_amount(-cents))
+    } else {
+        cents_to_amount(0)
     }
-    let datetime_1 = format!("{}-{}-01", new_year, new_month);
-    let datetime_2 = format!("{}-{}-31", new_year, new_month);
-    (datetime_1, datetime_2)
 }
 
-/// Gathers all the balance of all sources from the previous month or from earlier.
-/// If all the previous month's balances are 0, returns 0
-/// return example: `{"source_1": 10.50, "source_2": 100.0}`
+/// Returns the id_num of the balance_all row that holds the balance of the given month and year.
+/// Month starts from 1. The absolute final balance is always saved at id_num 0.
+/// return example: `202207`
+pub fn get_balance_id(month: usize, year: usize) -> i32 {
+    (year * 100 + month) as i32
+}
+
+/// Returns all the years that have their monthly rows added in the balance_all table
+/// return example: `["2022", "2023", "2026"]`
+pub fn get_all_years(conn: &Connection) -> Vec<String> {
+    let mut statement = conn
+        .prepare("SELECT DISTINCT id_num / 100 FROM balance_all WHERE id_num > 0 ORDER BY id_num")
+        .expect("could not prepare statement");
+
+    let rows = statement
+        .query_map([], |row| {
+            let year: i32 = row.get(0)?;
+            Ok(year.to_string())
+        })
+        .expect("Error");
+
+    let mut all_years = vec![];
+    for i in rows {
+        all_years.push(i.unwrap());
+    }
+    all_years
+}
+
+/// Adds the 12 monthly balance rows of every Transaction Method of the given year to the balance_all table
+/// if they were not added before. Rows are only created once a year is needed so the database
+/// does not have to know the range of years beforehand. Each new row starts with the balance carried
+/// from the latest month before it.
+pub fn add_new_year(conn: &Connection, year: usize) -> sqlResult<()> {
+    // a new month starts with the balance of the month before it
+    for month in 1..13 {
+        conn.execute(
+            "INSERT OR IGNORE INTO balance_all (id_num, method_id, balance)
+            SELECT ?1, id, COALESCE((SELECT balance FROM balance_all WHERE method_id = tx_methods.id
+            AND id_num > 0 AND id_num < ?1 ORDER BY id_num DESC LIMIT 1), 0) FROM tx_methods",
+            [get_balance_id(month, year)],
+        )?;
+    }
+    Ok(())
+}
+
+/// Overwrites the balance of a Transaction Method saved at the given balance_all id_num
+pub fn set_balance(conn: &Connection, id_num: i32, tx_method: &str, balance: i64) -> sqlResult<()> {
+    conn.execute(
+        "UPDATE balance_all SET balance = ?
+        WHERE id_num = ? AND method_id = (SELECT id FROM tx_methods WHERE name = ?)",
+        params![balance, id_num, tx_method],
+    )?;
+    Ok(())
+}
+
+/// Gathers all the balance of all sources from the previous month or from earlier in cents.
+/// Every month row keeps the balance carried from the months before it so the latest row is used
+/// even if its balance is 0. Returns 0 if no month before it was added.
+/// return example: `{"source_1": 1050, "source_2": 10000}`
 pub fn get_last_time_balance(
     conn: &Connection,
     month: usize,
     year: usize,
     tx_method: &Vec<String>,
-) -> HashMap<String, f64> {
-    // We can get the id_num of the month which is saved in the database based on the
-    // month and year index there is passed.
-    let mut target_id_num = month as i32 + (year as i32 * 12);
+) -> HashMap<String, i64> {
+    // The month index starts from 0 so the id_num of the given month index is the id_num
+    // of the previous month. Everything at or below it was saved before the given month.
+    let target_id_num = get_balance_id(month, year);
 
     let mut final_value = HashMap::new();
     for i in tx_method {
-        final_value.insert(i.to_string(), 0.0);
-    }
-
-    if target_id_num == 0 {
-        return final_value;
+        final_value.insert(i.to_string(), 0);
     }
 
     // keep track of how many method's balances were discovered.
-    // If all of them are found, break the loop
-    let mut checked_methods: Vec<&str> = vec![];
+    // If all of them are found, stop checking the earlier months
+    let mut checked_methods: Vec<String> = vec![];
 
-    let mut breaking_vec = vec![];
-    for _i in tx_method {
-        breaking_vec.push(0.0)
-    }
-    // we need to go till the first month of 2022 or until the last balance of all tx methods are found
-    loop {
-        let mut query = format!("SELECT {:?} FROM balance_all WHERE id_num = ?", tx_method);
-        query = query.replace('[', "");
-        query = query.replace(']', "");
-
-        let final_balance = conn
-            .query_row(&query, [target_id_num], |row| {
-                let mut final_data: Vec<f64> = Vec::new();
-
-                // We don't know the amount of tx method so we need to loop
-                for i in 0..tx_method.len() {
-                    let to_push: String = row.get(i).unwrap();
-                    let final_value = to_push.parse::<f64>().unwrap();
-                    final_data.push(final_value);
-                }
-                Ok(final_data)
-            })
-            .unwrap();
+    let mut statement = conn
+        .prepare(
+            "SELECT tx_methods.name, balance_all.balance FROM balance_all
+            INNER JOIN tx_methods ON tx_methods.id = balance_all.method_id
+            WHERE balance_all.id_num > 0 AND balance_all.id_num <= ?
+            ORDER BY balance_all.id_num DESC",
+        )
+        .expect("could not prepare statement");
 
-        target_id_num -= 1;
+    let rows = statement
+        .query_map([target_id_num], |row| {
+            let method: String = row.get(0)?;
+            let balance: i64 = row.get(1)?;
+            Ok((method, balance))
+        })
+        .expect("Error");
 
-        // add the data in the return variable only if the value is not 0 and has not been previously discovered
-        for i in 0..tx_method.len() {
-            if !checked_methods.contains(&tx_method[i].as_ref()) && final_balance[i] != 0.0 {
-                *final_value.get_mut(&tx_method[i]).unwrap() = final_balance[i];
-                checked_methods.push(&tx_method[i]);
-            }
+    // we need to go till the first saved month or until the last balance of all tx methods are found.
+    // Only the latest balance of each method is taken
+    for row in rows {
+        let (method, balance) = row.unwrap();
+
+        if final_value.contains_key(&method) && !checked_methods.contains(&method) {
+            *final_value.get_mut(&method).unwrap() = balance;
+            checked_methods.push(method);
         }
 
-        // We will keep the loop ongoing until we hit a non-zero balance for all tx method or
-        // the id number goes to zero.
-        if target_id_num == 0 || checked_methods.len() == tx_method.len() {
+        if checked_methods.len() == tx_method.len() {
             break;
         }
     }
     final_value
 }
 
-/// The functions sends all the changes that happened after transactions on the month and year provided
-pub fn get_all_changes(conn: &Connection, month: usize, year: usize) -> Vec<Vec<String>> {
+/// The functions sends all the changes that happened after transactions on the month and year provided.
+/// Changes are in cents, negative values mean the balance went down.
+pub fn get_all_changes(conn: &Connection, month: usize, year: usize) -> Vec<Vec<i64>> {
     // returns all balance changes recorded within a given date
 
-    let mut final_result = Vec::new();
+    let mut final_result: Vec<Vec<i64>> = Vec::new();
     let tx_methods = get_all_tx_methods(conn);
 
     let (datetime_1, datetime_2) = get_sql_dates(month + 1, year);
 
+    // only the changes that are not zero are saved so every tx of the month is joined with its changes, if any
     let mut statement = conn
-        .prepare("SELECT * FROM changes_all Where date BETWEEN date(?) AND date(?) ORDER BY date, id_num")
+        .prepare(
+            "SELECT tx_all.id_num, tx_methods.name, changes_all.amount FROM tx_all
+            LEFT JOIN changes_all ON changes_all.id_num = tx_all.id_num
+            LEFT JOIN tx_methods ON tx_methods.id = changes_all.method_id
+            WHERE tx_all.date BETWEEN date(?) AND date(?) ORDER BY tx_all.date, tx_all.id_num",
+        )
         .expect("could not prepare statement");
 
     let rows = statement
         .query_map([datetime_1, datetime_2], |row| {
-            let mut balance_vec: Vec<String> = Vec::new();
-            // Why start at 2? Because the first two rows are date and id_num
-            for i in 2..tx_methods.len() + 2 {
-                balance_vec.push(row.get(i).unwrap());
-            }
-            Ok(balance_vec)
+            let id_num: i32 = row.get(0)?;
+            let method: Option<String> = row.get(1)?;
+            let amount: Option<i64> = row.get(2)?;
+            Ok((id_num, method, amount))
         })
         .expect("Error");
 
+    let mut last_id_num = None;
     for i in rows {
-        final_result.push(i.unwrap());
+        let (id_num, method, amount) = i.unwrap();
+
+        if last_id_num != Some(id_num) {
+            final_result.push(vec![0; tx_methods.len()]);
+            last_id_num = Some(id_num);
+        }
+
+        if let (Some(method), Some(amount)) = (method, amount) {
+            let index = tx_methods.iter().position(|a| a == &method).unwrap();
+            final_result.last_mut().unwrap()[index] = amount;
+        }
     }
     final_result
 }
 
 /// This is a multi-use function used to retrieving all Transaction within a given date, balance and the id_num related to them.
 /// Once the transactions are fetched, we immediately start calculating the current balance values after each transaction happened
-/// and finally return all of them in a tuple
+/// and finally return all of them in a tuple. Transaction amounts are formatted for the interface
+/// while the balances are kept in cents. Transactions without a category get an empty category.
+/// tx example: `["19-07-2022", "Groceries", "Cash", "15.00", "Expense", "Food"]`
 pub fn get_all_txs(
     conn: &Connection,
     month: usize,
     year: usize,
-) -> (Vec<Vec<String>>, Vec<Vec<String>>, Vec<String>) {
+) -> (Vec<Vec<String>>, Vec<Vec<i64>>, Vec<String>) {
     // returns all transactions recorded within a given date + balance changes + the relevant id_num
 
     let all_tx_methods = get_all_tx_methods(conn);
 
     let mut final_all_txs: Vec<Vec<String>> = Vec::new();
-    let mut final_all_balances: Vec<Vec<String>> = Vec::new();
+    let mut final_all_balances: Vec<Vec<i64>> = Vec::new();
+    let mut all_amounts: Vec<i64> = Vec::new();
     let mut all_id_num = Vec::new();
 
     // we will go through the last month balances and add/subtract
@@ -181,7 +363,10 @@ pub fn get_all_txs(
     // preparing the query for db, getting current month's all transactions
     let mut statement = conn
         .prepare(
-            "SELECT * FROM tx_all Where date BETWEEN date(?) AND date(?) ORDER BY date, id_num",
+            "SELECT tx_all.date, tx_all.details, tx_all.tx_method, tx_all.amount, tx_all.tx_type, tx_all.id_num, categories.name,
+            (SELECT group_concat(tag, ', ') FROM tx_tags WHERE tx_tags.id_num = tx_all.id_num)
+            FROM tx_all LEFT JOIN categories ON categories.id = tx_all.category_id
+            WHERE tx_all.date BETWEEN date(?) AND date(?) ORDER BY tx_all.date, tx_all.id_num",
         )
         .expect("could not prepare statement");
 
@@ -189,7 +374,10 @@ pub fn get_all_txs(
         .query_map([&datetime_1, &datetime_2], |row| {
             // collect the row data and put them in a vec
             let date: String = row.get(0).unwrap();
+            let amount: i64 = row.get(3).unwrap();
             let id_num: i32 = row.get(5).unwrap();
+            let category: Option<String> = row.get(6).unwrap();
+            let tags: Option<String> = row.get(7).unwrap();
             let splitted_date = date.split('-');
             let collected_date: Vec<&str> = splitted_date.collect();
             let new_date = format!(
@@ -197,40 +385,70 @@ pub fn get_all_txs(
                 collected_date[2], collected_date[1], collected_date[0]
             );
 
-            Ok(vec![
-                new_date,
-                row.get(1).unwrap(),
-                row.get(2).unwrap(),
-                row.get(3).unwrap(),
-                row.get(4).unwrap(),
-                id_num.to_string(),
-            ])
+            Ok((
+                vec![
+                    new_date,
+                    row.get(1).unwrap(),
+                    row.get(2).unwrap(),
+                    cents_to_amount(amount),
+                    row.get(4).unwrap(),
+                    category.unwrap_or_default(),
+                    tags.unwrap_or_default(),
+                    id_num.to_string(),
+                ],
+                amount,
+            ))
         })
         .expect("Error");
 
     for i in rows {
         // data contains all tx data of a transaction
-        let mut data = i.unwrap();
+        let (mut data, amount) = i.unwrap();
         let id_num = &data.pop().unwrap();
         all_id_num.push(id_num.to_string());
+        all_amounts.push(amount);
         final_all_txs.push(data);
     }
 
-    for i in &final_all_txs {
+    for ((i, amount), id_num) in final_all_txs.iter().zip(all_amounts).zip(&all_id_num) {
         // this is where the calculation for the balance happens. We will loop through each tx,
         // look at the tx type, tx method and add/subtract the amount on last month balance which was fetched earlier
         // while adding the balance data after each calculation is done inside a vector.
 
+        // split transactions change the balance of the method of every leg
+        let legs = if i[2] == "Split" {
+            get_tx_legs(conn, id_num.parse().unwrap())
+        } else {
+            Vec::new()
+        };
+
+        if !legs.is_empty() {
+            for leg in &legs {
+                let leg_amount = amount_to_cents(&leg[1]).unwrap();
+                if i[4] == "Expense" {
+                    *last_month_balance.get_mut(&leg[0]).unwrap() -= leg_amount;
+                } else {
+                    *last_month_balance.get_mut(&leg[0]).unwrap() += leg_amount;
+                }
+            }
+            final_all_balances.push(
+                all_tx_methods
+                    .iter()
+                    .map(|method| last_month_balance[method])
+                    .collect(),
+            );
+            continue;
+        }
+
         // collect data inside variables
         let tx_type = &i[4];
-        let amount = &i[3].to_string().parse::<f64>().unwrap();
         let tx_method = &i[2];
 
         // If the transaction is not a transfer, default balance goes to new_balance_from
         // and new_balance_to remains empty. On transfer TX both of them are used
 
-        let mut new_balance_from: f64 = 0.0;
-        let mut new_balance_to: f64 = 0.0;
+        let mut new_balance_from: i64 = 0;
+        let mut new_balance_to: i64 = 0;
 
         let mut from_method = "".to_string();
         let mut to_method = "".to_string();
@@ -251,7 +469,7 @@ pub fn get_all_txs(
 
         // make changes to the balance map based on the tx
         // for transfer TX first block executes
-        if new_balance_to != 0.0 {
+        if tx_type == "Transfer" {
             *last_month_balance.get_mut(&from_method).unwrap() = new_balance_from;
             *last_month_balance.get_mut(&to_method).unwrap() = new_balance_to;
         } else {
@@ -261,7 +479,7 @@ pub fn get_all_txs(
         // push all the changes gathered to the return variable
         let mut to_push = vec![];
         for i in &all_tx_methods {
-            to_push.push(format!("{:.2}", last_month_balance[i]))
+            to_push.push(last_month_balance[i])
         }
 
         final_all_balances.push(to_push);
@@ -270,62 +488,72 @@ pub fn get_all_txs(
     // This one here is added as an insurance. If somehow the balance table is corrupted,
     // this will correct the balance amount on that month's balance row. This checks the final index balance
     // in the previously generated vector and pushes it to the db on the relevant row
-    if !final_all_balances.is_empty() {
-        let final_index = final_all_balances.len() - 1;
-
-        let mut balance_query = "UPDATE balance_all SET ".to_string();
-
-        for i in 0..final_all_balances[final_index].len() {
-            if i != final_all_balances[final_index].len() - 1 {
-                balance_query.push_str(&format!(
-                    r#""{}" = "{}", "#,
-                    all_tx_methods[i], final_all_balances[final_index][i]
-                ))
-            } else {
-                balance_query.push_str(&format!(
-                    r#""{}" = "{}" "#,
-                    all_tx_methods[i], final_all_balances[final_index][i]
-                ))
-            }
+    if let Some(final_balance) = final_all_balances.last() {
+        let target_id_num = get_balance_id(month + 1, year);
+        for (method, balance) in all_tx_methods.iter().zip(final_balance) {
+            set_balance(conn, target_id_num, method, *balance)
+                .expect("Error updating balance query");
         }
-        let target_id_num = month as i32 + 1 + (year as i32 * 12);
-        balance_query.push_str(&format!("WHERE id_num = {target_id_num}"));
-        conn.execute(&balance_query, [])
-            .expect("Error updating balance query");
     }
 
     (final_all_txs, final_all_balances, all_id_num)
 }
 
+/// Returns the legs of a split transaction in the order they were added. Transactions that are
+/// not split return an empty vector.
+/// return example: `[["Card", "30.00", "Food"], ["Cash", "12.50", ""]]`
+pub fn get_tx_legs(conn: &Connection, id_num: i32) -> Vec<Vec<String>> {
+    let mut statement = conn
+        .prepare(
+            "SELECT tx_methods.name, tx_legs.amount, categories.name FROM tx_legs
+            INNER JOIN tx_methods ON tx_methods.id = tx_legs.method_id
+            LEFT JOIN categories ON categories.id = tx_legs.category_id
+            WHERE tx_legs.id_num = ? ORDER BY tx_legs.leg_num",
+        )
+        .expect("could not prepare statement");
+
+    statement
+        .query_map([id_num], |row| {
+            let amount: i64 = row.get(1)?;
+            let category: Option<String> = row.get(2)?;
+            Ok(vec![
+                row.get(0)?,
+                cents_to_amount(amount),
+                category.unwrap_or_default(),
+            ])
+        })
+        .expect("Error")
+        .map(|leg| leg.expect("Error"))
+        .collect()
+}
+
 /// Returns the a vector with data required to create the Changes row for zero changes in the home page.
 pub fn get_empty_changes(conn: &Connection) -> Vec<String> {
     // function for quick vec with 0 changes for adding in widget
     let tx_methods = get_all_tx_methods(conn);
     let mut changes = vec!["Changes".to_string()];
     for _i in tx_methods {
-        changes.push(format!("{:.2}", 0.0))
+        changes.push(cents_to_change(0))
     }
     changes
 }
 
 /// Returns the absolute final balance which is the balance saved after each transaction was counted
-/// or the last row on balance_all table.
-pub fn get_last_balances(conn: &Connection, tx_method: &Vec<String>) -> Vec<String> {
-    let mut query = format!(
-        "SELECT {:?} FROM balance_all ORDER BY id_num DESC LIMIT 1",
-        tx_method
-    );
-    query = query.replace('[', "");
-    query = query.replace(']', "");
+/// or the first row on balance_all table. The balances are in cents.
+pub fn get_last_balances(conn: &Connection, tx_method: &Vec<String>) -> Vec<i64> {
+    let mut statement = conn
+        .prepare(
+            "SELECT balance_all.balance FROM balance_all
+            INNER JOIN tx_methods ON tx_methods.id = balance_all.method_id
+            WHERE tx_methods.name = ? ORDER BY balance_all.id_num LIMIT 1",
+        )
+        .expect("could not prepare statement");
 
-    let final_balance = conn.query_row(&query, [], |row| {
-        let mut final_data: Vec<String> = Vec::new();
-        for i in 0..tx_method.len() {
-            final_data.push(row.get(i).unwrap());
-        }
-        Ok(final_data)
-    });
-    final_balance.unwrap()
+    let mut final_balance = vec![];
+    for i in tx_method {
+        final_balance.push(statement.query_row([i], |row| row.get(0)).unwrap());
+    }
+    final_balance
 }
 
 /// Returns the last id_num recorded by tx_all table
@@ -338,21 +566,115 @@ pub fn get_last_tx_id(conn: &Connection) -> sqlResult<i32> {
     last_id
 }
 
-/// Returns the last id_num recorded by balance_all table or the id_num of the absolute final balance
+/// Returns the id_num of the absolute final balance which is the lowest id_num recorded by balance_all table
 pub fn get_last_balance_id(conn: &Connection) -> sqlResult<i32> {
     let last_id: sqlResult<i32> = conn.query_row(
-        "SELECT id_num FROM balance_all ORDER BY id_num DESC LIMIT 1",
+        "SELECT id_num FROM balance_all ORDER BY id_num LIMIT 1",
         [],
         |row| row.get(0),
     );
     last_id
 }
 
+/// Returns the number of rows that were changed through the connection since it was opened.
+/// Used to find out whether the data saved in memory is outdated.
+pub fn get_total_changes(conn: &Connection) -> i64 {
+    conn.query_row("SELECT total_changes()", [], |row| row.get(0))
+        .unwrap()
+}
+
+/// Asks the user to pick one of the ledgers inside the data directory by its name or number.
+/// A name that is not in the list creates a new ledger.
+pub fn get_user_ledger(all_ledgers: &[String]) -> String {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+
+    let mut ledger_line = "Found Ledgers: ".to_string();
+    for (index, ledger) in all_ledgers.iter().enumerate() {
+        ledger_line.push_str(&format!("\n{}. {ledger}", index + 1))
+    }
+
+    loop {
+        let mut line = String::new();
+
+        println!("{ledger_line}\n");
+        println!("\nEnter the ledger name or number to open. A new name creates a new ledger:");
+
+        std::io::stdin().read_line(&mut line).unwrap();
+        let line = line.trim();
+
+        if let Some(ledger) = line
+            .parse::<usize>()
+            .ok()
+            .and_then(|a| all_ledgers.get(a.wrapping_sub(1)))
+        {
+            return ledger.to_string();
+        }
+
+        if verify_ledger_name(line) {
+            return line.to_string();
+        }
+
+        execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+        println!("\nLedger name not acceptable. Only letters, numbers, - and _ can be used");
+    }
+}
+
+/// Asks the user to type a passphrase without showing it in the terminal. Empty passphrases are not
+/// accepted. If confirm is true, the passphrase has to be typed twice the same way.
+pub fn get_user_passphrase(message: &str, confirm: bool) -> String {
+    loop {
+        println!("{message}");
+        let passphrase = read_hidden_line();
+
+        if passphrase.is_empty() {
+            println!("The passphrase cannot be empty\n");
+            continue;
+        }
+        if !confirm {
+            return passphrase;
+        }
+
+        println!("Enter the passphrase again to confirm:");
+        if read_hidden_line() == passphrase {
+            return passphrase;
+        }
+        println!("The passphrases did not match\n");
+    }
+}
+
+/// Reads a line from the terminal without echoing the typed characters. Ctrl+C exits the app.
+fn read_hidden_line() -> String {
+    let mut line = String::new();
+    enable_raw_mode().unwrap();
+
+    loop {
+        if let Event::Key(key) = event::read().unwrap() {
+            match key.code {
+                KeyCode::Enter => break,
+                KeyCode::Backspace => {
+                    line.pop();
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    disable_raw_mode().unwrap();
+                    std::process::exit(1);
+                }
+                KeyCode::Char(c) => line.push(c),
+                _ => {}
+            }
+        }
+    }
+
+    disable_raw_mode().unwrap();
+    println!();
+    line
+}
+
 /// This function asks user to input one or more Transaction Method names.
 /// Once the collection is done sends to the database for adding the columns.
 /// This functions is both used when creating the initial db and when updating
 /// the database with new transaction methods.
-pub fn get_user_tx_methods(add_new_method: bool) -> Vec<String> {
+pub fn get_user_tx_methods(add_new_method: bool, conn: &Connection) -> Vec<String> {
     let mut stdout = io::stdout();
 
     // this command clears up the terminal. This is added so the terminal doesn't get
@@ -367,8 +689,7 @@ pub fn get_user_tx_methods(add_new_method: bool) -> Vec<String> {
     // if we are adding more tx methods to an existing database, we need to
     // to get the existing columns to prevent duplicates/error.
     if add_new_method {
-        let conn = Connection::open("data.sqlite").expect("Could not connect to database");
-        cu_tx_methods = get_all_tx_methods(&conn);
+        cu_tx_methods = get_all_tx_methods(conn);
         for i in &cu_tx_methods {
             method_line.push_str(&format!("\n- {i}"))
         }
@@ -407,7 +728,7 @@ or ', '. Example: Bank, Cash, PayPal.\n\nEnter Transaction Methods:");
 
         // Unknown how it works. Taken from somewhere in the internet
         let set: HashSet<_> = splitted.drain(..).collect();
-        splitted.extend(set.into_iter());
+        splitted.extend(set);
 
         let mut filtered_splitted = vec![];
 
@@ -422,13 +743,27 @@ or ', '. Example: Bank, Cash, PayPal.\n\nEnter Transaction Methods:");
             }
         }
         // Check if the input is not empty. If yes, start from the beginning
-        if (splitted == vec!["".to_string()] && !add_new_method)
-            || (filtered_splitted == vec!["".to_string()] && add_new_method)
-            || (!add_new_method && splitted.is_empty())
-            || (add_new_method && filtered_splitted.is_empty())
-        {
+        let empty_input = if add_new_method {
+            filtered_splitted.is_empty() || filtered_splitted == vec!["".to_string()]
+        } else {
+            splitted.is_empty() || splitted == vec![""]
+        };
+
+        // existing methods were already removed so only the name itself is checked
+        let name_error = if add_new_method {
+            filtered_splitted.clone()
+        } else {
+            splitted.iter().map(|a| a.to_string()).collect()
+        }
+        .iter()
+        .find_map(|a| check_method_name(a, &[]).err());
+
+        if empty_input {
             execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
             println!("\nTransaction Method input cannot be empty and existing Transaction Methods cannot be used twice");
+        } else if let Some(e) = name_error {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\n{e}");
         } else {
             if add_new_method {
                 for i in &filtered_splitted {
@@ -466,3 +801,907 @@ or ', '. Example: Bank, Cash, PayPal.\n\nEnter Transaction Methods:");
     }
     db_tx_methods
 }
+
+/// Asks the user for a Transaction Method to rename and its new name through the terminal.
+/// The new name cannot be empty, already exist or contain ` to ` as it is used to separate
+/// the methods of a transfer. Returns `["".to_string()]` if the operation is cancelled.
+/// return example: `["Old Bank", "New Bank"]`
+pub fn get_user_rename_data(conn: &Connection) -> Vec<String> {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+    let cu_tx_methods = get_all_tx_methods(conn);
+
+    let mut method_line = "Currently added Transaction Methods: ".to_string();
+    for i in &cu_tx_methods {
+        method_line.push_str(&format!("\n- {i}"))
+    }
+
+    loop {
+        let mut old_method = String::new();
+        let mut new_method = String::new();
+        let mut verify_line = String::new();
+
+        println!("{method_line}\n");
+        println!(
+            "\nInput 'Cancel' to cancel the operation\n\nEnter the Transaction Method to rename:"
+        );
+
+        std::io::stdin().read_line(&mut old_method).unwrap();
+        old_method = old_method.trim().to_string();
+
+        if old_method.to_lowercase().starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        if !cu_tx_methods.contains(&old_method) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nTransaction Method {old_method} was not found");
+            continue;
+        }
+
+        println!("\nEnter the new name for {old_method}:");
+
+        std::io::stdin().read_line(&mut new_method).unwrap();
+        new_method = new_method.trim().to_string();
+
+        if new_method.to_lowercase().starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        if let Err(e) = check_method_name(&new_method, &cu_tx_methods) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\n{e}");
+            continue;
+        }
+
+        println!("\nRename {old_method} to {new_method}? y/n");
+        std::io::stdin().read_line(&mut verify_line).unwrap();
+
+        if verify_line.trim().to_lowercase().starts_with('y') {
+            return vec![old_method, new_method];
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+        }
+    }
+}
+
+/// Asks the user through the terminal whether to archive, restore or delete a Transaction Method
+/// and which method to use. Returns `["".to_string()]` if the operation is cancelled.
+/// return example: `["Archive", "Old Bank"]`
+pub fn get_user_manage_data(conn: &Connection) -> Vec<String> {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+    let cu_tx_methods = get_all_tx_methods(conn);
+    let archived_methods = get_archived_tx_methods(conn);
+
+    let mut method_line = "Currently added Transaction Methods: ".to_string();
+    for i in &cu_tx_methods {
+        if archived_methods.contains(i) {
+            method_line.push_str(&format!("\n- {i} (Archived)"))
+        } else {
+            method_line.push_str(&format!("\n- {i}"))
+        }
+    }
+
+    loop {
+        let mut action = String::new();
+        let mut tx_method = String::new();
+        let mut verify_line = String::new();
+
+        println!("{method_line}\n");
+        println!("\nArchived methods are hidden and cannot be used for new transactions. Only methods without \
+any transaction and a zero balance can be deleted.\n\nInput 'Cancel' to cancel the operation\n\nEnter the action. Values: Archive/Restore/Delete/A/R/D:");
+
+        std::io::stdin().read_line(&mut action).unwrap();
+        action = action.trim().to_lowercase();
+
+        if action.starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        let action = if action.starts_with('a') {
+            "Archive"
+        } else if action.starts_with('r') {
+            "Restore"
+        } else if action.starts_with('d') {
+            "Delete"
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nAction not acceptable. Values: Archive/Restore/Delete/A/R/D");
+            continue;
+        };
+
+        println!("\nEnter the Transaction Method:");
+
+        std::io::stdin().read_line(&mut tx_method).unwrap();
+        tx_method = tx_method.trim().to_string();
+
+        if tx_method.to_lowercase().starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        if !cu_tx_methods.contains(&tx_method) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nTransaction Method {tx_method} was not found");
+            continue;
+        }
+
+        println!("\n{action} {tx_method}? y/n");
+        std::io::stdin().read_line(&mut verify_line).unwrap();
+
+        if verify_line.trim().to_lowercase().starts_with('y') {
+            return vec![action.to_string(), tx_method];
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+        }
+    }
+}
+
+/// Asks the user what to do with the categories. Returns the action with the category and the
+/// new name for renaming. Returns an empty vector if the operation is cancelled.
+///
+/// return example: `["Rename", "Food", "Grocery"]`, `["Add", "Rent"]`
+pub fn get_user_category_data(conn: &Connection) -> Vec<String> {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+    let cu_categories = get_all_categories(conn);
+
+    let mut category_line = "Currently added Categories: ".to_string();
+    for i in &cu_categories {
+        category_line.push_str(&format!("\n- {i}"))
+    }
+
+    loop {
+        let mut action = String::new();
+        let mut category = String::new();
+        let mut new_category = String::new();
+        let mut verify_line = String::new();
+
+        println!("{category_line}\n");
+        println!("\nDeleting a category keeps its transactions without a category.\n\nInput 'Cancel' to cancel the operation\n\nEnter the action. Values: Add/Rename/Delete/A/R/D:");
+
+        std::io::stdin().read_line(&mut action).unwrap();
+        action = action.trim().to_lowercase();
+
+        if action.starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        let action = if action.starts_with('a') {
+            "Add"
+        } else if action.starts_with('r') {
+            "Rename"
+        } else if action.starts_with('d') {
+            "Delete"
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nAction not acceptable. Values: Add/Rename/Delete/A/R/D");
+            continue;
+        };
+
+        println!("\nEnter the Category:");
+
+        std::io::stdin().read_line(&mut category).unwrap();
+        category = category.trim().to_string();
+
+        if category.to_lowercase().starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        if category.is_empty() {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nCategory cannot be empty");
+            continue;
+        } else if action == "Add" && cu_categories.contains(&category) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nCategory {category} already exists");
+            continue;
+        } else if action != "Add" && !cu_categories.contains(&category) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nCategory {category} was not found");
+            continue;
+        }
+
+        if action == "Rename" {
+            println!("\nEnter the new name for {category}:");
+
+            std::io::stdin().read_line(&mut new_category).unwrap();
+            new_category = new_category.trim().to_string();
+
+            if new_category.to_lowercase().starts_with("cancel") {
+                return vec!["".to_string()];
+            }
+
+            if new_category.is_empty() || cu_categories.contains(&new_category) {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\nThe new name cannot be empty or an existing Category");
+                continue;
+            }
+
+            println!("\nRename {category} to {new_category}? y/n");
+        } else {
+            println!("\n{action} {category}? y/n");
+        }
+        std::io::stdin().read_line(&mut verify_line).unwrap();
+
+        if verify_line.trim().to_lowercase().starts_with('y') {
+            if action == "Rename" {
+                return vec![action.to_string(), category, new_category];
+            }
+            return vec![action.to_string(), category];
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+        }
+    }
+}
+
+/// Asks the user through the terminal for the details of a new recurring transaction template.
+/// Transfer templates take the Transaction Methods as `From to To`. The end date is left empty
+/// if the template should never end. Returns `["".to_string()]` if the operation is cancelled.
+/// return example: `["Rent", "Bank", "500.00", "Expense", "Housing", "home", "Monthly", "2022-01-01", ""]`
+pub fn get_user_recurring_data(conn: &Connection) -> Vec<String> {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+    let cu_tx_methods = get_active_tx_methods(conn);
+    let cu_categories = get_all_categories(conn);
+
+    let mut info_line = "Currently active Transaction Methods: ".to_string();
+    for i in &cu_tx_methods {
+        info_line.push_str(&format!("\n- {i}"))
+    }
+    info_line.push_str("\n\nCurrently added Categories: ");
+    for i in &cu_categories {
+        info_line.push_str(&format!("\n- {i}"))
+    }
+
+    // reads a trimmed line from the terminal. None if the user wants to cancel
+    let read_input = || {
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim().to_string();
+        if input.to_lowercase().starts_with("cancel") {
+            None
+        } else {
+            Some(input)
+        }
+    };
+
+    loop {
+        let mut verify_line = String::new();
+
+        println!("{info_line}\n");
+        println!("\nInput 'Cancel' to cancel the operation\n\nEnter the Details:");
+        let details = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+
+        println!("\nEnter the Transaction Type. Values: Expense/Income/Transfer/E/I/T:");
+        let tx_type = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+        let tx_type = match tx_type.to_lowercase().chars().next() {
+            Some('e') => "Expense",
+            Some('i') => "Income",
+            Some('t') => "Transfer",
+            _ => {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!(
+                    "\nTransaction Type not acceptable. Values: Expense/Income/Transfer/E/I/T"
+                );
+                continue;
+            }
+        };
+
+        if tx_type == "Transfer" {
+            println!("\nEnter the Transaction Methods. Example: From to To");
+        } else {
+            println!("\nEnter the Transaction Method:");
+        }
+        let tx_method = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+
+        let method_accepted = if tx_type == "Transfer" {
+            let from_to = tx_method.split(" to ").collect::<Vec<&str>>();
+            from_to.len() == 2
+                && from_to[0] != from_to[1]
+                && from_to
+                    .iter()
+                    .all(|a| cu_tx_methods.contains(&a.to_string()))
+        } else {
+            cu_tx_methods.contains(&tx_method)
+        };
+
+        if !method_accepted {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nTransaction Method {tx_method} is not an active Transaction Method");
+            continue;
+        }
+
+        println!("\nEnter the Amount:");
+        let amount = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+
+        let amount = match amount_to_cents(&amount) {
+            Ok(cents) if cents > 0 => cents_to_amount(cents),
+            _ => {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\nAmount {amount} is not a valid amount above 0");
+                continue;
+            }
+        };
+
+        println!("\nEnter the Category. Leave empty for no Category:");
+        let category = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+
+        if !category.is_empty() && !cu_categories.contains(&category) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nCategory {category} was not found");
+            continue;
+        }
+
+        println!("\nEnter the Tags separated by commas. Leave empty for no Tags:");
+        let tags = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+        let tags = split_tags(&tags).join(", ");
+
+        println!("\nEnter the Rule. Values: Monthly/Weekly/Yearly/M/W/Y:");
+        let rule = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+        let rule = match rule.to_lowercase().chars().next() {
+            Some('m') => "Monthly",
+            Some('w') => "Weekly",
+            Some('y') => "Yearly",
+            _ => {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\nRule not acceptable. Values: Monthly/Weekly/Yearly/M/W/Y");
+                continue;
+            }
+        };
+
+        println!("\nEnter the Start Date. Example: 2022-01-31");
+        let start_date = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+
+        println!("\nEnter the End Date. Leave empty if it never ends:");
+        let end_date = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+
+        let start = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d");
+        let end = NaiveDate::parse_from_str(&end_date, "%Y-%m-%d");
+
+        let date_accepted = match (start, end) {
+            (Ok(_), _) if end_date.is_empty() => true,
+            (Ok(start), Ok(end)) => end >= start,
+            _ => false,
+        };
+
+        if !date_accepted {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nDates must be valid YYYY-MM-DD dates and the End Date cannot be before the Start Date");
+            continue;
+        }
+
+        println!("\nAdd {rule} {tx_type} {details} of {amount} from {start_date}? y/n");
+        std::io::stdin().read_line(&mut verify_line).unwrap();
+
+        if verify_line.trim().to_lowercase().starts_with('y') {
+            return vec![
+                details,
+                tx_method,
+                amount,
+                tx_type.to_string(),
+                category,
+                tags,
+                rule.to_string(),
+                start_date,
+                end_date,
+            ];
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+        }
+    }
+}
+
+/// Asks the user through the terminal whether to set or delete the monthly budget of a Category
+/// or a Transaction Method. Setting a budget also asks for the amount and whether the unspent
+/// amount should carry over to the next month. Returns `["".to_string()]` if the operation is cancelled.
+/// return example: `["Set", "Category", "Food", "300.00", "Yes"]`
+pub fn get_user_budget_data(conn: &Connection) -> Vec<String> {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+    let cu_tx_methods = get_all_tx_methods(conn);
+    let cu_categories = get_all_categories(conn);
+
+    let mut info_line = "Currently added Categories: ".to_string();
+    for i in &cu_categories {
+        info_line.push_str(&format!("\n- {i}"))
+    }
+    info_line.push_str("\n\nCurrently added Transaction Methods: ");
+    for i in &cu_tx_methods {
+        info_line.push_str(&format!("\n- {i}"))
+    }
+
+    loop {
+        let mut action = String::new();
+        let mut budget_type = String::new();
+        let mut target = String::new();
+        let mut amount = String::new();
+        let mut rollover = String::new();
+        let mut verify_line = String::new();
+
+        println!("{info_line}\n");
+        println!("\nSetting an existing budget changes its amount.\n\nInput 'Cancel' to cancel the operation\n\nEnter the action. Values: Set/Delete/S/D:");
+
+        std::io::stdin().read_line(&mut action).unwrap();
+        action = action.trim().to_lowercase();
+
+        if action.starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        let action = if action.starts_with('s') {
+            "Set"
+        } else if action.starts_with('d') {
+            "Delete"
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nAction not acceptable. Values: Set/Delete/S/D");
+            continue;
+        };
+
+        println!("\nEnter the budget type. Values: Category/Method/C/M:");
+
+        std::io::stdin().read_line(&mut budget_type).unwrap();
+        budget_type = budget_type.trim().to_lowercase();
+
+        if budget_type.starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        let (budget_type, all_targets) = if budget_type.starts_with('c') {
+            ("Category", &cu_categories)
+        } else if budget_type.starts_with('m') {
+            ("Method", &cu_tx_methods)
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nBudget type not acceptable. Values: Category/Method/C/M");
+            continue;
+        };
+
+        println!("\nEnter the {budget_type}:");
+
+        std::io::stdin().read_line(&mut target).unwrap();
+        target = target.trim().to_string();
+
+        if target.to_lowercase().starts_with("cancel") {
+            return vec!["".to_string()];
+        }
+
+        if !all_targets.contains(&target) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\n{budget_type} {target} was not found");
+            continue;
+        }
+
+        if action == "Delete" {
+            println!("\nDelete the budget of {target}? y/n");
+        } else {
+            println!("\nEnter the monthly budget amount:");
+
+            std::io::stdin().read_line(&mut amount).unwrap();
+            amount = amount.trim().to_string();
+
+            if amount.to_lowercase().starts_with("cancel") {
+                return vec!["".to_string()];
+            }
+
+            amount = match amount_to_cents(&amount) {
+                Ok(cents) if cents > 0 => cents_to_amount(cents),
+                _ => {
+                    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                    println!("\nAmount {amount} is not a valid amount above 0");
+                    continue;
+                }
+            };
+
+            println!("\nCarry the unspent amount over to the next month? y/n");
+
+            std::io::stdin().read_line(&mut rollover).unwrap();
+            rollover = if rollover.trim().to_lowercase().starts_with('y') {
+                "Yes".to_string()
+            } else {
+                "No".to_string()
+            };
+
+            println!("\nSet the monthly budget of {target} to {amount}? y/n");
+        }
+        std::io::stdin().read_line(&mut verify_line).unwrap();
+
+        if verify_line.trim().to_lowercase().starts_with('y') {
+            if action == "Set" {
+                return vec![
+                    action.to_string(),
+                    budget_type.to_string(),
+                    target,
+                    amount,
+                    rollover,
+                ];
+            }
+            return vec![action.to_string(), budget_type.to_string(), target];
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+        }
+    }
+}
+
+/// Asks the user through the terminal for a split transaction and its legs. Each leg has a Transaction
+/// Method, an amount and an optional category. At least 2 legs are required. Returns `["".to_string()]`
+/// if the operation is cancelled. The first 4 values are the date, details, tx type and tags
+/// followed by 3 values for each leg.
+/// return example: `["2022-08-19", "Groceries", "Expense", "", "Card", "30.00", "Food", "Cash", "12.50", ""]`
+pub fn get_user_split_data(conn: &Connection) -> Vec<String> {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+    let cu_tx_methods = get_active_tx_methods(conn);
+    let cu_categories = get_all_categories(conn);
+
+    let mut info_line = "Currently active Transaction Methods: ".to_string();
+    for i in &cu_tx_methods {
+        info_line.push_str(&format!("\n- {i}"))
+    }
+    info_line.push_str("\n\nCurrently added Categories: ");
+    for i in &cu_categories {
+        info_line.push_str(&format!("\n- {i}"))
+    }
+
+    // reads a trimmed line from the terminal. None if the user wants to cancel
+    let read_input = || {
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim().to_string();
+        if input.to_lowercase().starts_with("cancel") {
+            None
+        } else {
+            Some(input)
+        }
+    };
+
+    loop {
+        let mut verify_line = String::new();
+
+        println!("{info_line}\n");
+        println!("\nInput 'Cancel' to cancel the operation\n\nEnter the Date. Example: 2022-08-19");
+        let date = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+
+        if NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nDate {date} is not a valid YYYY-MM-DD date");
+            continue;
+        }
+
+        println!("\nEnter the Details:");
+        let details = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+
+        println!("\nEnter the Transaction Type. Values: Expense/Income/E/I:");
+        let tx_type = match read_input() {
+            Some(input) => input,
+            None => return vec!["".to_string()],
+        };
+        let tx_type = match tx_type.to_lowercase().chars().next() {
+            Some('e') => "Expense",
+            Some('i') => "Income",
+            _ => {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\nTransaction Type not acceptable. Values: Expense/Income/E/I");
+                continue;
+            }
+        };
+
+        let mut legs: Vec<String> = Vec::new();
+        let mut leg_error = None;
+
+        loop {
+            let leg_num = legs.len() / 3 + 1;
+
+            if leg_num > 2 {
+                println!("\nEnter the Transaction Method of leg {leg_num}. Leave empty to finish adding legs:");
+            } else {
+                println!("\nEnter the Transaction Method of leg {leg_num}:");
+            }
+            let tx_method = match read_input() {
+                Some(input) => input,
+                None => return vec!["".to_string()],
+            };
+
+            if tx_method.is_empty() && leg_num > 2 {
+                break;
+            } else if !cu_tx_methods.contains(&tx_method) {
+                leg_error = Some(format!(
+                    "Transaction Method {tx_method} is not an active Transaction Method"
+                ));
+                break;
+            }
+
+            println!("\nEnter the Amount of leg {leg_num}:");
+            let amount = match read_input() {
+                Some(input) => input,
+                None => return vec!["".to_string()],
+            };
+
+            let amount = match amount_to_cents(&amount) {
+                Ok(cents) if cents > 0 => cents_to_amount(cents),
+                _ => {
+                    leg_error = Some(format!("Amount {amount} is not a valid amount above 0"));
+                    break;
+                }
+            };
+
+            println!("\nEnter the Category of leg {leg_num}. Leave empty for no Category:");
+            let category = match read_input() {
+                Some(input) => input,
+                None => return vec!["".to_string()],
+            };
+
+            if !category.is_empty() && !cu_categories.contains(&category) {
+                leg_error = Some(format!("Category {category} was not found"));
+                break;
+            }
+
+            legs.extend([tx_method, amount, category]);
+        }
+
+        if let Some(error) = leg_error {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\n{error}");
+            continue;
+        }
+
+        println!("\nEnter the Tags separated by commas. Leave empty for no Tags:");
+        let tags = match read_input() {
+            Some(input) => split_tags(&input).join(", "),
+            None => return vec!["".to_string()],
+        };
+
+        let total_amount = legs
+            .chunks(3)
+            .map(|leg| amount_to_cents(&leg[1]).unwrap())
+            .sum();
+
+        println!(
+            "\nAdd {tx_type} {details} of {} split into {} legs? y/n",
+            cents_to_amount(total_amount),
+            legs.len() / 3
+        );
+        std::io::stdin().read_line(&mut verify_line).unwrap();
+
+        if verify_line.trim().to_lowercase().starts_with('y') {
+            let mut split_data = vec![date, details, tx_type.to_string(), tags];
+            split_data.extend(legs);
+            return split_data;
+        } else {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+        }
+    }
+}
+
+/// Asks the user through the terminal for a bank statement file. For a CSV file, asks how its columns map
+/// to a transaction where a saved mapping can be picked or a new one can be created and saved under a name
+/// for later imports. For an OFX or QFX file, only asks for the Transaction Method. For a QIF file, asks
+/// for the method of the records without an account and the date format.
+/// Returns None if the operation is cancelled. The name of a mapping that is not saved is empty.
+/// return example: `Some(("statement.csv", ImportSource::Csv(ImportProfile { date_column: 1, .. })))`
+pub fn get_user_import_data(conn: &Connection) -> Option<(String, ImportSource)> {
+    let mut stdout = io::stdout();
+    execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+    let cu_tx_methods = get_active_tx_methods(conn);
+    let saved_profiles = get_import_profiles(conn).unwrap_or_default();
+
+    // reads a trimmed line from the terminal. None if the user wants to cancel
+    let read_input = || {
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim().to_string();
+        if input.to_lowercase().starts_with("cancel") {
+            None
+        } else {
+            Some(input)
+        }
+    };
+
+    // column numbers start from 1
+    let parse_column = |input: &str| input.parse::<usize>().ok().filter(|a| *a > 0);
+
+    let mut method_line = "\nCurrently active Transaction Methods:".to_string();
+    for i in &cu_tx_methods {
+        method_line.push_str(&format!("\n- {i}"))
+    }
+
+    loop {
+        println!("\nInput 'Cancel' to cancel the operation\n\nEnter the path of the CSV, OFX, QFX or QIF file:");
+        let path = read_input()?;
+
+        // the entries of an OFX file are always read the same way so no mapping is needed
+        if is_ofx_file(Path::new(&path)) {
+            println!("{method_line}\n\nEnter the Transaction Method to add the entries to:");
+            let tx_method = read_input()?;
+
+            if !cu_tx_methods.contains(&tx_method) {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\nTransaction Method {tx_method} is not an active Transaction Method");
+                continue;
+            }
+            return Some((path, ImportSource::Ofx(tx_method)));
+        }
+
+        // a QIF file names the accounts of its records so the method is only used for the
+        // records without an account
+        if is_qif_file(Path::new(&path)) {
+            println!("{method_line}\n\nEnter the Transaction Method for the records without an account. Leave empty if every record has one:");
+            let tx_method = read_input()?;
+
+            if !tx_method.is_empty() && !cu_tx_methods.contains(&tx_method) {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\nTransaction Method {tx_method} is not an active Transaction Method");
+                continue;
+            }
+
+            println!(
+                "\nEnter the date format of the file. Leave empty for %m/%d/%Y. Example: %d/%m/%Y"
+            );
+            let mut date_format = read_input()?;
+            if date_format.is_empty() {
+                date_format = "%m/%d/%Y".to_string();
+            }
+            return Some((path, ImportSource::Qif(tx_method, date_format)));
+        }
+
+        let first_row = match std::fs::read_to_string(&path) {
+            Ok(text) => parse_csv(&text).into_iter().next(),
+            Err(e) => {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\nCould not read {path}. Error: {e}");
+                continue;
+            }
+        };
+
+        let first_row = match first_row {
+            Some((_, fields)) => fields,
+            None => {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\n{path} does not have any rows");
+                continue;
+            }
+        };
+
+        let mut info_line = "Columns of the first row:".to_string();
+        for (index, field) in first_row.iter().enumerate() {
+            info_line.push_str(&format!("\n{}. {field}", index + 1));
+        }
+        println!("\n{info_line}");
+
+        if !saved_profiles.is_empty() {
+            let mut profile_line = "\nSaved mappings:".to_string();
+            for (index, profile) in saved_profiles.iter().enumerate() {
+                profile_line.push_str(&format!(
+                    "\n{}. {} (to {})",
+                    index + 1,
+                    profile.name,
+                    profile.tx_method
+                ));
+            }
+            println!("{profile_line}");
+            println!("\nEnter the number of a saved mapping. Leave empty to create a new one:");
+
+            let input = read_input()?;
+            if !input.is_empty() {
+                match parse_column(&input).and_then(|a| saved_profiles.get(a - 1)) {
+                    Some(profile) => return Some((path, ImportSource::Csv(profile.clone()))),
+                    None => {
+                        execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                        println!("\nMapping {input} was not found");
+                        continue;
+                    }
+                }
+            }
+        }
+
+        println!("\nDoes the first row have the column names? y/n");
+        let has_header = read_input()?.to_lowercase().starts_with('y');
+
+        let mut columns = Vec::new();
+        for column_name in ["Date", "Details", "Amount"] {
+            println!("\nEnter the column number of the {column_name}:");
+            let input = read_input()?;
+            columns.push((input.clone(), parse_column(&input)));
+        }
+
+        println!("\nEnter the column number that tells if a row is an Expense or an Income. Leave empty if negative amounts are Expenses:");
+        let input = read_input()?;
+        let sign_column = parse_column(&input);
+        if !input.is_empty() {
+            columns.push((input, sign_column));
+        }
+
+        if let Some((input, _)) = columns.iter().find(|(_, column)| column.is_none()) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\n{input} is not a column number");
+            continue;
+        }
+
+        let mut expense_value = String::new();
+        if sign_column.is_some() {
+            println!("\nEnter the value of that column that marks an Expense. Example: Debit");
+            expense_value = read_input()?;
+        }
+
+        println!(
+            "\nEnter the date format of the file. Leave empty for %Y-%m-%d. Example: %d/%m/%Y"
+        );
+        let mut date_format = read_input()?;
+        if date_format.is_empty() {
+            date_format = "%Y-%m-%d".to_string();
+        }
+
+        println!(
+            "\nEnter the decimal separator of the amounts. Leave empty for a dot. Values: . or ,"
+        );
+        let decimal_separator = match read_input()?.as_str() {
+            "" | "." => '.',
+            "," => ',',
+            input => {
+                execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+                println!("\n{input} is not a decimal separator. Values: . or ,");
+                continue;
+            }
+        };
+
+        println!("{method_line}\n\nEnter the Transaction Method to add the rows to:");
+        let tx_method = read_input()?;
+
+        if !cu_tx_methods.contains(&tx_method) {
+            execute!(stdout, Clear(ClearType::FromCursorUp)).unwrap();
+            println!("\nTransaction Method {tx_method} is not an active Transaction Method");
+            continue;
+        }
+
+        println!("\nEnter a name to save this mapping. Leave empty to not save it:");
+        let name = read_input()?;
+
+        let profile = ImportProfile {
+            name,
+            has_header,
+            date_column: columns[0].1.unwrap(),
+            details_column: columns[1].1.unwrap(),
+            amount_column: columns[2].1.unwrap(),
+            sign_column,
+            expense_value,
+            date_format,
+            decimal_separator,
+            tx_method,
+        };
+        return Some((path, ImportSource::Csv(profile)));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::db::journal::{record_change, JournalChange, TxSnapshot};
+use crate::db::tx_manager::remove_tx;
+use crate::db::DbSavepoint;
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection, Error as sqlError, OptionalExtension, Result as sqlResult};
+use std::error::Error;
+
+/// Restores a transaction from the trash with the same id_num. The balances are calculated again the same
+/// way a new transaction is added. Fails if the transaction is not in the trash or any Transaction Method
+/// it used no longer exists.
+pub fn restore_tx(conn: &Connection, id_num: i32) -> Result<(), Box<dyn Error>> {
+    let sp = DbSavepoint::new(conn)?;
+
+    untrash_tx(&sp, id_num)?;
+    record_change(&sp, &JournalChange::RestoreTx { id_num })?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Removes a transaction from the trash for good. It can no longer be restored or undone.
+pub fn purge_tx(conn: &Connection, id_num: i32) -> sqlResult<()> {
+    let purged = conn.execute("DELETE FROM trash WHERE id_num = ?", [id_num])?;
+    if purged == 0 {
+        return Err(sqlError::QueryReturnedNoRows);
+    }
+    Ok(())
+}
+
+/// Removes the transactions that were deleted more than the given days before today from the trash.
+/// Nothing is removed if the days are 0. Returns the number of transactions that were purged.
+pub fn purge_old_trash(conn: &Connection, days: i64, today: NaiveDate) -> sqlResult<usize> {
+    if days == 0 {
+        return Ok(0);
+    }
+    let oldest_date = today - Duration::days(days);
+    conn.execute(
+        "DELETE FROM trash WHERE deleted_at < ?",
+        [oldest_date.to_string()],
+    )
+}
+
+/// Returns all the transactions in the trash, the latest deleted first, along with their id_num.
+/// A transaction that cannot be read is still listed so it can be purged.
+///
+/// all_txs : `[[deleted_at, date, details, tx_method, amount, tx_type],]`
+///
+/// all_id : `[1, 2, 3]` required to restore or purge a transaction
+pub fn get_trash_txs(conn: &Connection) -> (Vec<Vec<String>>, Vec<i32>) {
+    let mut all_txs = Vec::new();
+    let mut all_id = Vec::new();
+
+    for (id_num, deleted_at, tx) in get_trash_snapshots(conn).expect("Error") {
+        let tx = match tx {
+            Some(tx) => vec![tx.date, tx.details, tx.tx_method, tx.amount, tx.tx_type],
+            None => vec![
+                String::new(),
+                format!("Transaction {id_num} cannot be read"),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+        };
+        all_txs.push([vec![deleted_at], tx].concat());
+        all_id.push(id_num);
+    }
+    (all_txs, all_id)
+}
+
+/// Returns the number of transactions in the trash that use the given Transaction Method.
+/// Transactions that cannot be read are not counted.
+pub(crate) fn count_trash_txs_using(conn: &Connection, tx_method: &str) -> sqlResult<usize> {
+    let total_txs = get_trash_snapshots(conn)?
+        .iter()
+        .filter(|(_, _, tx)| {
+            tx.as_ref()
+                .is_some_and(|tx| tx.get_used_methods().contains(&tx_method))
+        })
+        .count();
+    Ok(total_txs)
+}
+
+/// Runs the update on every transaction in the trash and saves the ones it changed. Keeps the trash on the
+/// current Transaction Method and category names so the transactions can still be restored.
+/// Transactions that cannot be read are left as they are.
+pub(crate) fn update_trash_txs<F>(conn: &Connection, update: F) -> sqlResult<()>
+where
+    F: Fn(&mut TxSnapshot) -> bool,
+{
+    for (id_num, _, tx) in get_trash_snapshots(conn)? {
+        if let Some(mut tx) = tx {
+            if update(&mut tx) {
+                let tx = serde_json::to_string(&tx)
+                    .map_err(|e| sqlError::ToSqlConversionFailure(Box::new(e)))?;
+                conn.execute(
+                    "UPDATE trash SET tx = ? WHERE id_num = ?",
+                    params![tx, id_num],
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Collects every transaction in the trash, the latest deleted first, along with its id_num and the day it
+/// was deleted. A transaction that cannot be read is None.
+fn get_trash_snapshots(conn: &Connection) -> sqlResult<Vec<(i32, String, Option<TxSnapshot>)>> {
+    let mut statement = conn.prepare(
+        "SELECT id_num, deleted_at, tx FROM trash ORDER BY deleted_at DESC, id_num DESC",
+    )?;
+
+    let rows = statement.query_map([], |row| {
+        let tx: String = row.get(2)?;
+        Ok((row.get(0)?, row.get(1)?, serde_json::from_str(&tx).ok()))
+    })?;
+    rows.collect()
+}
+
+/// Does the actual work of delete_tx inside the given connection or savepoint without committing.
+/// The effect of the transaction on the balances is reversed and it is kept in the trash with the
+/// day it was deleted.
+pub(crate) fn trash_tx(conn: &Connection, id_num: i32, today: NaiveDate) -> sqlResult<()> {
+    let tx = TxSnapshot::get(conn, id_num)?.ok_or(sqlError::QueryReturnedNoRows)?;
+    let tx =
+        serde_json::to_string(&tx).map_err(|e| sqlError::ToSqlConversionFailure(Box::new(e)))?;
+
+    remove_tx(conn, id_num as usize)?;
+    conn.execute(
+        "INSERT INTO trash (id_num, deleted_at, tx) VALUES (?, ?, ?)",
+        params![id_num, today.to_string(), tx],
+    )?;
+    Ok(())
+}
+
+/// Does the actual work of restore_tx inside the given connection or savepoint without committing
+pub(crate) fn untrash_tx(conn: &Connection, id_num: i32) -> Result<(), Box<dyn Error>> {
+    let tx: Option<String> = conn
+        .query_row("SELECT tx FROM trash WHERE id_num = ?", [id_num], |row| {
+            row.get(0)
+        })
+        .optional()?;
+
+    let tx: TxSnapshot = match tx {
+        Some(tx) => serde_json::from_str(&tx)?,
+        None => return Err(format!("Transaction {id_num} is not in the trash").into()),
+    };
+
+    tx.insert(conn, id_num)?;
+    conn.execute("DELETE FROM trash WHERE id_num = ?", [id_num])?;
+    Ok(())
+}
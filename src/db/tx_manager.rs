@@ -1,342 +1,395 @@
+use crate::db::journal::{record_change, record_tx_change, JournalChange, TxSnapshot};
+use crate::db::trash::trash_tx;
 use crate::db::{
-    get_all_tx_methods, get_last_balance_id, get_last_balances, get_last_time_balance,
-    get_last_tx_id,
+    add_new_year, amount_to_cents, get_all_tx_methods, get_balance_id, get_category_id,
+    get_last_balance_id, get_last_balances, get_last_time_balance, get_last_tx_id, set_balance,
+    split_tags, DbSavepoint,
 };
-use rusqlite::{Connection, Result as sqlResult};
+use chrono::Local;
+use rusqlite::{params, Connection, Error as sqlError, Result as sqlResult};
 use std::collections::HashMap;
 
+/// Selects the monthly balance rows. Every month keeps the balance carried from the months before it
+/// even without any transaction so a change goes to every later month, whatever its balance is.
+const MONTH_ROWS: &str = "id_num != 0";
+
 /// Adds a transaction to the database with the given info. The flow of this goes like this:
 /// - Add the new transaction to the database
 /// - Calculate the changes that happened to the Tx Method
 /// - Calculate the absolute final balance
 /// - Find the Changes that happened due to the transaction
 /// - Push them to the database
+///
+/// The amount is turned into cents before anything is saved. An empty category saves the
+/// transaction without a category. Tags are comma separated, example: `work, trip-2026`.
+#[allow(clippy::too_many_arguments)]
 pub fn add_new_tx(
+    conn: &Connection,
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tx_type: &str,
+    category: &str,
+    tags: &str,
+    id_num: Option<&str>,
+) -> sqlResult<()> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let id_num = insert_tx(
+        &sp, date, details, tx_method, amount, tx_type, category, tags, id_num,
+    )?;
+    record_tx_change(&sp, id_num, None)?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Edits an existing transaction. The old transaction's effect on the balances and changes is reversed,
+/// the tx_all row is rewritten with the new data keeping the same id_num and the new effects are applied.
+/// Everything happens in a single savepoint so a failure leaves the original transaction untouched.
+#[allow(clippy::too_many_arguments)]
+pub fn update_tx(
+    conn: &Connection,
+    id_num: usize,
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    amount: &str,
+    tx_type: &str,
+    category: &str,
+    tags: &str,
+) -> sqlResult<()> {
+    let sp = DbSavepoint::new(conn)?;
+
+    let before = TxSnapshot::get(&sp, id_num as i32)?;
+    remove_tx(&sp, id_num)?;
+    insert_tx(
+        &sp,
+        date,
+        details,
+        tx_method,
+        amount,
+        tx_type,
+        category,
+        tags,
+        Some(&id_num.to_string()),
+    )?;
+    record_tx_change(&sp, id_num as i32, before)?;
+
+    sp.commit()?;
+    Ok(())
+}
+
+/// Does the actual work of add_new_tx inside the given connection or savepoint without committing.
+/// Returns the id_num the transaction was saved with.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn insert_tx(
+    conn: &Connection,
     date: &str,
     details: &str,
     tx_method: &str,
     amount: &str,
     tx_type: &str,
-    path: &str,
+    category: &str,
+    tags: &str,
+    id_num: Option<&str>,
+) -> sqlResult<i32> {
+    let int_amount = amount_to_cents(amount).map_err(sqlError::ToSqlConversionFailure)?;
+
+    // a category that does not exist fails the whole transaction instead of being silently dropped
+    let category_id = get_category_id(conn, category)?;
+    let tx_changes = get_tx_changes(tx_method, tx_type, int_amount);
+
+    save_tx(
+        conn,
+        date,
+        details,
+        tx_method,
+        int_amount,
+        tx_type,
+        category_id,
+        tags,
+        id_num,
+        &tx_changes,
+    )
+}
+
+/// Adds a split transaction where the amount is divided into multiple legs. Each leg is
+/// `[tx_method, amount, category]` and the category can be empty. tx_type is either Expense or Income
+/// and applies to every leg. The tx_all row gets `Split` as the Transaction Method, the total amount
+/// of the legs and no category while the balances and changes of every leg are saved at once.
+pub fn add_split_tx(
+    conn: &Connection,
+    date: &str,
+    details: &str,
+    tx_type: &str,
+    legs: &[Vec<String>],
+    tags: &str,
     id_num: Option<&str>,
 ) -> sqlResult<()> {
-    // create a connection and a savepoint
-    let mut conn = Connection::open(path)?;
-    let sp = conn.savepoint()?;
+    let sp = DbSavepoint::new(conn)?;
+
+    let id_num = insert_split_tx(&sp, date, details, tx_type, legs, tags, id_num)?;
+    record_tx_change(&sp, id_num, None)?;
+
+    sp.commit()?;
+    Ok(())
+}
 
+/// Does the actual work of add_split_tx inside the given connection or savepoint without committing.
+/// Returns the id_num the transaction was saved with.
+pub(crate) fn insert_split_tx(
+    conn: &Connection,
+    date: &str,
+    details: &str,
+    tx_type: &str,
+    legs: &[Vec<String>],
+    tags: &str,
+    id_num: Option<&str>,
+) -> sqlResult<i32> {
+    if legs.len() < 2 || (tx_type != "Expense" && tx_type != "Income") {
+        return Err(sqlError::InvalidParameterName(
+            "a split transaction needs at least 2 legs and must be an Expense or an Income"
+                .to_string(),
+        ));
+    }
+
+    let mut int_legs = Vec::new();
+    let mut tx_changes: Vec<(String, i64)> = Vec::new();
+
+    for leg in legs {
+        let int_amount = amount_to_cents(&leg[1]).map_err(sqlError::ToSqlConversionFailure)?;
+        let method_id: i32 = conn.query_row(
+            "SELECT id FROM tx_methods WHERE name = ?",
+            [&leg[0]],
+            |row| row.get(0),
+        )?;
+        let category_id = get_category_id(conn, &leg[2])?;
+
+        // legs on the same method are saved as a single change
+        for (method, change) in get_tx_changes(&leg[0], tx_type, int_amount) {
+            match tx_changes.iter_mut().find(|(saved, _)| saved == &method) {
+                Some((_, saved_change)) => *saved_change += change,
+                None => tx_changes.push((method, change)),
+            }
+        }
+        int_legs.push((method_id, int_amount, category_id));
+    }
+
+    let total_amount = int_legs.iter().map(|(_, amount, _)| amount).sum();
+
+    let last_id = save_tx(
+        conn,
+        date,
+        details,
+        "Split",
+        total_amount,
+        tx_type,
+        None,
+        tags,
+        id_num,
+        &tx_changes,
+    )?;
+
+    for (leg_num, (method_id, amount, category_id)) in int_legs.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO tx_legs (id_num, leg_num, method_id, amount, category_id) VALUES (?, ?, ?, ?, ?)",
+            params![last_id, leg_num + 1, method_id, amount, category_id],
+        )?;
+    }
+    Ok(last_id)
+}
+
+/// Saves the tx_all row, the tags, the balances and the changes of a transaction with the given
+/// balance changes. Returns the id_num the transaction was saved with.
+#[allow(clippy::too_many_arguments)]
+fn save_tx(
+    conn: &Connection,
+    date: &str,
+    details: &str,
+    tx_method: &str,
+    int_amount: i64,
+    tx_type: &str,
+    category_id: Option<i32>,
+    tags: &str,
+    id_num: Option<&str>,
+    tx_changes: &[(String, i64)],
+) -> sqlResult<i32> {
     if let Some(id) = id_num {
-        let query = r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type, id_num) VALUES (?, ?, ?, ?, ?, ?)"#;
-        sp.execute(query, [date, details, tx_method, amount, tx_type, id])?;
+        let query = r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type, category_id, id_num) VALUES (?, ?, ?, ?, ?, ?, ?)"#;
+        conn.execute(
+            query,
+            params![
+                date,
+                details,
+                tx_method,
+                int_amount,
+                tx_type,
+                category_id,
+                id
+            ],
+        )?;
     } else {
-        let query = r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type) VALUES (?, ?, ?, ?, ?)"#;
-        sp.execute(query, [date, details, tx_method, amount, tx_type])?;
+        let query = r#"INSERT INTO tx_all (date, details, "tx_method", amount, tx_type, category_id) VALUES (?, ?, ?, ?, ?, ?)"#;
+        conn.execute(
+            query,
+            params![date, details, tx_method, int_amount, tx_type, category_id],
+        )?;
     }
 
     let split = date.split('-');
     let vec = split.collect::<Vec<&str>>();
-    let mut mnth = vec[1].to_string();
-    if &mnth[0..0] == "0" {
-        mnth = mnth.replace('0', "");
-    }
-    let month = mnth.parse::<i32>().unwrap();
-    let year = vec[0][2..].parse::<i32>().unwrap() - 22;
-
-    let mut from_method = String::new();
-    let mut to_method = String::new();
+    let month = vec[1].parse::<usize>().unwrap();
+    let year = vec[0].parse::<usize>().unwrap();
 
-    if tx_type == "Transfer" {
-        let split = tx_method.split(" to ");
-        let vec = split.collect::<Vec<&str>>();
-        from_method = vec[0].to_string();
-        to_method = vec[1].to_string();
-    }
+    // the balance rows of the year might not exist yet if this is the first transaction of the year
+    add_new_year(conn, year)?;
 
-    let target_id_num = month as i32 + (year as i32 * 12);
+    let target_id_num = get_balance_id(month, year);
 
     // This is necessary for the foreign key field in the changes_all table
     // and must align with the latest transaction id_num
-    let mut last_id = get_last_tx_id(&sp)?;
+    let mut last_id = get_last_tx_id(conn)?;
     if let Some(id) = id_num {
         last_id = id.parse().unwrap();
     }
-    let last_balance_id = get_last_balance_id(&sp)?;
+    let last_balance_id = get_last_balance_id(conn)?;
 
-    // we have to get these following data to push to the database
-    // new_balance_data : the current month balance after the transaction
-    // new_changes_data : the new changes data to push to the database
-    // last_balance_data : the absolute final balance after all transaction
-    let mut new_balance_data = Vec::new();
-    let mut new_changes_data = Vec::new();
-    let mut last_balance_data = HashMap::new();
-
-    let all_tx_methods = get_all_tx_methods(&sp);
-    let last_balance = get_last_balances(&sp, &all_tx_methods);
-    let mut cu_month_balance =
-        get_last_time_balance(&sp, month as usize, year as usize, &all_tx_methods);
-
-    let mut new_balance = 0.0;
-    let int_amount = amount.parse::<f64>().unwrap();
-
-    if tx_type == "Transfer" {
-        let new_balance_from = cu_month_balance[&from_method] - int_amount;
-        let new_balance_to = cu_month_balance[&to_method] + int_amount;
-        *cu_month_balance.get_mut(&from_method).unwrap() = new_balance_from;
-        *cu_month_balance.get_mut(&to_method).unwrap() = new_balance_to;
-    } else {
-        // makes changes to the current month balance and push them to vector
-        if tx_type == "Expense" {
-            new_balance = cu_month_balance[tx_method] - int_amount;
-        } else if tx_type == "Income" {
-            new_balance = cu_month_balance[tx_method] + int_amount;
-        }
+    for tag in split_tags(tags) {
+        conn.execute(
+            "INSERT INTO tx_tags (id_num, tag) VALUES (?, ?)",
+            params![last_id, tag],
+        )?;
+    }
 
-        *cu_month_balance.get_mut(tx_method).unwrap() = new_balance;
+    // we have to get these following data to push to the database
+    // cu_month_balance : the current month balance after the transaction
+    // last_balance : the absolute final balance after all transaction
+    // tx_changes : the changes that happened to the tx methods used by the transaction
+    let all_tx_methods = get_all_tx_methods(conn);
+    let mut last_balance: HashMap<String, i64> = all_tx_methods
+        .iter()
+        .cloned()
+        .zip(get_last_balances(conn, &all_tx_methods))
+        .collect();
+    let mut cu_month_balance = get_last_time_balance(conn, month, year, &all_tx_methods);
+
+    for (method, change) in tx_changes {
+        *cu_month_balance.get_mut(method).unwrap() += change;
+        *last_balance.get_mut(method).unwrap() += change;
     }
 
-    for i in &all_tx_methods {
-        new_balance_data.push(format!("{:.2}", cu_month_balance[i]))
+    // the month balance of every method is saved as the month row might be holding older balances
+    for method in &all_tx_methods {
+        set_balance(conn, target_id_num, method, cu_month_balance[method])?;
     }
 
-    for i in 0..all_tx_methods.len() {
-        // the variable to keep track whether any changes were made to the tx method
-        let cu_last_balance = last_balance[i].parse::<f64>().unwrap();
-        let mut default_change = format!("{:.2}", 0.0);
-
-        // we could have just used the tx_method from the argument but adding the default values
-        // manually after that would make it tricky because have to maintain the tx method balance order
-        // and the Changes order
-
-        // add the proper values and changes based on the tx type
-        if tx_type == "Transfer" && all_tx_methods[i] == from_method {
-            default_change = format!("↓{:.2}", &int_amount);
-            let edited_balance = cu_last_balance - int_amount;
-            last_balance_data.insert(&from_method, format!("{edited_balance:.2}"));
-        } else if tx_type == "Transfer" && all_tx_methods[i] == to_method {
-            default_change = format!("↑{:.2}", &int_amount);
-            let edited_balance = cu_last_balance + int_amount;
-            last_balance_data.insert(&to_method, format!("{edited_balance:.2}"));
-        } else if tx_type != "Transfer" && all_tx_methods[i] == tx_method {
-            if tx_type == "Expense" {
-                default_change = format!("↓{:.2}", &int_amount);
-                let edited_balance = cu_last_balance - int_amount;
-                last_balance_data.insert(&all_tx_methods[i], format!("{edited_balance:.2}"));
-            } else if tx_type == "Income" {
-                default_change = format!("↑{:.2}", &int_amount);
-                let edited_balance = cu_last_balance + int_amount;
-                last_balance_data.insert(&all_tx_methods[i], format!("{edited_balance:.2}"));
-            }
-        }
-        new_changes_data.push(default_change);
+    for (method, change) in tx_changes {
+        set_balance(conn, last_balance_id, method, last_balance[method])?;
+
+        // the same way delete_tx reverses it, the later months get the change too
+        // so a transaction added on an earlier month does not leave them behind
+        conn.execute(
+            &format!(
+                "UPDATE balance_all SET balance = balance + ?
+                WHERE id_num > ? AND {MONTH_ROWS} AND method_id = (SELECT id FROM tx_methods WHERE name = ?)"
+            ),
+            params![change, target_id_num, method],
+        )?;
+
+        conn.execute(
+            "INSERT INTO changes_all (id_num, method_id, amount)
+            SELECT ?, id, ? FROM tx_methods WHERE name = ?",
+            params![last_id, change, method],
+        )?;
     }
+    Ok(last_id)
+}
 
-    // the query kept on breaking for a single comma so had to follow this ugly way to do this.
-    // loop and add a comma until the last index and ignore it in the last time
-    let mut balance_query = "UPDATE balance_all SET ".to_string();
-    for i in 0..new_balance_data.len() {
-        if i != new_balance_data.len() - 1 {
-            balance_query.push_str(&format!(
-                r#""{}" = "{}", "#,
-                all_tx_methods[i], new_balance_data[i]
-            ))
-        } else {
-            balance_query.push_str(&format!(
-                r#""{}" = "{}" "#,
-                all_tx_methods[i], new_balance_data[i]
-            ))
+/// Returns the balance changes a transaction causes to each tx method it uses in cents.
+/// Expense and Income change a single method while Transfer moves the amount from one method to the other.
+/// return example: `[("source_1", -10000), ("source_2", 10000)]`
+pub(crate) fn get_tx_changes(tx_method: &str, tx_type: &str, amount: i64) -> Vec<(String, i64)> {
+    match tx_type {
+        "Transfer" => {
+            let from_to = tx_method.split(" to ").collect::<Vec<&str>>();
+            vec![
+                (from_to[0].to_string(), -amount),
+                (from_to[1].to_string(), amount),
+            ]
         }
+        "Expense" => vec![(tx_method.to_string(), -amount)],
+        "Income" => vec![(tx_method.to_string(), amount)],
+        _ => vec![],
     }
-    balance_query.push_str(&format!("WHERE id_num = {target_id_num}"));
+}
+
+/// Moves the selected transaction to the trash. The absolute final balance and balance data are updated
+/// the same way as before so the transaction no longer counts anywhere until it is restored.
+pub fn delete_tx(conn: &Connection, id_num: usize) -> sqlResult<()> {
+    let sp = DbSavepoint::new(conn)?;
+
+    trash_tx(&sp, id_num as i32, Local::today().naive_local())?;
+    record_change(
+        &sp,
+        &JournalChange::DeleteTx {
+            id_num: id_num as i32,
+        },
+    )?;
 
-    let last_balance_query: String = if tx_type == "Transfer" {
-        format!(
-            r#"UPDATE balance_all SET "{from_method}" = "{}", "{to_method}" = "{}" WHERE id_num = {}"#,
-            last_balance_data[&from_method], last_balance_data[&to_method], last_balance_id
-        )
-    } else {
-        format!(
-            r#"UPDATE balance_all SET "{tx_method}" = "{}" WHERE id_num = {}"#,
-            last_balance_data[&tx_method.to_string()],
-            last_balance_id
-        )
-    };
-
-    let mut changes_query = format!("INSERT INTO changes_all (id_num, date, {all_tx_methods:?}) VALUES ({last_id}, ?, {new_changes_data:?})");
-    changes_query = changes_query.replace('[', "");
-    changes_query = changes_query.replace(']', "");
-
-    sp.execute(&balance_query, [])?;
-    sp.execute(&last_balance_query, [])?;
-    sp.execute(&changes_query, [date])?;
     sp.commit()?;
     Ok(())
 }
 
-/// Updates the absolute final balance, balance data and deletes the selected transaction.
-/// Foreign key cascade takes care of the Changes data in the database.
-pub fn delete_tx(id_num: usize, path: &str) -> sqlResult<()> {
-    let mut conn = Connection::open(path)?;
-    let sp = conn.savepoint()?;
-
-    let tx_methods = get_all_tx_methods(&sp);
-    let last_balance = get_last_balances(&sp, &tx_methods);
-    let last_balance_id = get_last_balance_id(&sp)?;
-
-    let mut final_last_balance = Vec::new();
-
-    // get the deletion tx data
-    let query = format!("SELECT * FROM tx_all Where id_num = {}", id_num);
-    let data = sp.query_row(&query, [], |row| {
-        let mut final_data: Vec<String> = Vec::new();
-        final_data.push(row.get(0)?);
-        final_data.push(row.get(2)?);
-        final_data.push(row.get(3)?);
-        final_data.push(row.get(4)?);
-        Ok(final_data)
-    })?;
-
-    let split = data[0].split('-');
+/// Reverses the effect of a transaction on the balances and removes it without committing.
+/// Foreign key cascade takes care of the Changes, the tags and the split legs data in the database.
+pub(crate) fn remove_tx(conn: &Connection, id_num: usize) -> sqlResult<()> {
+    let last_balance_id = get_last_balance_id(conn)?;
+
+    let date: String = conn.query_row(
+        "SELECT date FROM tx_all WHERE id_num = ?",
+        [id_num],
+        |row| row.get(0),
+    )?;
+
+    let split = date.split('-');
     let splitted = split.collect::<Vec<&str>>();
     let (year, month) = (
-        splitted[0].parse::<i32>().unwrap(),
-        splitted[1].parse::<i32>().unwrap(),
+        splitted[0].parse::<usize>().unwrap(),
+        splitted[1].parse::<usize>().unwrap(),
     );
 
-    let year = year - 2022;
-
-    let mut target_id_num = month as i32 + (year as i32 * 12);
-
-    //
-    let mut from_method = "";
-    let mut to_method = "";
-
-    // the tx_method of the tx
-    let source = &data[1];
-
-    // execute this block to get block tx method if the tx type is a Transfer
-    if source.contains(" to ") {
-        let from_to = data[1].split(" to ").collect::<Vec<&str>>();
-
-        from_method = from_to[0];
-        to_method = from_to[1];
+    let target_id_num = get_balance_id(month, year);
+
+    // the saved changes cover every method the transaction touched, including all legs of a split transaction
+    let mut statement = conn.prepare(
+        "SELECT tx_methods.name, changes_all.amount FROM changes_all
+        INNER JOIN tx_methods ON tx_methods.id = changes_all.method_id
+        WHERE changes_all.id_num = ?",
+    )?;
+    let tx_changes = statement
+        .query_map([id_num], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<sqlResult<Vec<(String, i64)>>>()?;
+
+    // reverse the amount that was previously added to all the monthly balance rows from the deletion point
+    // till the latest month and to the absolute final balance
+    for (method, change) in tx_changes {
+        conn.execute(
+            &format!(
+                "UPDATE balance_all SET balance = balance - ?
+                WHERE id_num >= ? AND {MONTH_ROWS} AND method_id = (SELECT id FROM tx_methods WHERE name = ?)"
+            ),
+            params![change, target_id_num, method],
+        )?;
+
+        conn.execute(
+            "UPDATE balance_all SET balance = balance - ?
+            WHERE id_num = ? AND method_id = (SELECT id FROM tx_methods WHERE name = ?)",
+            params![change, last_balance_id, method],
+        )?;
     }
 
-    let amount = &data[2].parse::<f64>().unwrap();
-    let tx_type: &str = &data[3];
-
-    // loop through all rows in the balance_all table from the deletion point and update balance
-    loop {
-        let mut query = format!(
-            "SELECT {:?} FROM balance_all WHERE id_num = {}",
-            tx_methods, target_id_num
-        );
-        query = query.replace('[', "");
-        query = query.replace(']', "");
-
-        let cu_month_balance = sp.query_row(&query, [], |row| {
-            let mut final_data: Vec<String> = Vec::new();
-            for i in 0..tx_methods.len() {
-                final_data.push(row.get(i)?)
-            }
-            Ok(final_data)
-        })?;
-
-        let mut updated_month_balance = vec![];
-
-        // reverse that amount that was previously added and commit them to db
-        // add or subtract based on the tx type to the relevant method
-
-        // check the month balance as not zero because if it is 0, there was never any transaction
-        // done on that month
-        for i in 0..tx_methods.len() {
-            if &tx_methods[i] == source && cu_month_balance[i] != "0.00" {
-                let mut cu_int_amount = cu_month_balance[i].parse::<f64>().unwrap();
-                if tx_type == "Expense" {
-                    cu_int_amount += amount;
-                } else if tx_type == "Income" {
-                    cu_int_amount -= amount;
-                }
-                updated_month_balance.push(format!("{:.2}", cu_int_amount));
-            } else if tx_methods[i] == from_method && cu_month_balance[i] != "0.00" {
-                let mut cu_int_amount = cu_month_balance[i].parse::<f64>().unwrap();
-                cu_int_amount += amount;
-                updated_month_balance.push(format!("{:.2}", cu_int_amount));
-            } else if tx_methods[i] == to_method && cu_month_balance[i] != "0.00" {
-                let mut cu_int_amount = cu_month_balance[i].parse::<f64>().unwrap();
-                cu_int_amount -= amount;
-                updated_month_balance.push(format!("{:.2}", cu_int_amount));
-            } else {
-                updated_month_balance.push(format!(
-                    "{:.2}",
-                    cu_month_balance[i].parse::<f64>().unwrap()
-                ));
-            }
-        }
-
-        // the query kept on breaking for a single comma so had to follow this ugly way to do this.
-        // loop and add a comma until the last index and ignore it in the last time
-        let mut balance_query = "UPDATE balance_all SET ".to_string();
-        for i in 0..updated_month_balance.len() {
-            if i != updated_month_balance.len() - 1 {
-                balance_query.push_str(&format!(
-                    r#""{}" = "{}", "#,
-                    tx_methods[i], updated_month_balance[i]
-                ))
-            } else {
-                balance_query.push_str(&format!(
-                    r#""{}" = "{}" "#,
-                    tx_methods[i], updated_month_balance[i]
-                ))
-            }
-        }
-        balance_query.push_str(&format!("WHERE id_num = {target_id_num}"));
-        sp.execute(&balance_query, [])?;
-
-        // 49 is the absolute final balance which we don't need to modify
-        target_id_num += 1;
-        if target_id_num == 49 {
-            break;
-        }
-    }
-
-    // we are deleting 1 transaction, so loop through all tx methods, and whichever method matches
-    // with the one we are deleting, add/subtract from the amount.
-    // Calculate the balance/s for the absolute final balance and create the query
-    for i in 0..tx_methods.len() {
-        let mut cu_balance = last_balance[i].parse::<f64>().unwrap();
-        if &tx_methods[i] == source && tx_type != "Transfer" {
-            match tx_type {
-                "Expense" => cu_balance += amount,
-                "Income" => cu_balance -= amount,
-                _ => {}
-            }
-        } else if tx_methods[i] == from_method && tx_type == "Transfer" {
-            cu_balance += amount;
-        } else if tx_methods[i] == to_method && tx_type == "Transfer" {
-            cu_balance -= amount;
-        }
-        final_last_balance.push(format!("{:.2}", cu_balance));
-    }
-
-    let del_query = format!("DELETE FROM tx_all WHERE id_num = {id_num}");
-
-    let mut last_balance_query = "UPDATE balance_all SET ".to_string();
-    for i in 0..final_last_balance.len() {
-        if i != final_last_balance.len() - 1 {
-            last_balance_query.push_str(&format!(
-                r#""{}" = "{}", "#,
-                tx_methods[i], final_last_balance[i]
-            ))
-        } else {
-            last_balance_query.push_str(&format!(
-                r#""{}" = "{}" "#,
-                tx_methods[i], final_last_balance[i]
-            ))
-        }
-    }
-    last_balance_query.push_str(&format!("WHERE id_num = {last_balance_id}"));
-    sp.execute(&last_balance_query, [])?;
-    sp.execute(&del_query, [])?;
-
-    sp.commit()?;
+    conn.execute("DELETE FROM tx_all WHERE id_num = ?", [id_num])?;
     Ok(())
 }
@@ -1,4 +1,4 @@
-use crate::db::get_all_tx_methods;
+use crate::db::{get_active_tx_methods, get_all_categories, get_archived_tx_methods, split_tags};
 use chrono::naive::NaiveDate;
 use rusqlite::Connection;
 use std::error::Error;
@@ -9,7 +9,6 @@ pub trait StatusChecker {
     /// Checks if:
     ///
     /// - the date length is 10 characters
-    /// - the inputted year is between 2022 to 2025
     /// - the inputted month is between 01 to 12
     /// - the inputted date is between 01 to 31
     /// - the inputted date is empty
@@ -19,7 +18,6 @@ pub trait StatusChecker {
     /// adding 0 if the beginning if the length is smaller than necessary
     /// or restores to the smallest or the largest date if date is beyond the
     /// accepted value.
-
     fn verify_date(&self, user_date: &mut String) -> Result<String, Box<dyn Error>> {
         // cancel other verification if there is no text
         if user_date.is_empty() {
@@ -45,7 +43,8 @@ pub trait StatusChecker {
         // rewrite the original date in case of extra spaces
         *user_date = format!("{}-{}-{}", data[0], data[1], data[2]);
 
-        let int_year: u32 = data[0].trim().parse()?;
+        // any year is accepted as long as it is a number
+        data[0].trim().parse::<u32>()?;
         let int_month: u32 = data[1].trim().parse()?;
         let int_day: u32 = data[2].trim().parse()?;
 
@@ -81,16 +80,6 @@ pub trait StatusChecker {
 
             return Ok("Date: Day length not acceptable. Example Date: 2022-05-01".to_string());
 
-        // checks if the year value is between 2022 and 2025
-        } else if !(2022..=2025).contains(&int_year) {
-            if int_year < 2022 {
-                *user_date = format!("2022-{}-{}", data[1], data[2]);
-            } else if int_year > 2025 {
-                *user_date = format!("2025-{}-{}", data[1], data[2]);
-            }
-
-            return Ok("Date: Year must be between 2022-2025".to_string());
-
         // checks if the month value is between 1 and 12
         } else if !(1..=12).contains(&int_month) {
             if int_month < 1 {
@@ -134,7 +123,6 @@ pub trait StatusChecker {
     /// - contains any extra spaces
     ///
     /// if the value is not float, tries to make it float ending with double zero
-
     fn verify_amount(&self, amount: &mut String) -> Result<String, Box<dyn Error>> {
         // cancel all verification if the amount is empty
         if amount.is_empty() {
@@ -214,59 +202,86 @@ pub trait StatusChecker {
     /// Checks if:
     ///
     /// - The Transaction method exists on the database.
+    /// - The Transaction method is archived
     /// - The Transaction method is empty
     /// - contains any extra spaces
-    /// if the Transaction is not found, matches each character with the available
-    /// Transaction Methods and corrects to the best matching one.
-
+    ///
+    /// if the Transaction is not found or archived, matches each character with the available
+    /// active Transaction Methods and corrects to the best matching one.
     fn verify_tx_method(
         &self,
         cu_method: &mut String,
         conn: &Connection,
     ) -> Result<String, Box<dyn Error>> {
-        // get all currently added tx methods
-        let all_tx_methods = get_all_tx_methods(conn);
+        // get all tx methods that can still be used. Archived methods are rejected
+        let all_tx_methods = get_active_tx_methods(conn);
 
         *cu_method = cu_method.trim().to_string();
+        let archived = get_archived_tx_methods(conn).contains(cu_method);
 
         // cancel all verification if the text is empty
         if cu_method.is_empty() {
             return Ok("TX Method: Nothing to check".to_string());
         }
 
-        // loops through all tx methods and matches each character
-        // of the tx method with the current inputted text. Based on matches
-        // selects the best matching one if text is not any exact match.
         if all_tx_methods.contains(cu_method) {
             return Ok("TX Method: Transaction Method Accepted".to_string());
-        } else {
-            let mut current_match = all_tx_methods[0].clone();
-            let mut current_chance = 0;
-
-            for i in all_tx_methods {
-                let mut total_match = 0;
-                let method = i.trim().to_string();
-                for i in method.chars() {
-                    if cu_method
-                        .to_lowercase()
-                        .contains(&i.to_string().to_lowercase().to_string())
-                    {
-                        total_match += 1;
-                    }
-                }
-                let chance = (100 * total_match) / method.len();
-
-                if chance > current_chance {
-                    current_match = method;
-                    current_chance = chance;
-                }
-            }
-            *cu_method = current_match;
         }
+        *cu_method = get_best_match(cu_method, &all_tx_methods);
 
+        if archived {
+            return Ok("TX Method: Transaction Method is archived".to_string());
+        }
         Ok("TX Method: Transaction Method not found".to_string())
     }
 
+    /// Checks if:
+    ///
+    /// - The category exists on the database.
+    /// - The category is empty
+    /// - contains any extra spaces
+    ///
+    /// if the category is not found, corrects it to the best matching category the same way
+    /// as the Transaction Methods.
+    fn verify_category(
+        &self,
+        cu_category: &mut String,
+        conn: &Connection,
+    ) -> Result<String, Box<dyn Error>> {
+        let all_categories = get_all_categories(conn);
+
+        *cu_category = cu_category.trim().to_string();
+
+        // cancel all verification if the text is empty. Categories are optional
+        if cu_category.is_empty() {
+            return Ok("Category: Nothing to check".to_string());
+        }
+
+        if all_categories.is_empty() {
+            return Ok("Category: No category has been added yet".to_string());
+        } else if all_categories.contains(cu_category) {
+            return Ok("Category: Category Accepted".to_string());
+        }
+
+        *cu_category = get_best_match(cu_category, &all_categories);
+        Ok("Category: Category not found".to_string())
+    }
+
+    /// Checks if:
+    ///
+    /// - The tags are empty
+    /// - contains any extra spaces, empty or repeated tags
+    ///
+    /// Rewrites the tags separated by a comma and a space.
+    fn verify_tags(&self, tags: &mut String) -> Result<String, Box<dyn Error>> {
+        *tags = split_tags(tags).join(", ");
+
+        if tags.is_empty() {
+            return Ok("Tags: Nothing to check".to_string());
+        }
+        Ok("Tags: Tags Accepted".to_string())
+    }
+
     /// Checks if:
     ///
     /// - The transaction method starts with E or I
@@ -289,3 +304,30 @@ pub trait StatusChecker {
         }
     }
 }
+
+/// Loops through all the values and matches each character of them with the
+/// inputted text. Returns the value with the highest match percentage.
+fn get_best_match(text: &str, all_values: &[String]) -> String {
+    let mut current_match = all_values[0].clone();
+    let mut current_chance = 0;
+
+    for i in all_values {
+        let mut total_match = 0;
+        let value = i.trim().to_string();
+        for i in value.chars() {
+            if text
+                .to_lowercase()
+                .contains(&i.to_string().to_lowercase().to_string())
+            {
+                total_match += 1;
+            }
+        }
+        let chance = (100 * total_match) / value.len();
+
+        if chance > current_chance {
+            current_match = value;
+            current_chance = chance;
+        }
+    }
+    current_match
+}
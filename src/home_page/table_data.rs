@@ -1,36 +1,156 @@
-use crate::db::{delete_tx, get_all_changes, get_all_tx_methods, get_all_txs, get_last_balances};
+use crate::db::{
+    amount_to_cents, cents_to_amount, cents_to_change, delete_tx, get_all_changes,
+    get_all_tx_methods, get_all_txs, get_last_balances, get_tx_legs,
+};
 use rusqlite::{Connection, Result as sqlResult};
 
 /// This struct stores the transaction data, balance, changes and the id num
 /// TX, Balance, Changes are parsed and selected to only show the relevant part.
 /// Data storing format is:
 ///
-/// all_tx : `[[date, details, tx_method, source, tx_type],]`
+/// all_tx : `[[date, details, tx_method, amount, tx_type, category, tags],]`
 ///
-/// all_balance: `[["123.00", "123.00"],]`
+/// all_balance: `[[12300, 12300],]`
 ///
-/// all_changes: `[["↓123.00", "↑123.00"],]`
+/// all_changes: `[[-12300, 12300],]`
 ///
 /// all_id_num : `["1", "2", "3",]` required only to delete the transaction
+///
+/// tag : the tag the transactions are filtered with, if any
+///
+/// leg_rows : `[false, true, true]` marks the rows that show a leg of the split transaction above them
+///
+/// Balances and changes are kept in cents and only turned into text when they are returned for the interface.
 pub struct TransactionData {
     pub all_tx: Vec<Vec<String>>,
-    all_balance: Vec<Vec<String>>,
-    all_changes: Vec<Vec<String>>,
+    all_balance: Vec<Vec<i64>>,
+    all_changes: Vec<Vec<i64>>,
     all_id_num: Vec<String>,
+    tag: Option<String>,
+    leg_rows: Vec<bool>,
 }
 
 impl TransactionData {
     /// Calls the db to fetch transaction data, transaction changes, balances and id numbers
-    /// from the given month and year index
+    /// from the given month index and year
     pub fn new(conn: &Connection, month: usize, year: usize) -> Self {
         let (all_tx, all_balance, all_id_num) = get_all_txs(conn, month, year);
         let all_changes = get_all_changes(conn, month, year);
+        let leg_rows = vec![false; all_tx.len()];
         TransactionData {
             all_tx,
             all_balance,
             all_changes,
             all_id_num,
+            tag: None,
+            leg_rows,
+        }
+    }
+
+    /// Keeps only the transactions that carry the given tag. The balance, changes and id num
+    /// of each kept transaction stay the same. None keeps every transaction.
+    pub fn filter_by_tag(&mut self, tag: Option<String>) {
+        if let Some(target_tag) = &tag {
+            let kept_index = self
+                .all_tx
+                .iter()
+                .enumerate()
+                .filter(|(_, tx)| tx[6].split(", ").any(|tx_tag| tx_tag == target_tag))
+                .map(|(index, _)| index)
+                .collect::<Vec<usize>>();
+
+            self.all_tx = kept_index.iter().map(|i| self.all_tx[*i].clone()).collect();
+            self.all_balance = kept_index
+                .iter()
+                .map(|i| self.all_balance[*i].clone())
+                .collect();
+            self.all_changes = kept_index
+                .iter()
+                .map(|i| self.all_changes[*i].clone())
+                .collect();
+            self.all_id_num = kept_index
+                .iter()
+                .map(|i| self.all_id_num[*i].clone())
+                .collect();
+            self.leg_rows = kept_index.iter().map(|i| self.leg_rows[*i]).collect();
         }
+        self.tag = tag;
+    }
+
+    /// Returns whether the row of the given index is a split transaction or one of its legs
+    pub fn is_split(&self, index: usize) -> bool {
+        self.leg_rows[index] || self.all_tx[index][2] == "Split"
+    }
+
+    /// Shows the legs of the split transaction of the given index under it or hides them if they
+    /// are already shown. The index can be the split transaction or one of its legs. Leg rows keep the
+    /// balance of their split transaction and only show the change of their own leg.
+    /// Returns the index of the split transaction.
+    pub fn toggle_legs(&mut self, conn: &Connection, index: usize) -> usize {
+        let mut parent = index;
+        while self.leg_rows[parent] {
+            parent -= 1;
+        }
+
+        let total_shown = self.leg_rows[parent + 1..]
+            .iter()
+            .take_while(|is_leg| **is_leg)
+            .count();
+
+        if total_shown != 0 {
+            let shown_range = parent + 1..parent + 1 + total_shown;
+            self.all_tx.drain(shown_range.clone());
+            self.all_balance.drain(shown_range.clone());
+            self.all_changes.drain(shown_range.clone());
+            self.all_id_num.drain(shown_range.clone());
+            self.leg_rows.drain(shown_range);
+            return parent;
+        }
+
+        let all_tx_methods = get_all_tx_methods(conn);
+        let tx_type = self.all_tx[parent][4].clone();
+
+        for (leg_index, leg) in get_tx_legs(conn, self.get_id_num(parent))
+            .iter()
+            .enumerate()
+        {
+            let position = parent + 1 + leg_index;
+
+            let leg_amount = amount_to_cents(&leg[1]).unwrap();
+            let mut leg_changes = vec![0; all_tx_methods.len()];
+            if let Some(method_index) = all_tx_methods.iter().position(|a| a == &leg[0]) {
+                leg_changes[method_index] = if tx_type == "Expense" {
+                    -leg_amount
+                } else {
+                    leg_amount
+                };
+            }
+
+            self.all_tx.insert(
+                position,
+                vec![
+                    "".to_string(),
+                    format!("↳ Leg {}", leg_index + 1),
+                    leg[0].clone(),
+                    leg[1].clone(),
+                    tx_type.clone(),
+                    leg[2].clone(),
+                    "".to_string(),
+                ],
+            );
+            self.all_balance
+                .insert(position, self.all_balance[parent].clone());
+            self.all_changes.insert(position, leg_changes);
+            self.all_id_num
+                .insert(position, self.all_id_num[parent].clone());
+            self.leg_rows.insert(position, true);
+        }
+        parent
+    }
+
+    /// Returns the tag the transactions are currently filtered with
+    pub fn get_tag(&self) -> &Option<String> {
+        &self.tag
     }
 
     /// returns all the Transaction data that is saved inside the struct for the
@@ -46,17 +166,13 @@ impl TransactionData {
     /// returns all the balance data that is saved inside the struct for the current selected month
     pub fn get_balance(&self, index: usize) -> Vec<String> {
         let mut balance_data = vec!["Balance".to_string()];
+        let mut total_balance = 0;
         for i in self.all_balance[index].iter() {
-            balance_data.push(format!("{:.2}", i.parse::<f64>().unwrap()));
+            balance_data.push(cents_to_amount(*i));
+            total_balance += i;
         }
 
-        let mut total_balance: f64 = 0.0;
-        for i in balance_data.iter().skip(1) {
-            let int_bal = i.parse::<f64>().unwrap();
-            total_balance += int_bal;
-        }
-        let formatted_total_balance = format!("{:.2}", total_balance);
-        balance_data.push(formatted_total_balance);
+        balance_data.push(cents_to_amount(total_balance));
         balance_data
     }
 
@@ -64,18 +180,14 @@ impl TransactionData {
     /// The value is saved in the DB at the final row
     pub fn get_last_balance(&self, conn: &Connection) -> Vec<String> {
         let mut balance_data = vec!["Balance".to_string()];
+        let mut total_balance = 0;
         let db_data = get_last_balances(conn, &get_all_tx_methods(conn));
         for i in db_data.iter() {
-            balance_data.push(format!("{:.2}", i.parse::<f64>().unwrap()));
+            balance_data.push(cents_to_amount(*i));
+            total_balance += i;
         }
 
-        let mut total_balance: f64 = 0.0;
-        for i in balance_data.iter().skip(1) {
-            let int_bal = i.parse::<f64>().unwrap();
-            total_balance += int_bal;
-        }
-        let formatted_total_balance = format!("{:.2}", total_balance);
-        balance_data.push(formatted_total_balance);
+        balance_data.push(cents_to_amount(total_balance));
         balance_data
     }
 
@@ -83,20 +195,7 @@ impl TransactionData {
     pub fn get_changes(&self, index: usize) -> Vec<String> {
         let mut changes_data = vec!["Changes".to_string()];
         for i in self.all_changes[index].iter() {
-            let mut new_value = i.to_string();
-            let split = i.split('.');
-            let splitted = split.collect::<Vec<&str>>();
-
-            // the splitting and checking is necessary to make sure all strings are
-            // properly ending with 2 values after dot. it's a string with ↓ or ↑
-            // so format!("{:.2}", parse to f64 won't work for the symbol.
-
-            if splitted[1].len() == 1 {
-                new_value = format!("{}0", i)
-            } else if splitted[1].is_empty() {
-                new_value = format!("{}.00", i)
-            }
-            changes_data.push(new_value);
+            changes_data.push(cents_to_change(*i));
         }
         changes_data
     }
@@ -107,9 +206,9 @@ impl TransactionData {
     }
 
     /// gets the ID Number of the selected table row and calls the function to delete a transaction from the database
-    pub fn del_tx(&self, index: usize) -> sqlResult<()> {
+    pub fn del_tx(&self, index: usize, conn: &Connection) -> sqlResult<()> {
         let target_id = self.get_id_num(index);
-        delete_tx(target_id as usize, "data.sqlite")
+        delete_tx(conn, target_id as usize)
     }
 
     /// returns total incomes for the selected month by going through all the tx saved in the struct
@@ -126,13 +225,14 @@ impl TransactionData {
             stopping_index = a as i32;
         }
 
-        let mut total_income = 0.0_f64;
-        for tx in self.all_tx.iter() {
+        let mut total_income = 0;
+        for (tx, is_leg) in self.all_tx.iter().zip(&self.leg_rows) {
             let amount = &tx[3];
             let tx_type = &tx[4];
 
-            if tx_type == "Income" {
-                total_income += amount.parse::<f64>().unwrap();
+            // legs are already counted in the amount of their split transaction
+            if tx_type == "Income" && !is_leg {
+                total_income += amount_to_cents(amount).unwrap();
             }
             if stopping_index == 0 {
                 break;
@@ -140,7 +240,7 @@ impl TransactionData {
                 stopping_index -= 1
             }
         }
-        final_income.push(format!("{:.2}", total_income));
+        final_income.push(cents_to_amount(total_income));
         final_income
     }
 
@@ -158,13 +258,14 @@ impl TransactionData {
             stopping_index = a as i32;
         }
 
-        let mut total_expense = 0.0_f64;
-        for tx in self.all_tx.iter() {
+        let mut total_expense = 0;
+        for (tx, is_leg) in self.all_tx.iter().zip(&self.leg_rows) {
             let amount = &tx[3];
             let tx_type = &tx[4];
 
-            if tx_type == "Expense" {
-                total_expense += amount.parse::<f64>().unwrap();
+            // legs are already counted in the amount of their split transaction
+            if tx_type == "Expense" && !is_leg {
+                total_expense += amount_to_cents(amount).unwrap();
             }
             if stopping_index == 0 {
                 break;
@@ -172,7 +273,7 @@ impl TransactionData {
                 stopping_index -= 1
             }
         }
-        final_expense.push(format!("{:.2}", total_expense));
+        final_expense.push(cents_to_amount(total_expense));
         final_expense
     }
 }
@@ -11,7 +11,7 @@ use tui::{
 /// This function is responsible for drawing all the widgets in the Home page,
 /// coloring everything and all related things.  This function takes several arguments
 /// from the run_app function with the necessary data and fields.
-
+#[allow(clippy::too_many_arguments)]
 pub fn ui<B: Backend>(
     f: &mut Frame<B>,
     months: &TimeData,
@@ -20,6 +20,7 @@ pub fn ui<B: Backend>(
     balance: &mut [Vec<String>],
     cu_tab: &SelectedTab,
     width_data: &mut [Constraint],
+    tag_filter: &Option<String>,
 ) {
     let size = f.size();
 
@@ -40,9 +41,17 @@ pub fn ui<B: Backend>(
     let normal_style = Style::default().bg(Color::LightBlue);
 
     // Transaction widget's top row/header to highlight what each data will mean
-    let header_cells = ["Date", "Details", "TX Method", "Amount", "Type"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Rgb(255, 255, 255))));
+    let header_cells = [
+        "Date",
+        "Details",
+        "TX Method",
+        "Amount",
+        "Type",
+        "Category",
+        "Tags",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(Color::Rgb(255, 255, 255))));
 
     let header = Row::new(header_cells)
         .style(normal_style)
@@ -141,13 +150,21 @@ pub fn ui<B: Backend>(
     // set up the table columns and their size
     // resizing the table headers to match a % of the
     // terminal space
+    // show the selected tag on the Transactions widget while the transactions are filtered
+    let table_title = match tag_filter {
+        Some(tag) => format!("Transactions (Tag: {tag})"),
+        None => "Transactions".to_string(),
+    };
+
     let mut table_area = Table::new(rows)
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Transactions"))
+        .block(Block::default().borders(Borders::ALL).title(table_title))
         .widths(&[
             Constraint::Percentage(10),
-            Constraint::Percentage(40),
+            Constraint::Percentage(25),
             Constraint::Percentage(15),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
             Constraint::Percentage(15),
             Constraint::Percentage(15),
         ]);
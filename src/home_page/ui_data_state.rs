@@ -57,20 +57,36 @@ impl TableData {
 /// It is used for keeping track of the Months and Years current index.
 ///
 /// titles: `["January", "February",]`
-pub struct TimeData<'a> {
-    pub titles: Vec<&'a str>,
+pub struct TimeData {
+    pub titles: Vec<String>,
     pub index: usize,
 }
 
-impl<'a> TimeData<'a> {
+impl TimeData {
     /// Creates a new time data with the given titles and index at 0.
-    pub fn new(values: Vec<&'a str>) -> Self {
+    pub fn new(values: Vec<&str>) -> Self {
         TimeData {
-            titles: values,
+            titles: values.iter().map(|a| a.to_string()).collect(),
             index: 0,
         }
     }
 
+    /// Replaces the titles with the given values while keeping the currently selected
+    /// title selected. Goes to index 0 if the selected title no longer exists.
+    pub fn update_titles(&mut self, values: Vec<String>) {
+        let selected = self.titles.get(self.index).cloned();
+        self.index = values
+            .iter()
+            .position(|a| Some(a) == selected.as_ref())
+            .unwrap_or(0);
+        self.titles = values;
+    }
+
+    /// Returns the currently selected title as a year. Used with the Years widget.
+    pub fn get_year(&self) -> usize {
+        self.titles[self.index].parse().unwrap()
+    }
+
     /// Increases the current index by 1 or goes to 0 if at the final value
     pub fn next(&mut self) {
         self.index = (self.index + 1) % self.titles.len();
@@ -128,6 +144,8 @@ pub enum TxTab {
     TxMethod,
     Amount,
     TxType,
+    Category,
+    Tags,
     Nothing,
 }
 
@@ -141,6 +159,7 @@ pub enum TransferTab {
     From,
     To,
     Amount,
+    Tags,
     Nothing,
 }
 
@@ -152,6 +171,10 @@ pub enum CurrentUi {
     AddTx,
     Transfer,
     Chart,
+    Recurring,
+    Budget,
+    Trash,
+    Import,
 }
 
 /// Indicates which popup is currently on and is being shown in the screen
@@ -159,5 +182,15 @@ pub enum PopupState {
     NewUpdate,
     Helper,
     DeleteFailed,
+    /// The result of undoing or redoing a change
+    Journal(String),
+    /// The result of exporting the selected month
+    Export(String),
+    /// The result of importing the rows of a statement file
+    Import(String),
+    /// The reason a transaction could not be restored from the trash
+    RestoreFailed(String),
+    /// Asks before the selected transaction is removed from the trash for good
+    PurgeConfirm,
     Nothing,
 }
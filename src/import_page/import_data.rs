@@ -0,0 +1,57 @@
+use crate::db::{import_rows, StatementImport};
+use rusqlite::{Connection, Result as sqlResult};
+
+/// Stores the rows of a statement file that are previewed on the Import page before they are added
+///
+/// file_name : the name of the file the rows were read from, shown on the page title
+pub struct ImportData {
+    pub file_name: String,
+    statement: StatementImport,
+}
+
+impl ImportData {
+    /// Keeps the checked rows of the file for the preview
+    pub fn new(file_name: &str, statement: StatementImport) -> Self {
+        ImportData {
+            file_name: file_name.to_string(),
+            statement,
+        }
+    }
+
+    /// Returns all the rows inside a vector for the table. The status is either OK or every
+    /// problem found in the row.
+    /// return example: `[["2", "2022-08-19", "Groceries", "Card", "30.00", "Expense", "OK"],]`
+    pub fn get_rows(&self) -> Vec<Vec<String>> {
+        self.statement
+            .rows
+            .iter()
+            .map(|row| {
+                let status = if row.problems.is_empty() {
+                    "OK".to_string()
+                } else {
+                    row.problems.join(". ")
+                };
+                vec![
+                    row.line.to_string(),
+                    row.date.clone(),
+                    row.details.clone(),
+                    row.tx_method.clone(),
+                    row.amount.clone(),
+                    row.tx_type.clone(),
+                    status,
+                ]
+            })
+            .collect()
+    }
+
+    /// Returns the number of rows that will be added and the number of rows that will be skipped
+    pub fn get_totals(&self) -> (usize, usize) {
+        let total_valid = self.statement.total_valid();
+        (total_valid, self.statement.rows.len() - total_valid)
+    }
+
+    /// Adds every row without a problem to its Transaction Method
+    pub fn save(&self, conn: &Connection) -> sqlResult<usize> {
+        import_rows(conn, &self.statement)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::home_page::TableData;
+use crate::import_page::ImportData;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+/// The UI function that draws the Import page. Shows every row of the statement file as it will be added
+/// along with the problems that stop a row from being added. Nothing is saved until it is confirmed.
+pub fn import_ui<B: Backend>(f: &mut Frame<B>, table: &mut TableData, import_data: &ImportData) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Length(7), Constraint::Min(0)].as_ref())
+        .split(size);
+
+    let block = Block::default().style(
+        Style::default()
+            .bg(Color::Rgb(255, 255, 255))
+            .fg(Color::Rgb(50, 205, 50)),
+    );
+    f.render_widget(block, size);
+
+    let (total_valid, total_skipped) = import_data.get_totals();
+
+    let help_text = vec![
+        Spans::from(format!(
+            "{total_valid} rows will be added. {total_skipped} rows with problems will be skipped."
+        )),
+        Spans::from("'Arrow Up/Down' : Select a row"),
+        Spans::from("'S' : Add all the rows marked OK"),
+        Spans::from("'F' : Cancel the import and go to the Home Page"),
+    ];
+
+    let help_sec = Paragraph::new(help_text)
+        .style(
+            Style::default()
+                .bg(Color::Rgb(255, 255, 255))
+                .fg(Color::Rgb(50, 205, 50)),
+        )
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(
+            "Help",
+            Style::default().add_modifier(Modifier::BOLD),
+        )))
+        .alignment(Alignment::Left);
+
+    let header_cells = [
+        "Line",
+        "Date",
+        "Details",
+        "TX Method",
+        "Amount",
+        "Type",
+        "Status",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(Color::Rgb(255, 255, 255))));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::LightBlue))
+        .height(1)
+        .bottom_margin(0);
+
+    // rows with problems are shown in red
+    let rows = table.items.iter().map(|item| {
+        let cells = item.iter().map(|c| Cell::from(c.to_string()));
+        let style = match item.last() {
+            Some(status) if status != "OK" => Style::default().fg(Color::Red),
+            _ => Style::default(),
+        };
+        Row::new(cells).style(style).height(1).bottom_margin(0)
+    });
+
+    let table_area = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Import Preview: {}", import_data.file_name)),
+        )
+        .widths(&[
+            Constraint::Percentage(6),
+            Constraint::Percentage(11),
+            Constraint::Percentage(22),
+            Constraint::Percentage(15),
+            Constraint::Percentage(10),
+            Constraint::Percentage(8),
+            Constraint::Percentage(28),
+        ])
+        .highlight_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::REVERSED),
+        )
+        .highlight_symbol(">> ");
+
+    f.render_widget(help_sec, chunks[0]);
+    f.render_stateful_widget(table_area, chunks[1], &mut table.state)
+}
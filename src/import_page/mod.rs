@@ -0,0 +1,5 @@
+mod import_data;
+mod import_ui;
+
+pub use import_data::ImportData;
+pub use import_ui::import_ui;
@@ -1,14 +1,24 @@
+use crate::budget_page::{budget_ui, BudgetData};
 use crate::chart_page::{chart_ui, ChartData};
-use crate::db::{get_all_tx_methods, get_empty_changes};
+use crate::db::{
+    get_all_tx_methods, get_all_years, get_archived_tx_methods, get_empty_changes,
+    get_total_changes,
+};
 use crate::home_page::ui;
 use crate::home_page::TransactionData;
 use crate::home_page::{
     CurrentUi, PopupState, SelectedTab, TableData, TimeData, TransferTab, TxTab,
 };
+use crate::import_page::{import_ui, ImportData};
 use crate::initial_page::starter_ui;
-use crate::key_checker::{add_tx_keys, chart_keys, home_keys, initial_keys, transfer_keys};
-use crate::popup_page::add_popup;
+use crate::key_checker::{
+    add_tx_keys, budget_keys, chart_keys, home_keys, import_keys, initial_keys, recurring_keys,
+    transfer_keys, trash_keys,
+};
+use crate::popup_page::{add_confirm_popup, add_message_popup, add_popup};
+use crate::recurring_page::{recurring_ui, RecurringData};
 use crate::transfer_page::{transfer_ui, TransferData};
+use crate::trash_page::{trash_ui, TrashData};
 use crate::tx_page::tx_ui;
 use crate::tx_page::AddTxData;
 use crossterm::event::poll;
@@ -25,16 +35,20 @@ use tui::{backend::Backend, Terminal};
 pub fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     mut months: TimeData,
-    mut years: TimeData,
     new_version_available: bool,
+    mut import_data: Option<ImportData>,
+    conn: &Connection,
 ) -> Result<String, Box<dyn Error>> {
     // Setting up some default values. Let's go through all of them
     // selected_tab : Basically the current selected widget/field. Default set to the month selection/3rd widget
     //
+    // years : The year selection widget data. Years are gathered from the database so a new year shows up
+    // as soon as a transaction is added to it.
+    //
     // last_month_index & last_year_index : The current selected index of the 2nd and 3rd or month and year selection widget.
     // This is important because using the index we will be moving the cursor on arrow key presses by passing it to the home page ui.
     //
-    // path & conn : The connection status and the path of the database
+    // conn : The connection to the database that is shared by every page
     //
     // all_data : This is a struct that fetches and stores the home page data based on the current month and year index.
     // It contains the selected month and year's all transaction, all ↑ and ↓ which is stored in the database,
@@ -53,6 +67,26 @@ pub fn run_app<B: Backend>(
     // calculated from the transaction saved in the database, it is needed for the Income and Expense section in the Home page.
     // Why is it a vector? Because the entire row has to be saved inside this to put in the UI.
     //
+    // recurring_table : The table of the Recurring page. The templates are only reloaded after the database changes.
+    //
+    // trash_table : The table of the Trash page. The deleted transactions are only reloaded after the database changes.
+    //
+    // import_table : The table of the Import page. Filled with the rows of the statement file when the app starts with an import.
+    //
+    // import_data : The checked rows of a statement file waiting to be confirmed. The app opens on the Import page when it is given.
+    //
+    // budget_table : The table of the Budget page. The budgets are only reloaded after the selected month or the database changes.
+    //
+    // recurring_changes, trash_changes & budget_key : The number of changes done on the database when each table was
+    // last loaded, along with the month and year of the budgets. None until the page is opened.
+    //
+    // chart_data : The data of the Chart page. Kept until the selected year or the database changes.
+    //
+    // all_tx_methods & archived_methods : The Transaction Methods shown on the home page and the ones whose columns
+    // are hidden. Reloaded with the year list only after the database changes.
+    //
+    // tag_filter : The tag selected on the home page. Only the transactions with this tag are shown when selected.
+    //
     // starter_index : to keep track of the loop on each iteration on the initial page's animation.
     // version_checked : during the loop of the app, this variable is tracked so we don't keep opening the popup multiple times
 
@@ -60,53 +94,81 @@ pub fn run_app<B: Backend>(
     let mut selected_tab = SelectedTab::Months;
     let mut last_month_index = 99;
     let mut last_year_index = 99;
-    let path = "data.sqlite";
-    let conn = Connection::open(path).expect("Could not connect to database");
-    conn.execute("PRAGMA foreign_keys = ON", [])
-        .expect("Could not enable foreign keys");
-    let mut all_data = TransactionData::new(&conn, 0, 0);
+    let mut years = TimeData::new(get_all_years(conn).iter().map(|a| a.as_str()).collect());
+    let mut all_data = TransactionData::new(conn, 0, years.get_year());
     let mut table = TableData::new(all_data.get_txs());
-    let mut cu_page = CurrentUi::Initial;
+    let mut cu_page = match import_data {
+        Some(_) => CurrentUi::Import,
+        None => CurrentUi::Initial,
+    };
     let mut cu_popup = PopupState::Nothing;
     let mut cu_tx_page = TxTab::Nothing;
     let mut cu_transfer_page = TransferTab::Nothing;
     let mut data_for_tx = AddTxData::new();
     let mut data_for_transfer = TransferData::new();
+    let mut recurring_table = TableData::new(Vec::new());
+    let mut trash_table = TableData::new(Vec::new());
+    let mut budget_table = TableData::new(Vec::new());
+    let mut recurring_changes: Option<i64> = None;
+    let mut trash_changes: Option<i64> = None;
+    let mut budget_key: Option<(usize, usize, i64)> = None;
+    let mut import_table = TableData::new(
+        import_data
+            .as_ref()
+            .map(|data| data.get_rows())
+            .unwrap_or_default(),
+    );
+    let mut chart_data: Option<ChartData> = None;
+    let mut tag_filter: Option<String> = None;
+    let mut all_tx_methods = get_all_tx_methods(conn);
+    let mut archived_methods = get_archived_tx_methods(conn);
+    let mut last_total_changes = get_total_changes(conn);
     let mut starter_index = 0;
 
     // The loop begins at this point and before the loop starts, multiple variables are initiated
     // with the default values which will quickly be changing once the loop starts.
     loop {
+        // a transaction on a new year adds the year to the database so keep the year list updated.
+        // The years and the methods only change with the database so they are not reloaded on every frame
+        let total_changes = get_total_changes(conn);
+        if total_changes != last_total_changes {
+            let all_years = get_all_years(conn);
+            if all_years != years.titles {
+                years.update_titles(all_years);
+            }
+            all_tx_methods = get_all_tx_methods(conn);
+            archived_methods = get_archived_tx_methods(conn);
+            last_total_changes = total_changes;
+        }
+
         // after each refresh this will check the current selected month, year and if a table/spreadsheet row is selected in the ui.
         let cu_month_index = months.index;
         let cu_year_index = years.index;
+        let cu_year = years.get_year();
         let cu_table_index = table.state.selected();
 
-        // reload the data saved in memory each time the month or the year changes
-        if cu_month_index != last_month_index || cu_year_index != last_year_index {
-            all_data = TransactionData::new(&conn, cu_month_index, cu_year_index);
+        // reload the data saved in memory each time the month, the year or the tag filter changes.
+        // Pages that reload the data themselves do not filter it so the filter is applied again here.
+        if cu_month_index != last_month_index
+            || cu_year_index != last_year_index
+            || all_data.get_tag() != &tag_filter
+        {
+            all_data = TransactionData::new(conn, cu_month_index, cu_year);
+            all_data.filter_by_tag(tag_filter.clone());
             table = TableData::new(all_data.get_txs());
             last_month_index = cu_month_index;
             last_year_index = cu_year_index;
         };
 
-        let total_income = all_data.get_total_income(&conn, cu_table_index);
-        let total_expense = all_data.get_total_expense(&conn, cu_table_index);
+        let total_income = all_data.get_total_income(conn, cu_table_index);
+        let total_expense = all_data.get_total_expense(conn, cu_table_index);
 
         // balance variable contains all the 'rows' of the first/Balance widget in the home page.
         // So each line is inside a vector. "" represents empty placeholder.
         let mut balance: Vec<Vec<String>> = vec![vec!["".to_string()]];
-        balance[0].extend(get_all_tx_methods(&conn));
+        balance[0].extend(all_tx_methods.clone());
         balance[0].extend(vec!["Total".to_string()]);
 
-        // save the % of space each column should take in the Balance section based on the total
-        // transaction methods/columns available
-        let width_percent = 100 / balance[0].len() as u16;
-        let mut width_data = vec![];
-        for _i in 0..balance[0].len() {
-            width_data.push(Constraint::Percentage(width_percent));
-        }
-
         // cu_table_index is the spreadsheet/Transaction widget index. If a row is selected,
         // get the balance there was once that transaction happened + the changes it did
         // otherwise, get the absolute final balance after all transaction happened + no changes.
@@ -119,8 +181,8 @@ pub fn run_app<B: Backend>(
             }
             // if none selected, get empty changes + the absolute final balance
             None => {
-                balance.push(all_data.get_last_balance(&conn));
-                balance.push(get_empty_changes(&conn));
+                balance.push(all_data.get_last_balance(conn));
+                balance.push(get_empty_changes(conn));
             }
         }
 
@@ -129,6 +191,31 @@ pub fn run_app<B: Backend>(
         balance.push(total_income.clone());
         balance.push(total_expense.clone());
 
+        // archived methods keep their history and still count towards the Total but their
+        // columns are not shown. The first column of each row is the row name.
+        if !archived_methods.is_empty() {
+            let hidden_columns = balance[0]
+                .iter()
+                .enumerate()
+                .filter(|(index, method)| *index != 0 && archived_methods.contains(method))
+                .map(|(index, _)| index)
+                .collect::<Vec<usize>>();
+
+            for row in balance.iter_mut() {
+                for index in hidden_columns.iter().rev() {
+                    row.remove(*index);
+                }
+            }
+        }
+
+        // save the % of space each column should take in the Balance section based on the total
+        // transaction methods/columns available
+        let width_percent = 100 / balance[0].len() as u16;
+        let mut width_data = vec![];
+        for _i in 0..balance[0].len() {
+            width_data.push(Constraint::Percentage(width_percent));
+        }
+
         // check the version of current TUI and based on that, turn on the popup
         if !version_checked {
             if new_version_available {
@@ -148,11 +235,15 @@ pub fn run_app<B: Backend>(
                     &mut balance,
                     &selected_tab,
                     &mut width_data,
+                    &tag_filter,
                 );
 
-                match cu_popup {
+                match &cu_popup {
                     PopupState::Helper => add_popup(f, 1),
                     PopupState::DeleteFailed => add_popup(f, 2),
+                    PopupState::Journal(message) => add_message_popup(f, "Undo/Redo", message),
+                    PopupState::Export(message) => add_message_popup(f, "Export", message),
+                    PopupState::Import(message) => add_message_popup(f, "Import", message),
                     _ => {}
                 }
             })?,
@@ -192,8 +283,82 @@ pub fn run_app<B: Backend>(
                     add_popup(f, 1)
                 }
             })?,
+            CurrentUi::Recurring => {
+                if recurring_changes != Some(total_changes) {
+                    recurring_table.items = RecurringData::new(conn).get_txs();
+                    recurring_changes = Some(total_changes);
+                }
+                terminal.draw(|f| {
+                    recurring_ui(f, &mut recurring_table);
+
+                    if let PopupState::Helper = cu_popup {
+                        add_popup(f, 1)
+                    }
+                })?
+            }
+            CurrentUi::Trash => {
+                if trash_changes != Some(total_changes) {
+                    trash_table.items = TrashData::new(conn).get_txs();
+                    trash_changes = Some(total_changes);
+                }
+                terminal.draw(|f| {
+                    trash_ui(f, &mut trash_table);
+
+                    match &cu_popup {
+                        PopupState::Helper => add_popup(f, 1),
+                        PopupState::RestoreFailed(message) => {
+                            add_message_popup(f, "Restore Error", message)
+                        }
+                        PopupState::PurgeConfirm => add_confirm_popup(
+                            f,
+                            "Delete For Good",
+                            "The selected transaction will be removed for good.\nIt cannot be restored or undone.",
+                        ),
+                        _ => {}
+                    }
+                })?
+            }
+            CurrentUi::Import => match &import_data {
+                Some(data) => terminal.draw(|f| {
+                    import_ui(f, &mut import_table, data);
+
+                    match &cu_popup {
+                        PopupState::Helper => add_popup(f, 1),
+                        PopupState::Import(message) => add_message_popup(f, "Import", message),
+                        _ => {}
+                    }
+                })?,
+                // the import is only removed while leaving the page
+                None => {
+                    cu_page = CurrentUi::Home;
+                    continue;
+                }
+            },
+            CurrentUi::Budget => {
+                let key = (cu_month_index, cu_year, total_changes);
+                if budget_key != Some(key) {
+                    budget_table.items =
+                        BudgetData::new(conn, cu_month_index, cu_year).get_budgets();
+                    budget_key = Some(key);
+                }
+                let month_title = format!("{} {}", months.titles[cu_month_index], cu_year);
+                terminal.draw(|f| {
+                    budget_ui(f, &mut budget_table, &month_title);
+
+                    if let PopupState::Helper = cu_popup {
+                        add_popup(f, 1)
+                    }
+                })?
+            }
             CurrentUi::Chart => {
-                let data_for_chart = ChartData::set(cu_year_index);
+                // the chart data is only collected again if the year or the database has changed
+                if chart_data
+                    .as_ref()
+                    .is_none_or(|data| data.is_outdated(cu_year, conn))
+                {
+                    chart_data = Some(ChartData::set(cu_year, conn));
+                }
+                let data_for_chart = chart_data.as_ref().unwrap();
                 terminal.draw(|f| {
                     chart_ui(f, data_for_chart);
 
@@ -222,10 +387,11 @@ pub fn run_app<B: Backend>(
                             &mut selected_tab,
                             cu_table_index,
                             cu_month_index,
-                            cu_year_index,
+                            cu_year,
                             &mut months,
                             &mut years,
-                            &conn,
+                            &mut tag_filter,
+                            conn,
                         )?;
                         if status != "0" {
                             return Ok(status);
@@ -242,8 +408,8 @@ pub fn run_app<B: Backend>(
                             &mut table,
                             &mut selected_tab,
                             cu_month_index,
-                            cu_year_index,
-                            &conn,
+                            cu_year,
+                            conn,
                         )?;
                         if status != "0" {
                             return Ok(status);
@@ -266,8 +432,67 @@ pub fn run_app<B: Backend>(
                             &mut table,
                             &mut selected_tab,
                             cu_month_index,
-                            cu_year_index,
-                            &conn,
+                            cu_year,
+                            conn,
+                        )?;
+                        if status != "0" {
+                            return Ok(status);
+                        }
+                    }
+                    CurrentUi::Recurring => {
+                        let status = recurring_keys(
+                            key,
+                            &mut cu_page,
+                            &mut cu_popup,
+                            &mut recurring_table,
+                            conn,
+                        )?;
+                        if status != "0" {
+                            return Ok(status);
+                        }
+                    }
+                    CurrentUi::Trash => {
+                        let status = trash_keys(
+                            key,
+                            &mut cu_page,
+                            &mut cu_popup,
+                            &mut trash_table,
+                            &mut all_data,
+                            &mut table,
+                            cu_month_index,
+                            cu_year,
+                            conn,
+                        )?;
+                        if status != "0" {
+                            return Ok(status);
+                        }
+                    }
+                    CurrentUi::Import => {
+                        let status = import_keys(
+                            key,
+                            &mut cu_page,
+                            &mut cu_popup,
+                            &mut import_table,
+                            &mut import_data,
+                            &mut all_data,
+                            &mut table,
+                            cu_month_index,
+                            cu_year,
+                            conn,
+                        )?;
+                        if status != "0" {
+                            return Ok(status);
+                        }
+                    }
+                    CurrentUi::Budget => {
+                        let status = budget_keys(
+                            key,
+                            &mut cu_page,
+                            &mut cu_popup,
+                            &mut budget_table,
+                            &mut months,
+                            cu_year,
+                            conn,
                         )?;
                         if status != "0" {
                             return Ok(status);
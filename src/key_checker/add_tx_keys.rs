@@ -6,6 +6,7 @@ use std::error::Error;
 
 /// Tracks the keys once interacting with the Add Transaction interface. Based on the key pressed,
 /// calls functions and passes them to a struct
+#[allow(clippy::too_many_arguments)]
 pub fn add_tx_keys(
     key: KeyEvent,
     cu_page: &mut CurrentUi,
@@ -16,7 +17,7 @@ pub fn add_tx_keys(
     table: &mut TableData,
     selected_tab: &mut SelectedTab,
     cu_month_index: usize,
-    cu_year_index: usize,
+    cu_year: usize,
     conn: &Connection,
 ) -> Result<String, Box<dyn Error>> {
     match cu_popup {
@@ -36,12 +37,12 @@ pub fn add_tx_keys(
                     }
                     KeyCode::Char('h') => *cu_popup = PopupState::Helper,
                     KeyCode::Char('s') => {
-                        let status = data_for_tx.add_tx();
+                        let status = data_for_tx.add_tx(conn);
                         if status == *"" {
                             // reload home page and switch UI
                             *selected_tab = SelectedTab::Months;
                             *data_for_tx = AddTxData::new();
-                            *all_data = TransactionData::new(conn, cu_month_index, cu_year_index);
+                            *all_data = TransactionData::new(conn, cu_month_index, cu_year);
                             *table = TableData::new(all_data.get_txs());
                             *cu_page = CurrentUi::Home;
                         } else {
@@ -53,6 +54,8 @@ pub fn add_tx_keys(
                     KeyCode::Char('3') => *cu_tx_page = TxTab::TxMethod,
                     KeyCode::Char('4') => *cu_tx_page = TxTab::Amount,
                     KeyCode::Char('5') => *cu_tx_page = TxTab::TxType,
+                    KeyCode::Char('6') => *cu_tx_page = TxTab::Category,
+                    KeyCode::Char('7') => *cu_tx_page = TxTab::Tags,
                     KeyCode::Enter => *cu_tx_page = TxTab::Nothing,
                     KeyCode::Esc => *cu_tx_page = TxTab::Nothing,
                     _ => {}
@@ -172,7 +175,7 @@ pub fn add_tx_keys(
                                 Ok(a) => {
                                     data_for_tx.add_tx_status(&a);
                                     if a.contains("Accepted") || a.contains("Nothing") {
-                                        *cu_tx_page = TxTab::Nothing
+                                        *cu_tx_page = TxTab::Category
                                     }
                                 }
                                 Err(_) => data_for_tx
@@ -197,6 +200,45 @@ pub fn add_tx_keys(
                         _ => {}
                     }
                 }
+
+                TxTab::Category => match key.code {
+                    KeyCode::Enter | KeyCode::Esc => {
+                        let status = data_for_tx.check_category(conn);
+
+                        match status {
+                            Ok(a) => {
+                                data_for_tx.add_tx_status(&a);
+                                if a.contains("Accepted") || a.contains("Nothing") {
+                                    *cu_tx_page = TxTab::Tags
+                                }
+                            }
+                            Err(_) => data_for_tx
+                                .add_tx_status("Category: Error acquired while checking."),
+                        }
+                    }
+                    KeyCode::Backspace => data_for_tx.edit_category('a', true),
+                    KeyCode::Char(a) => data_for_tx.edit_category(a, false),
+                    _ => {}
+                },
+
+                TxTab::Tags => match key.code {
+                    KeyCode::Enter | KeyCode::Esc => {
+                        let status = data_for_tx.check_tags();
+
+                        match status {
+                            Ok(a) => {
+                                data_for_tx.add_tx_status(&a);
+                                *cu_tx_page = TxTab::Nothing
+                            }
+                            Err(_) => {
+                                data_for_tx.add_tx_status("Tags: Error acquired while checking.")
+                            }
+                        }
+                    }
+                    KeyCode::Backspace => data_for_tx.edit_tags('a', true),
+                    KeyCode::Char(a) => data_for_tx.edit_tags(a, false),
+                    _ => {}
+                },
             }
         }
         _ => *cu_popup = PopupState::Nothing,
@@ -0,0 +1,48 @@
+use crate::budget_page::BudgetData;
+use crate::home_page::{CurrentUi, PopupState, TableData, TimeData};
+use crossterm::event::{KeyCode, KeyEvent};
+use rusqlite::Connection;
+use std::error::Error;
+
+/// Tracks the keys once interacting with the Budget page. The page uses the month and year
+/// selected on the Home page so changing the month here changes it there as well.
+pub fn budget_keys(
+    key: KeyEvent,
+    cu_page: &mut CurrentUi,
+    cu_popup: &mut PopupState,
+    table: &mut TableData,
+    months: &mut TimeData,
+    cu_year: usize,
+    conn: &Connection,
+) -> Result<String, Box<dyn Error>> {
+    match cu_popup {
+        PopupState::Nothing => match key.code {
+            KeyCode::Char('q') => return Ok("".to_string()),
+            KeyCode::Char('f') => {
+                *cu_page = CurrentUi::Home;
+                table.state.select(None);
+            }
+            KeyCode::Char('h') => *cu_popup = PopupState::Helper,
+            KeyCode::Char('s') => return Ok("Budget".to_string()),
+            KeyCode::Char('d') => {
+                if let Some(a) = table.state.selected() {
+                    BudgetData::new(conn, months.index, cu_year).delete(a, conn)?;
+                    table.state.select(None);
+                }
+            }
+            KeyCode::Left => {
+                months.previous();
+                table.state.select(None);
+            }
+            KeyCode::Right => {
+                months.next();
+                table.state.select(None);
+            }
+            KeyCode::Up if !table.items.is_empty() => table.previous(),
+            KeyCode::Down if !table.items.is_empty() => table.next(),
+            _ => {}
+        },
+        _ => *cu_popup = PopupState::Nothing,
+    }
+    Ok("0".to_string())
+}
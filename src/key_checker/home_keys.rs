@@ -1,10 +1,14 @@
+use crate::db::{export_csv, get_all_tags, parse_date_range, redo_last_change, undo_last_change};
 use crate::home_page::{CurrentUi, PopupState, SelectedTab, TableData, TimeData, TransactionData};
 use crate::transfer_page::TransferData;
 use crate::tx_page::AddTxData;
 use crossterm::event::{KeyCode, KeyEvent};
 use rusqlite::Connection;
 use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
 
+#[allow(clippy::too_many_arguments)]
 pub fn home_keys(
     key: KeyEvent,
     cu_page: &mut CurrentUi,
@@ -16,9 +20,10 @@ pub fn home_keys(
     selected_tab: &mut SelectedTab,
     cu_table_index: Option<usize>,
     cu_month_index: usize,
-    cu_year_index: usize,
+    cu_year: usize,
     months: &mut TimeData,
     years: &mut TimeData,
+    tag_filter: &mut Option<String>,
     conn: &Connection,
 ) -> Result<String, Box<dyn Error>> {
     match cu_popup {
@@ -28,10 +33,42 @@ pub fn home_keys(
                 KeyCode::Char('a') => *cu_page = CurrentUi::AddTx,
                 KeyCode::Char('t') => *cu_page = CurrentUi::Transfer,
                 KeyCode::Char('r') => *cu_page = CurrentUi::Chart,
+                KeyCode::Char('p') => *cu_page = CurrentUi::Recurring,
+                KeyCode::Char('b') => *cu_page = CurrentUi::Budget,
+                KeyCode::Char('w') => *cu_page = CurrentUi::Trash,
                 KeyCode::Char('j') => return Ok("Change".to_string()),
+                KeyCode::Char('n') => return Ok("Rename".to_string()),
+                KeyCode::Char('v') => return Ok("Manage".to_string()),
+                KeyCode::Char('g') => return Ok("Category".to_string()),
+                KeyCode::Char('l') => return Ok("Split".to_string()),
+                KeyCode::Char('c') => return Ok("Rebuild".to_string()),
+                KeyCode::Char('i') => return Ok("Import".to_string()),
                 KeyCode::Char('h') => *cu_popup = PopupState::Helper,
-                KeyCode::Char('e') => {
+                KeyCode::Char('x') => {
+                    // moves the filter to the next tag and goes back to showing every
+                    // transaction after the last one
+                    let all_tags = get_all_tags(conn);
+                    let next_index = match tag_filter {
+                        Some(tag) => all_tags.iter().position(|a| a == tag).map(|a| a + 1),
+                        None => Some(0),
+                    };
+                    *tag_filter = next_index.and_then(|a| all_tags.get(a).cloned());
+                    table.state.select(None);
+                    *selected_tab = SelectedTab::Months;
+                }
+                KeyCode::Enter => {
+                    // shows or hides the legs of the selected split transaction and keeps it selected
                     if let Some(a) = cu_table_index {
+                        if all_data.is_split(a) {
+                            let split_index = all_data.toggle_legs(conn, a);
+                            *table = TableData::new(all_data.get_txs());
+                            table.state.select(Some(split_index));
+                        }
+                    }
+                }
+                KeyCode::Char('e') => {
+                    // split transactions cannot be edited on the Add Transaction page
+                    if let Some(a) = cu_table_index.filter(|a| !all_data.is_split(*a)) {
                         let target_data = &all_data.get_txs()[a];
                         let target_id_num = all_data.get_id_num(a);
                         let tx_type = &target_data[4];
@@ -45,6 +82,8 @@ pub fn home_keys(
                                 &target_data[2],
                                 &target_data[3],
                                 &target_data[4],
+                                &target_data[5],
+                                &target_data[6],
                                 target_id_num,
                             );
                             *cu_page = CurrentUi::AddTx;
@@ -58,6 +97,7 @@ pub fn home_keys(
                                 from_method,
                                 to_method,
                                 &target_data[3],
+                                &target_data[6],
                                 target_id_num,
                             );
                             *cu_page = CurrentUi::Transfer;
@@ -65,13 +105,12 @@ pub fn home_keys(
                     }
                 }
                 KeyCode::Char('d') => {
-                    if table.state.selected() != None {
-                        let status = all_data.del_tx(table.state.selected().unwrap());
+                    if let Some(a) = table.state.selected() {
+                        let status = all_data.del_tx(a, conn);
                         match status {
                             Ok(_) => {
                                 // transaction deleted so reload the data again
-                                *all_data =
-                                    TransactionData::new(conn, cu_month_index, cu_year_index);
+                                *all_data = TransactionData::new(conn, cu_month_index, cu_year);
                                 *table = TableData::new(all_data.get_txs());
                                 table.state.select(None);
                                 *selected_tab = SelectedTab::Months;
@@ -82,6 +121,48 @@ pub fn home_keys(
                         }
                     }
                 }
+                KeyCode::Char('u') | KeyCode::Char('y') => {
+                    let (status, action) = if key.code == KeyCode::Char('u') {
+                        (undo_last_change(conn), "undo")
+                    } else {
+                        (redo_last_change(conn), "redo")
+                    };
+                    let message = match status {
+                        Ok(Some(message)) => {
+                            // the change can touch any month so reload the data again
+                            *all_data = TransactionData::new(conn, cu_month_index, cu_year);
+                            *table = TableData::new(all_data.get_txs());
+                            table.state.select(None);
+                            *selected_tab = SelectedTab::Months;
+                            message
+                        }
+                        Ok(None) => format!("There is nothing to {action}"),
+                        Err(e) => format!("Could not {action} the change. Error: {e}"),
+                    };
+                    *cu_popup = PopupState::Journal(message);
+                }
+                KeyCode::Char('o') => {
+                    // the file is saved in the directory the app was started from
+                    let month = format!("{cu_year}-{:02}", cu_month_index + 1);
+                    let file_name = format!("rex-{month}.csv");
+                    let status = match parse_date_range(&month) {
+                        Ok((start, end)) => export_csv(conn, Path::new(&file_name), start, end),
+                        Err(e) => Err(e.into()),
+                    };
+                    let message = match status {
+                        // the full path is shown so the file can be found
+                        Ok(total_txs) => {
+                            let file_path = fs::canonicalize(&file_name)
+                                .unwrap_or_else(|_| PathBuf::from(&file_name));
+                            format!(
+                                "Exported {total_txs} transactions to\n{}\n\nA year or a range of dates can be exported\nwith the export command",
+                                file_path.display()
+                            )
+                        }
+                        Err(e) => format!("Could not export the month. Error: {e}"),
+                    };
+                    *cu_popup = PopupState::Export(message);
+                }
                 KeyCode::Right => match &selected_tab {
                     SelectedTab::Months => months.next(),
                     SelectedTab::Years => {
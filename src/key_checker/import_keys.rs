@@ -0,0 +1,54 @@
+use crate::home_page::{CurrentUi, PopupState, TableData, TransactionData};
+use crate::import_page::ImportData;
+use crossterm::event::{KeyCode, KeyEvent};
+use rusqlite::Connection;
+use std::error::Error;
+
+/// Tracks the keys once interacting with the Import page. The import is dropped once it is saved
+/// or cancelled and the app goes back to the Home page.
+#[allow(clippy::too_many_arguments)]
+pub fn import_keys(
+    key: KeyEvent,
+    cu_page: &mut CurrentUi,
+    cu_popup: &mut PopupState,
+    table: &mut TableData,
+    import_data: &mut Option<ImportData>,
+    all_data: &mut TransactionData,
+    home_table: &mut TableData,
+    cu_month_index: usize,
+    cu_year: usize,
+    conn: &Connection,
+) -> Result<String, Box<dyn Error>> {
+    match cu_popup {
+        PopupState::Nothing => match key.code {
+            KeyCode::Char('q') => return Ok("".to_string()),
+            KeyCode::Char('f') => {
+                *import_data = None;
+                *cu_page = CurrentUi::Home;
+            }
+            KeyCode::Char('h') => *cu_popup = PopupState::Helper,
+            KeyCode::Char('s') => {
+                if let Some(data) = import_data {
+                    let (_, total_skipped) = data.get_totals();
+                    let message = match data.save(conn) {
+                        Ok(total_added) => {
+                            // the rows can be on any month so reload the data again
+                            *all_data = TransactionData::new(conn, cu_month_index, cu_year);
+                            *home_table = TableData::new(all_data.get_txs());
+                            *import_data = None;
+                            *cu_page = CurrentUi::Home;
+                            format!("Imported {total_added} transactions. {total_skipped} rows were skipped")
+                        }
+                        Err(e) => format!("Nothing was imported. Error: {e}"),
+                    };
+                    *cu_popup = PopupState::Import(message);
+                }
+            }
+            KeyCode::Up if !table.items.is_empty() => table.previous(),
+            KeyCode::Down if !table.items.is_empty() => table.next(),
+            _ => {}
+        },
+        _ => *cu_popup = PopupState::Nothing,
+    }
+    Ok("0".to_string())
+}
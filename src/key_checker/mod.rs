@@ -1,11 +1,19 @@
 mod add_tx_keys;
+mod budget_keys;
 mod chart_keys;
 mod home_keys;
+mod import_keys;
 mod initial_keys;
+mod recurring_keys;
 mod transfer_keys;
+mod trash_keys;
 
 pub use add_tx_keys::add_tx_keys;
+pub use budget_keys::budget_keys;
 pub use chart_keys::chart_keys;
 pub use home_keys::home_keys;
+pub use import_keys::import_keys;
 pub use initial_keys::initial_keys;
+pub use recurring_keys::recurring_keys;
 pub use transfer_keys::transfer_keys;
+pub use trash_keys::trash_keys;
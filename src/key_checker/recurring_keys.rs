@@ -0,0 +1,43 @@
+use crate::home_page::{CurrentUi, PopupState, TableData};
+use crate::recurring_page::RecurringData;
+use crossterm::event::{KeyCode, KeyEvent};
+use rusqlite::Connection;
+use std::error::Error;
+
+/// Tracks the keys once interacting with the Recurring page. The table items are reloaded
+/// from the database every time the page is drawn.
+pub fn recurring_keys(
+    key: KeyEvent,
+    cu_page: &mut CurrentUi,
+    cu_popup: &mut PopupState,
+    table: &mut TableData,
+    conn: &Connection,
+) -> Result<String, Box<dyn Error>> {
+    match cu_popup {
+        PopupState::Nothing => match key.code {
+            KeyCode::Char('q') => return Ok("".to_string()),
+            KeyCode::Char('f') => {
+                *cu_page = CurrentUi::Home;
+                table.state.select(None);
+            }
+            KeyCode::Char('h') => *cu_popup = PopupState::Helper,
+            KeyCode::Char('a') => return Ok("Recurring".to_string()),
+            KeyCode::Char('p') => {
+                if let Some(a) = table.state.selected() {
+                    RecurringData::new(conn).toggle_pause(a, conn)?;
+                }
+            }
+            KeyCode::Char('d') => {
+                if let Some(a) = table.state.selected() {
+                    RecurringData::new(conn).delete(a, conn)?;
+                    table.state.select(None);
+                }
+            }
+            KeyCode::Up if !table.items.is_empty() => table.previous(),
+            KeyCode::Down if !table.items.is_empty() => table.next(),
+            _ => {}
+        },
+        _ => *cu_popup = PopupState::Nothing,
+    }
+    Ok("0".to_string())
+}
@@ -6,6 +6,7 @@ use crossterm::event::{KeyCode, KeyEvent};
 use rusqlite::Connection;
 use std::error::Error;
 
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_keys(
     key: KeyEvent,
     cu_page: &mut CurrentUi,
@@ -16,7 +17,7 @@ pub fn transfer_keys(
     table: &mut TableData,
     selected_tab: &mut SelectedTab,
     cu_month_index: usize,
-    cu_year_index: usize,
+    cu_year: usize,
     conn: &Connection,
 ) -> Result<String, Box<dyn Error>> {
     match cu_popup {
@@ -34,12 +35,12 @@ pub fn transfer_keys(
                     }
                     KeyCode::Char('h') => *cu_popup = PopupState::Helper,
                     KeyCode::Char('s') => {
-                        let status = data_for_transfer.add_tx();
+                        let status = data_for_transfer.add_tx(conn);
                         if status == *"" {
                             // reload home page and switch UI
                             *selected_tab = SelectedTab::Months;
                             *data_for_transfer = TransferData::new();
-                            *all_data = TransactionData::new(conn, cu_month_index, cu_year_index);
+                            *all_data = TransactionData::new(conn, cu_month_index, cu_year);
                             *table = TableData::new(all_data.get_txs());
                             *cu_page = CurrentUi::Home;
                         } else {
@@ -51,6 +52,7 @@ pub fn transfer_keys(
                     KeyCode::Char('3') => *cu_tx_page = TransferTab::From,
                     KeyCode::Char('4') => *cu_tx_page = TransferTab::To,
                     KeyCode::Char('5') => *cu_tx_page = TransferTab::Amount,
+                    KeyCode::Char('6') => *cu_tx_page = TransferTab::Tags,
                     KeyCode::Enter => *cu_tx_page = TransferTab::Nothing,
                     KeyCode::Esc => *cu_tx_page = TransferTab::Nothing,
                     _ => {}
@@ -172,7 +174,7 @@ pub fn transfer_keys(
                                 data_for_transfer.add_tx_status(&a);
                                 if a.contains("zero") {
                                 } else {
-                                    *cu_tx_page = TransferTab::Nothing;
+                                    *cu_tx_page = TransferTab::Tags;
                                 }
                             }
                             Err(_) => {
@@ -199,6 +201,24 @@ pub fn transfer_keys(
                     KeyCode::Char(a) => data_for_transfer.edit_amount(a, false),
                     _ => {}
                 },
+
+                TransferTab::Tags => match key.code {
+                    KeyCode::Enter | KeyCode::Esc => {
+                        let status = data_for_transfer.check_tags();
+
+                        match status {
+                            Ok(a) => {
+                                data_for_transfer.add_tx_status(&a);
+                                *cu_tx_page = TransferTab::Nothing
+                            }
+                            Err(_) => data_for_transfer
+                                .add_tx_status("Tags: Error acquired while checking."),
+                        }
+                    }
+                    KeyCode::Backspace => data_for_transfer.edit_tags('a', true),
+                    KeyCode::Char(a) => data_for_transfer.edit_tags(a, false),
+                    _ => {}
+                },
             }
         }
         _ => *cu_popup = PopupState::Nothing,
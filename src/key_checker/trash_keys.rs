@@ -0,0 +1,61 @@
+use crate::home_page::{CurrentUi, PopupState, TableData, TransactionData};
+use crate::trash_page::TrashData;
+use crossterm::event::{KeyCode, KeyEvent};
+use rusqlite::Connection;
+use std::error::Error;
+
+/// Tracks the keys once interacting with the Trash page. The table items are reloaded
+/// from the database every time the page is drawn.
+#[allow(clippy::too_many_arguments)]
+pub fn trash_keys(
+    key: KeyEvent,
+    cu_page: &mut CurrentUi,
+    cu_popup: &mut PopupState,
+    table: &mut TableData,
+    all_data: &mut TransactionData,
+    home_table: &mut TableData,
+    cu_month_index: usize,
+    cu_year: usize,
+    conn: &Connection,
+) -> Result<String, Box<dyn Error>> {
+    match cu_popup {
+        PopupState::Nothing => match key.code {
+            KeyCode::Char('q') => return Ok("".to_string()),
+            KeyCode::Char('f') => {
+                *cu_page = CurrentUi::Home;
+                table.state.select(None);
+            }
+            KeyCode::Char('h') => *cu_popup = PopupState::Helper,
+            KeyCode::Char('r') => {
+                // a transaction cannot be restored if one of its methods was deleted
+                if let Some(a) = table.state.selected() {
+                    match TrashData::new(conn).restore(a, conn) {
+                        Ok(_) => {
+                            // the restored transaction can be on the selected month so reload the data again
+                            *all_data = TransactionData::new(conn, cu_month_index, cu_year);
+                            *home_table = TableData::new(all_data.get_txs());
+                            table.state.select(None);
+                        }
+                        Err(e) => *cu_popup = PopupState::RestoreFailed(e.to_string()),
+                    }
+                }
+            }
+            // purging cannot be undone so it is only done after confirming
+            KeyCode::Char('d') if table.state.selected().is_some() => {
+                *cu_popup = PopupState::PurgeConfirm
+            }
+            KeyCode::Up if !table.items.is_empty() => table.previous(),
+            KeyCode::Down if !table.items.is_empty() => table.next(),
+            _ => {}
+        },
+        PopupState::PurgeConfirm => {
+            if let (KeyCode::Char('y'), Some(a)) = (key.code, table.state.selected()) {
+                TrashData::new(conn).purge(a, conn)?;
+                table.state.select(None);
+            }
+            *cu_popup = PopupState::Nothing;
+        }
+        _ => *cu_popup = PopupState::Nothing,
+    }
+    Ok("0".to_string())
+}
@@ -1,34 +1,76 @@
+mod budget_page;
 mod chart_page;
+pub mod config;
 pub mod db;
 pub mod home_page;
+mod import_page;
 mod initial_page;
 mod interface;
 mod key_checker;
 mod popup_page;
+mod recurring_page;
 mod transfer_page;
+mod trash_page;
 pub mod tx_page;
 use atty::Stream;
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use db::{add_new_tx_methods, create_db, get_user_tx_methods};
+use chrono::prelude::Local;
+use config::{
+    get_all_ledgers, get_command, get_ledger_path, load_config, resolve_db_path,
+    verify_ledger_name, CliCommand, DbConfig,
+};
+use db::{
+    add_due_recurring_txs, add_new_categories, add_new_tx_methods, add_recurring_tx, add_split_tx,
+    archive_tx_method, backup_db, change_passphrase, create_db, decrypt_db, delete_budget,
+    delete_category, delete_tx_method, encrypt_db, export_csv, export_journal, export_qif,
+    get_all_backups, get_backup_dir, get_user_budget_data, get_user_category_data,
+    get_user_import_data, get_user_ledger, get_user_manage_data, get_user_passphrase,
+    get_user_recurring_data, get_user_rename_data, get_user_split_data, get_user_tx_methods,
+    is_db_encrypted, is_qif_file, migrate_db, open_db, parse_date_range, purge_old_trash,
+    rebuild_balances, remove_old_backups, rename_category, rename_tx_method, restore_backup,
+    restore_tx_method, save_import_profile, set_budget, unlock_db, verify_db, ImportSource,
+    JournalFormat, StatementImport,
+};
 use home_page::TimeData;
+use import_page::ImportData;
 use initial_page::check_version;
 use interface::run_app;
+use rusqlite::Connection;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::{error::Error, io, process, thread, time::Duration};
 use tui::{backend::CrosstermBackend, Terminal};
 
-/// The starting function checks for the local database location and creates a new database
-/// if not existing. Also checks if the user is trying to open the app via a terminal or the binary.
+/// The starting function finds out which database to open from the given CLI arguments, the environment
+/// variables and the config file and creates a new database if not existing.
+/// Also checks if the user is trying to open the app via a terminal or the binary.
 /// If trying to open using the binary, tries open the relevant terminal to execute the app.
 /// Lastly, starts a loop that keeps the interface running until exit command is given.
-pub fn initializer(is_windows: bool, verifying_path: &str) -> Result<(), Box<dyn Error>> {
+pub fn initializer(is_windows: bool, args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (command, flags) = match get_command(args) {
+        Ok(command) => command,
+        Err(e) => {
+            println!("{e}");
+            process::exit(1);
+        }
+    };
+
+    // commands run without checking the version or opening the interface
+    if let Some(command) = command {
+        if let Err(e) = run_command(&command, flags) {
+            println!("{e}");
+            process::exit(1);
+        }
+        return Ok(());
+    }
+
     let version_status = check_version();
     let mut new_version_available = false;
 
@@ -108,32 +150,88 @@ pub fn initializer(is_windows: bool, verifying_path: &str) -> Result<(), Box<dyn
         }
         return Ok(());
     }
-    // checks the local folder and searches for data.sqlite
-    let paths = fs::read_dir(".")?;
-    let mut db_found = false;
-    for path in paths {
-        let path = path?.path().display().to_string();
-        if path == verifying_path {
-            db_found = true;
-        }
-    }
-    // create a new db if not found. If there is an error, delete the failed data.sqlite file and exit
+
+    let (db_path, config) = get_db_path(flags)?;
+    let path = db_path.to_string_lossy().to_string();
+
+    // the connection is opened once and shared by the whole app
+    let db_found = db_path.exists();
+    let conn = open_db(&path)?;
+
+    // an encrypted database is unlocked before anything else is read. A new database is
+    // encrypted right away if it was asked in the config
+    let passphrase = if db_found {
+        unlock_with_user_passphrase(&conn, &db_path)?
+    } else if config.get_encrypt() {
+        let passphrase = get_user_passphrase(
+            &format!("Enter a passphrase to encrypt the new database at {path}:"),
+            true,
+        );
+        unlock_db(&conn, &passphrase)?;
+        Some(passphrase)
+    } else {
+        None
+    };
+
+    // create a new db if not found. If there is an error, delete the failed database file and exit
     if !db_found {
-        let db_tx_methods = get_user_tx_methods(false);
-        println!("Creating New Database. It may take some time...");
-        let status = create_db("data.sqlite", db_tx_methods);
+        let db_tx_methods = get_user_tx_methods(false, &conn);
+        println!("Creating New Database at {path}. It may take some time...");
+        let status = create_db(&conn, db_tx_methods);
         match status {
             Ok(_) => {}
             Err(e) => {
                 println!("Database creation failed. Try again. Error: {}", e);
-                fs::remove_file("data.sqlite")?;
+                drop(conn);
+                fs::remove_file(&path)?;
                 process::exit(1);
             }
         }
     }
+
+    // every session can change the data so a copy of the existing database is kept before anything else
+    if db_found {
+        if let Err(e) = take_backup(&conn, &db_path, passphrase.as_deref(), config.get_backups()) {
+            println!("Error while backing up the database. Error: {e}");
+            thread::sleep(Duration::from_millis(5000));
+        }
+    }
+
+    // bring older databases to the current schema. Databases from a newer version of the app are not opened
+    if let Err(e) = migrate_db(&conn) {
+        println!("Failed to open the database. Error: {}", e);
+        process::exit(1);
+    }
+
+    // add the recurring transactions that came due since the last time the app was opened
+    match add_due_recurring_txs(&conn, Local::today().naive_local()) {
+        Ok((_, skipped)) if !skipped.is_empty() => {
+            for reason in skipped {
+                println!("{reason}");
+            }
+            thread::sleep(Duration::from_millis(5000));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            println!("Error while adding the recurring transactions. Error: {e}");
+            thread::sleep(Duration::from_millis(5000));
+        }
+    }
+
+    // the transactions that stayed in the trash for longer than the configured days are removed for good
+    if let Err(e) = purge_old_trash(&conn, config.get_trash_days(), Local::today().naive_local()) {
+        println!("Error while emptying the trash. Error: {e}");
+        thread::sleep(Duration::from_millis(5000));
+    }
+    // the rows of a statement file waiting to be previewed on the next start of the interface
+    let mut import_data = None;
     loop {
         // Continue to loop to the main interface until the ending command or "break" is given
-        let status = check_app(start_interface(new_version_available));
+        let status = check_app(
+            start_interface(new_version_available, import_data.take(), &conn),
+            &conn,
+            &mut import_data,
+        );
         // turn it false here so if the interface restarts, it doesn't open the popup again.
         new_version_available = false;
         if &status == "break" {
@@ -143,9 +241,306 @@ pub fn initializer(is_windows: bool, verifying_path: &str) -> Result<(), Box<dyn
     Ok(())
 }
 
+/// Finds out which database file to open from the CLI flags, the environment variables and the config file.
+/// The directory of the database is created if it does not exist. The loaded config is returned along with the path.
+fn get_db_path(flags: &[String]) -> Result<(PathBuf, DbConfig), Box<dyn Error>> {
+    // the CLI arguments take priority over the environment variables and the config file
+    let config = match load_config(flags) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{e}");
+            process::exit(1);
+        }
+    };
+
+    if let Some(ledger) = config.ledger.as_ref().filter(|a| !verify_ledger_name(a)) {
+        println!("Ledger {ledger} is not valid. Only letters, numbers, - and _ can be used");
+        process::exit(1);
+    }
+
+    // older versions kept data.sqlite in the current directory so it is still opened if found.
+    // The user picks a ledger if there are more than one and nothing was selected
+    let db_path = match resolve_db_path(&config, Some(Path::new("data.sqlite"))) {
+        Some(db_path) => db_path,
+        None => {
+            let data_dir = config.get_data_dir();
+            let ledger = get_user_ledger(&get_all_ledgers(&data_dir));
+            get_ledger_path(&data_dir, &ledger)
+        }
+    };
+
+    if let Some(parent) = db_path.parent().filter(|a| !a.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    Ok((db_path, config))
+}
+
+/// Runs a command given in the CLI on the selected database and prints the result
+fn run_command(command: &CliCommand, flags: &[String]) -> Result<(), Box<dyn Error>> {
+    let (db_path, config) = get_db_path(flags)?;
+    if !db_path.exists() {
+        return Err(format!("No database was found at {}", db_path.display()).into());
+    }
+
+    // encrypting and decrypting replace the database file so they work without an open connection
+    match command.name.as_str() {
+        "encrypt" => {
+            let passphrase = get_user_passphrase(
+                &format!("Enter a passphrase to encrypt {}:", db_path.display()),
+                true,
+            );
+            encrypt_db(&db_path, &passphrase)?;
+            println!("{} is now encrypted", db_path.display());
+            println!(
+                "The backups taken before are not encrypted. They can be removed from {}",
+                get_backup_dir(&db_path).display()
+            );
+            return Ok(());
+        }
+        "decrypt" => {
+            let passphrase = get_user_passphrase(
+                &format!("Enter the passphrase of {}:", db_path.display()),
+                false,
+            );
+            decrypt_db(&db_path, &passphrase)?;
+            println!("{} is no longer encrypted", db_path.display());
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let mut conn = open_db(&db_path.to_string_lossy())?;
+    let passphrase = unlock_with_user_passphrase(&conn, &db_path)?;
+
+    // verify only reads the database and reports an outdated schema. Every other command works with the
+    // current schema so older databases are migrated first
+    if command.name != "verify" {
+        migrate_db(&conn)?;
+    }
+
+    match command.name.as_str() {
+        "rebuild-balances" => {
+            take_backup(&conn, &db_path, passphrase.as_deref(), config.get_backups())?;
+            print_rebuild_result(rebuild_balances(&conn));
+        }
+        "restore" => restore_command(
+            command,
+            &mut conn,
+            &db_path,
+            passphrase.as_deref(),
+            config.get_backups(),
+        )?,
+        "change-passphrase" => {
+            if passphrase.is_none() {
+                return Err(format!(
+                    "{} is not encrypted. Use the encrypt command to add a passphrase",
+                    db_path.display()
+                )
+                .into());
+            }
+            let new_passphrase = get_user_passphrase("Enter the new passphrase:", true);
+            change_passphrase(&conn, &new_passphrase)?;
+            println!("The passphrase of {} was changed", db_path.display());
+            println!("The backups taken before can only be opened with the old passphrase");
+        }
+        "verify" => {
+            let problems = verify_db(&conn)?;
+            if !problems.is_empty() {
+                println!(
+                    "Found {} problems in {}:\n",
+                    problems.len(),
+                    db_path.display()
+                );
+                for problem in &problems {
+                    println!("{problem}");
+                }
+                println!();
+                return Err("The database did not pass the verification".into());
+            }
+            println!("No problems were found in {}", db_path.display());
+        }
+        "export" => {
+            let range = command
+                .args
+                .first()
+                .ok_or("A year, a month or a range of dates to export is required")?;
+            let (start, end) = parse_date_range(range)?;
+            let file_name = match command.args.get(1) {
+                Some(file_name) => file_name.to_string(),
+                None => format!("rex-{}.csv", range.replace("..", "_to_")),
+            };
+
+            let file_path = Path::new(&file_name);
+            let total_txs = if is_qif_file(file_path) {
+                export_qif(&conn, file_path, start, end)?
+            } else {
+                export_csv(&conn, file_path, start, end)?
+            };
+            println!("Exported {total_txs} transactions from {start} to {end} to {file_name}");
+        }
+        "export-journal" => {
+            let format = command
+                .args
+                .first()
+                .ok_or("A journal format is required. Values: ledger/hledger/beancount")?;
+            let format = JournalFormat::from_name(format)?;
+            let range = command
+                .args
+                .get(1)
+                .ok_or("A year, a month or a range of dates to export is required")?;
+            let (start, end) = parse_date_range(range)?;
+            let file_name = match command.args.get(2) {
+                Some(file_name) => file_name.to_string(),
+                None => format!(
+                    "rex-{}.{}",
+                    range.replace("..", "_to_"),
+                    format.get_extension()
+                ),
+            };
+
+            let total_txs = export_journal(&conn, Path::new(&file_name), start, end, format)?;
+            println!("Exported {total_txs} transactions from {start} to {end} to {file_name}");
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Asks for the passphrase of the database and unlocks the connection with it if the database is
+/// encrypted. Returns the passphrase or None if the database is not encrypted.
+fn unlock_with_user_passphrase(
+    conn: &Connection,
+    db_path: &Path,
+) -> Result<Option<String>, Box<dyn Error>> {
+    if !is_db_encrypted(db_path) {
+        return Ok(None);
+    }
+
+    for _ in 0..3 {
+        let passphrase = get_user_passphrase(
+            &format!("Enter the passphrase of {}:", db_path.display()),
+            false,
+        );
+        if unlock_db(conn, &passphrase).is_ok() {
+            return Ok(Some(passphrase));
+        }
+        println!("Wrong passphrase\n");
+    }
+    Err(format!("Could not unlock {}", db_path.display()).into())
+}
+
+/// Takes a backup of the database and removes the oldest backups past the given number to keep.
+/// Nothing is done if no backup should be kept.
+fn take_backup(
+    conn: &Connection,
+    db_path: &Path,
+    passphrase: Option<&str>,
+    keep: usize,
+) -> Result<(), Box<dyn Error>> {
+    if keep == 0 {
+        return Ok(());
+    }
+    backup_db(conn, db_path, passphrase, Local::now().naive_local())?;
+    remove_old_backups(db_path, keep)?;
+    Ok(())
+}
+
+/// Lists the backups of the database with their number or restores the backup with the number given
+/// as the command value. The current state is backed up first so the restore can be reverted.
+fn restore_command(
+    command: &CliCommand,
+    conn: &mut Connection,
+    db_path: &Path,
+    passphrase: Option<&str>,
+    keep: usize,
+) -> Result<(), Box<dyn Error>> {
+    let all_backups = get_all_backups(db_path, passphrase)?;
+    if all_backups.is_empty() {
+        println!(
+            "No backups were found in {}",
+            get_backup_dir(db_path).display()
+        );
+        return Ok(());
+    }
+
+    let number = match command.args.first() {
+        Some(number) => number,
+        None => {
+            println!("Backups of {}:\n", db_path.display());
+            for (index, backup) in all_backups.iter().enumerate() {
+                // a backup taken before the passphrase was changed cannot be opened
+                let total_txs = match backup.total_txs {
+                    Some(total_txs) => format!("{total_txs} transactions"),
+                    None => "could not be opened".to_string(),
+                };
+                println!(
+                    "{}. {}  {}  {}",
+                    index + 1,
+                    backup.created_at,
+                    total_txs,
+                    backup.path.display()
+                );
+            }
+            println!("\nUse restore <number> to restore one of them");
+            return Ok(());
+        }
+    };
+
+    let backup = number
+        .parse::<usize>()
+        .ok()
+        .and_then(|a| a.checked_sub(1))
+        .and_then(|a| all_backups.get(a))
+        .ok_or(format!("There is no backup with the number {number}"))?;
+
+    // the old backups are only removed after restoring so the selected one is never removed first
+    let current_backup = match keep {
+        0 => None,
+        _ => Some(backup_db(
+            conn,
+            db_path,
+            passphrase,
+            Local::now().naive_local(),
+        )?),
+    };
+    restore_backup(conn, &backup.path, passphrase)?;
+
+    if let Some(current_backup) = current_backup {
+        remove_old_backups(db_path, keep)?;
+        println!(
+            "The database before restoring was saved at {}",
+            current_backup.display()
+        );
+    }
+
+    println!("Restored the backup from {}", backup.created_at);
+    Ok(())
+}
+
+/// Prints the differences that were fixed by rebuilding the balances
+fn print_rebuild_result(result: Result<Vec<String>, Box<dyn Error>>) {
+    match result {
+        Ok(differences) if differences.is_empty() => {
+            println!("All balances are correct. Nothing was changed")
+        }
+        Ok(differences) => {
+            println!("Fixed {} differences:\n", differences.len());
+            for difference in differences {
+                println!("{difference}");
+            }
+        }
+        Err(e) => println!("Error while rebuilding the balances. Error: {e}"),
+    }
+}
+
 /// The function to start run_app along with executing commands for switching to an alternate screen,
-/// mouse capturing and passing months and year data to the function and starts the interface
-fn start_interface(new_version_available: bool) -> Result<String, Box<dyn Error>> {
+/// mouse capturing and passing months data to the function and starts the interface.
+/// The interface opens on the Import page if the rows of a statement file are given.
+fn start_interface(
+    new_version_available: bool,
+    import_data: Option<ImportData>,
+    conn: &Connection,
+) -> Result<String, Box<dyn Error>> {
     // TUI magic functions starts here with multiple calls
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -167,10 +562,15 @@ fn start_interface(new_version_available: bool) -> Result<String, Box<dyn Error>
         "November",
         "December",
     ]);
-    let years = TimeData::new(vec!["2022", "2023", "2024", "2025"]);
 
     // pass a few data to the main function and loop forever or until quit/faced with an error
-    let res = run_app(&mut terminal, months, years, new_version_available)?;
+    let res = run_app(
+        &mut terminal,
+        months,
+        new_version_available,
+        import_data,
+        conn,
+    )?;
 
     Ok(res)
 }
@@ -189,7 +589,12 @@ fn exit_tui_interface() -> Result<(), Box<dyn Error>> {
 
 /// The function is used to check the output which caused the tui interface to end. This
 /// is used for quitting the app or do something outside of the main tui interface.
-fn check_app(res: Result<String, Box<dyn Error>>) -> String {
+/// The rows of a statement file to import are put in import_data to be previewed on the next start.
+fn check_app(
+    res: Result<String, Box<dyn Error>>,
+    conn: &Connection,
+    import_data: &mut Option<ImportData>,
+) -> String {
     exit_tui_interface().expect("Error exiting the interface");
 
     match res {
@@ -199,12 +604,12 @@ fn check_app(res: Result<String, Box<dyn Error>>) -> String {
         Ok(a) => {
             // the string is gotten from run_app to process the data here.
             if &a == "Change" {
-                let db_data = get_user_tx_methods(true);
+                let db_data = get_user_tx_methods(true, conn);
                 if db_data == vec!["".to_string()] {
                     println!("Operation Cancelled. Restarting in 5 seconds");
                     thread::sleep(Duration::from_millis(5000));
                 } else {
-                    let status = add_new_tx_methods("data.sqlite", db_data);
+                    let status = add_new_tx_methods(conn, db_data);
                     match status {
                         Ok(_) => {
                             println!("Added Transaction Methods Successfully. The app will restart in 5 seconds");
@@ -216,6 +621,200 @@ fn check_app(res: Result<String, Box<dyn Error>>) -> String {
                         }
                     }
                 }
+            } else if &a == "Rename" {
+                let rename_data = get_user_rename_data(conn);
+                if rename_data == vec!["".to_string()] {
+                    println!("Operation Cancelled. Restarting in 5 seconds");
+                    thread::sleep(Duration::from_millis(5000));
+                } else {
+                    let status = rename_tx_method(conn, &rename_data[0], &rename_data[1]);
+                    match status {
+                        Ok(_) => {
+                            println!("Renamed Transaction Method Successfully. The app will restart in 5 seconds");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                        Err(e) => {
+                            println!("Error while renaming the transaction method. Error: {e:?}");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                    }
+                }
+            } else if &a == "Manage" {
+                let manage_data = get_user_manage_data(conn);
+                if manage_data == vec!["".to_string()] {
+                    println!("Operation Cancelled. Restarting in 5 seconds");
+                    thread::sleep(Duration::from_millis(5000));
+                } else {
+                    let tx_method = &manage_data[1];
+                    let (status, done_text) = match manage_data[0].as_str() {
+                        "Archive" => (archive_tx_method(conn, tx_method), "Archived"),
+                        "Restore" => (restore_tx_method(conn, tx_method), "Restored"),
+                        _ => (delete_tx_method(conn, tx_method), "Deleted"),
+                    };
+                    match status {
+                        Ok(_) => {
+                            println!("{done_text} {tx_method} Successfully. The app will restart in 5 seconds");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                        Err(e) => {
+                            println!("Error while managing the transaction method. Error: {e}");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                    }
+                }
+            } else if &a == "Category" {
+                let category_data = get_user_category_data(conn);
+                if category_data == vec!["".to_string()] {
+                    println!("Operation Cancelled. Restarting in 5 seconds");
+                    thread::sleep(Duration::from_millis(5000));
+                } else {
+                    let category = &category_data[1];
+                    let (status, done_text) = match category_data[0].as_str() {
+                        "Add" => (
+                            add_new_categories(conn, vec![category.to_string()]),
+                            "Added",
+                        ),
+                        "Rename" => (
+                            rename_category(conn, category, &category_data[2]),
+                            "Renamed",
+                        ),
+                        _ => (delete_category(conn, category), "Deleted"),
+                    };
+                    match status {
+                        Ok(_) => {
+                            println!("{done_text} {category} Successfully. The app will restart in 5 seconds");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                        Err(e) => {
+                            println!("Error while managing the category. Error: {e}");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                    }
+                }
+            } else if &a == "Recurring" {
+                let recurring_data = get_user_recurring_data(conn);
+                if recurring_data == vec!["".to_string()] {
+                    println!("Operation Cancelled. Restarting in 5 seconds");
+                    thread::sleep(Duration::from_millis(5000));
+                } else {
+                    let end_date = match recurring_data[8].as_str() {
+                        "" => None,
+                        date => Some(date),
+                    };
+                    let status = add_recurring_tx(
+                        conn,
+                        &recurring_data[0],
+                        &recurring_data[1],
+                        &recurring_data[2],
+                        &recurring_data[3],
+                        &recurring_data[4],
+                        &recurring_data[5],
+                        &recurring_data[6],
+                        &recurring_data[7],
+                        end_date,
+                    )
+                    .and_then(|_| {
+                        add_due_recurring_txs(conn, Local::today().naive_local())
+                    });
+                    match status {
+                        Ok(_) => {
+                            println!("Added Recurring Transaction Successfully. The app will restart in 5 seconds");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                        Err(e) => {
+                            println!("Error while adding the recurring transaction. Error: {e}");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                    }
+                }
+            } else if &a == "Budget" {
+                let budget_data = get_user_budget_data(conn);
+                if budget_data == vec!["".to_string()] {
+                    println!("Operation Cancelled. Restarting in 5 seconds");
+                    thread::sleep(Duration::from_millis(5000));
+                } else {
+                    let target = &budget_data[2];
+                    // a new budget starts from the current month
+                    let (status, done_text) = match budget_data[0].as_str() {
+                        "Set" => (
+                            set_budget(
+                                conn,
+                                &budget_data[1],
+                                target,
+                                &budget_data[3],
+                                budget_data[4] == "Yes",
+                                &Local::today().format("%Y-%m").to_string(),
+                            ),
+                            "Set the budget of",
+                        ),
+                        _ => (
+                            delete_budget(conn, &budget_data[1], target),
+                            "Deleted the budget of",
+                        ),
+                    };
+                    match status {
+                        Ok(_) => {
+                            println!("{done_text} {target} Successfully. The app will restart in 5 seconds");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                        Err(e) => {
+                            println!("Error while managing the budget. Error: {e}");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                    }
+                }
+            } else if &a == "Split" {
+                let split_data = get_user_split_data(conn);
+                if split_data == vec!["".to_string()] {
+                    println!("Operation Cancelled. Restarting in 5 seconds");
+                    thread::sleep(Duration::from_millis(5000));
+                } else {
+                    let legs = split_data[4..]
+                        .chunks(3)
+                        .map(|leg| leg.to_vec())
+                        .collect::<Vec<Vec<String>>>();
+                    let status = add_split_tx(
+                        conn,
+                        &split_data[0],
+                        &split_data[1],
+                        &split_data[2],
+                        &legs,
+                        &split_data[3],
+                        None,
+                    );
+                    match status {
+                        Ok(_) => {
+                            println!("Added Split Transaction Successfully. The app will restart in 5 seconds");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                        Err(e) => {
+                            println!("Error while adding the split transaction. Error: {e}");
+                            thread::sleep(Duration::from_millis(5000));
+                        }
+                    }
+                }
+            } else if &a == "Import" {
+                match get_user_import_data(conn) {
+                    None => {
+                        println!("Operation Cancelled. Restarting in 5 seconds");
+                        thread::sleep(Duration::from_millis(5000));
+                    }
+                    Some((path, source)) => {
+                        match read_statement(conn, &path, &source) {
+                            // nothing is added until the rows are confirmed on the Import page
+                            Ok(statement) => *import_data = Some(ImportData::new(&path, statement)),
+                            Err(e) => {
+                                println!("Error while reading the statement file. Error: {e}");
+                                thread::sleep(Duration::from_millis(5000));
+                            }
+                        }
+                    }
+                }
+            } else if &a == "Rebuild" {
+                println!("Rebuilding the balances. It may take some time...\n");
+                print_rebuild_result(rebuild_balances(conn));
+                println!("\nPress Enter to go back to the app");
+                io::stdin().read_line(&mut String::new()).unwrap();
             } else if &a == "Link" {
                 println!(
                     "Could not open the link to the latest version.\n\nLink: https://github.com/WaffleMixer/Rex/releases/latest"
@@ -228,3 +827,25 @@ fn check_app(res: Result<String, Box<dyn Error>>) -> String {
     }
     "".to_string()
 }
+
+/// Reads the statement file and checks every row of it. The mapping of a CSV file is saved
+/// if it was given a name.
+fn read_statement(
+    conn: &Connection,
+    path: &str,
+    source: &ImportSource,
+) -> Result<StatementImport, Box<dyn Error>> {
+    let text = fs::read_to_string(path)?;
+    match source {
+        ImportSource::Csv(profile) => {
+            if !profile.name.is_empty() {
+                save_import_profile(conn, profile)?;
+            }
+            StatementImport::new(conn, &text, profile)
+        }
+        ImportSource::Ofx(tx_method) => StatementImport::from_ofx(conn, &text, tx_method),
+        ImportSource::Qif(tx_method, date_format) => {
+            StatementImport::from_qif(conn, &text, tx_method, date_format)
+        }
+    }
+}
@@ -1,10 +1,10 @@
 fn main() {
     let mut is_windows = false;
-    let mut verifying_path = "./data.sqlite";
     // change details if running on windows
     if cfg!(target_os = "windows") {
         is_windows = true;
-        verifying_path = r#".\data.sqlite"#;
     }
-    rex::initializer(is_windows, verifying_path).unwrap();
+    // the arguments after the binary name decide which database is opened
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    rex::initializer(is_windows, &args).unwrap();
 }
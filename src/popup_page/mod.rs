@@ -1,5 +1,5 @@
 mod popup_data;
 mod popup_ui;
 
-pub use popup_data::add_popup;
+pub use popup_data::{add_confirm_popup, add_message_popup, add_popup};
 pub use popup_ui::create_popup;
@@ -19,10 +19,24 @@ pub fn add_popup<B: Backend>(f: &mut Frame<B>, popup_num: usize) {
 'A' : Add Transaction Page
 'T' : Add Transfer Page
 'R' : Balance Chart (Follows your selected year)
+'P' : Recurring Transactions Page (Home Page)
+'B' : Monthly Budgets Page (Home Page)
 'F' : Home Page
-'D' : Delete selected Transaction (Home Page)
+'W' : Trash Page with the deleted Transactions (Home Page)
+'D' : Move selected Transaction to the Trash (Home Page)
 'J' : Add new Transaction Methods (Home Page)
+'N' : Rename a Transaction Method (Home Page)
+'V' : Archive, Restore or Delete a Transaction Method (Home Page)
+'G' : Add, Rename or Delete a Category (Home Page)
+'X' : Show only the transactions with the next tag (Home Page)
 'E' : Edit Selected Transaction (Home Page)
+'L' : Add a Split Transaction with multiple legs (Home Page)
+'Enter' : Show or hide the legs of a Split Transaction (Home Page)
+'C' : Rebuild all balances from the transactions (Home Page)
+'O' : Export the selected month to a CSV file (Home Page)
+'I' : Import a bank CSV, OFX, QFX or QIF file with a preview (Home Page)
+'U' : Undo the last change to the transactions or methods (Home Page)
+'Y' : Redo the last undone change (Home Page)
 'H' : Open Hotkey Help
 'Q' : Quit
 
@@ -38,7 +52,7 @@ Press Any Key to dismiss"
                 .to_string(),
         );
         data.push("50".to_string());
-        data.push("65".to_string());
+        data.push("84".to_string());
     } else {
         data.push("Delete Error".to_string());
         data.push("Error while deleting the transaction\n\nPress Any Key to dismiss".to_string());
@@ -47,3 +61,26 @@ Press Any Key to dismiss"
     }
     create_popup(f, &data);
 }
+
+/// Asks to confirm an action that cannot be undone. Only 'Y' confirms it.
+pub fn add_confirm_popup<B: Backend>(f: &mut Frame<B>, title: &str, message: &str) {
+    let data = vec![
+        title.to_string(),
+        format!("{message}\n\n'Y' : Confirm\nPress Any Other Key to cancel"),
+        "50".to_string(),
+        "25".to_string(),
+    ];
+    create_popup(f, &data);
+}
+
+/// Shows a message with the given title that is dismissed with any key. Used for the results of
+/// undoing, exporting, importing and restoring along with their errors.
+pub fn add_message_popup<B: Backend>(f: &mut Frame<B>, title: &str, message: &str) {
+    let data = vec![
+        title.to_string(),
+        format!("{message}\n\nPress Any Key to dismiss"),
+        "50".to_string(),
+        "25".to_string(),
+    ];
+    create_popup(f, &data);
+}
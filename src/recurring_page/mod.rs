@@ -0,0 +1,5 @@
+mod recurring_data;
+mod recurring_ui;
+
+pub use recurring_data::RecurringData;
+pub use recurring_ui::recurring_ui;
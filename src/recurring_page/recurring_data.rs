@@ -0,0 +1,43 @@
+use crate::db::{
+    delete_recurring_tx, get_all_recurring_txs, pause_recurring_tx, resume_recurring_tx,
+};
+use chrono::prelude::Local;
+use rusqlite::{Connection, Result as sqlResult};
+
+/// Stores the recurring transaction templates that are shown on the Recurring page along with their id
+///
+/// all_txs : `[[details, tx_method, amount, tx_type, rule, start_date, end_date, next_date, status],]`
+///
+/// all_id : `[1, 2, 3]` required to pause or delete a template
+pub struct RecurringData {
+    pub all_txs: Vec<Vec<String>>,
+    all_id: Vec<i32>,
+}
+
+impl RecurringData {
+    /// Calls the db to fetch all the recurring transaction templates
+    pub fn new(conn: &Connection) -> Self {
+        let (all_txs, all_id) = get_all_recurring_txs(conn);
+        RecurringData { all_txs, all_id }
+    }
+
+    /// Returns all the templates inside a vector for the table
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.all_txs.clone()
+    }
+
+    /// Pauses the template of the given index or resumes it if it was already paused
+    pub fn toggle_pause(&self, index: usize, conn: &Connection) -> sqlResult<()> {
+        let target_id = self.all_id[index];
+        if self.all_txs[index][8] == "Paused" {
+            resume_recurring_tx(conn, target_id, Local::today().naive_local())
+        } else {
+            pause_recurring_tx(conn, target_id)
+        }
+    }
+
+    /// Deletes the template of the given index
+    pub fn delete(&self, index: usize, conn: &Connection) -> sqlResult<()> {
+        delete_recurring_tx(conn, self.all_id[index])
+    }
+}
@@ -0,0 +1,107 @@
+use crate::home_page::TableData;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+/// The UI function that draws the Recurring page. Shows all the recurring transaction templates
+/// in a table where a template can be selected to pause or delete it.
+pub fn recurring_ui<B: Backend>(f: &mut Frame<B>, table: &mut TableData) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Length(9), Constraint::Min(0)].as_ref())
+        .split(size);
+
+    let block = Block::default().style(
+        Style::default()
+            .bg(Color::Rgb(255, 255, 255))
+            .fg(Color::Rgb(50, 205, 50)),
+    );
+    f.render_widget(block, size);
+
+    let help_text = vec![
+        Spans::from("Recurring transactions are added on startup every time they come due."),
+        Spans::from("'Arrow Up/Down' : Select a recurring transaction"),
+        Spans::from("'A' : Add a new recurring transaction"),
+        Spans::from("'P' : Pause or resume the selected recurring transaction"),
+        Spans::from("'D' : Delete the selected recurring transaction"),
+        Spans::from("'F' : Home Page"),
+        Spans::from("Deleting keeps the transactions that were already added"),
+    ];
+
+    let help_sec = Paragraph::new(help_text)
+        .style(
+            Style::default()
+                .bg(Color::Rgb(255, 255, 255))
+                .fg(Color::Rgb(50, 205, 50)),
+        )
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(
+            "Help",
+            Style::default().add_modifier(Modifier::BOLD),
+        )))
+        .alignment(Alignment::Left);
+
+    let header_cells = [
+        "Details",
+        "TX Method",
+        "Amount",
+        "Type",
+        "Rule",
+        "Start",
+        "End",
+        "Next",
+        "Status",
+    ]
+    .iter()
+    .map(|h| Cell::from(*h).style(Style::default().fg(Color::Rgb(255, 255, 255))));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::LightBlue))
+        .height(1)
+        .bottom_margin(0);
+
+    // paused templates are shown in gray
+    let rows = table.items.iter().map(|item| {
+        let cells = item.iter().map(|c| Cell::from(c.to_string()));
+        let mut row = Row::new(cells).height(1).bottom_margin(0);
+        if item[8] == "Paused" {
+            row = row.style(Style::default().fg(Color::DarkGray));
+        }
+        row
+    });
+
+    let table_area = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Recurring Transactions"),
+        )
+        .widths(&[
+            Constraint::Percentage(20),
+            Constraint::Percentage(15),
+            Constraint::Percentage(10),
+            Constraint::Percentage(8),
+            Constraint::Percentage(8),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(10),
+            Constraint::Percentage(9),
+        ])
+        .highlight_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::REVERSED),
+        )
+        .highlight_symbol(">> ");
+
+    f.render_widget(help_sec, chunks[0]);
+    f.render_stateful_widget(table_area, chunks[1], &mut table.state)
+}
@@ -1,5 +1,5 @@
 use crate::db::StatusChecker;
-use crate::db::{add_new_tx, delete_tx};
+use crate::db::{add_new_tx, update_tx};
 use chrono::prelude::Local;
 use rusqlite::Connection;
 use std::error::Error;
@@ -19,6 +19,7 @@ pub struct TransferData {
     to: String,
     amount: String,
     tx_type: String,
+    tags: String,
     pub tx_status: Vec<String>,
     editing_tx: bool,
     id_num: i32,
@@ -39,6 +40,7 @@ impl TransferData {
             to: "".to_string(),
             amount: "".to_string(),
             tx_type: "Transfer".to_string(),
+            tags: "".to_string(),
             tx_status: Vec::new(),
             editing_tx: false,
             id_num: 0,
@@ -53,6 +55,7 @@ impl TransferData {
         from: &str,
         to: &str,
         amount: &str,
+        tags: &str,
         id_num: i32,
     ) -> Self {
         let splitted = date.split('-');
@@ -70,6 +73,7 @@ impl TransferData {
             to: to.to_string(),
             amount: amount.to_string(),
             tx_type: "Transfer".to_string(),
+            tags: tags.to_string(),
             tx_status: Vec::new(),
             editing_tx: true,
             id_num,
@@ -86,6 +90,7 @@ impl TransferData {
             &self.to,
             &self.amount,
             &self.tx_type,
+            &self.tags,
         ]
     }
 
@@ -157,9 +162,22 @@ impl TransferData {
         }
     }
 
+    /// Used to add a new character to the tags value that is being inputted by the
+    /// user following each key press. Takes a bool value to represent backspace pressing.
+    pub fn edit_tags(&mut self, text: char, pop_last: bool) {
+        match pop_last {
+            true => {
+                if !self.tags.is_empty() {
+                    self.tags.pop().unwrap();
+                }
+            }
+            false => self.tags = format!("{}{text}", self.tags),
+        }
+    }
+
     /// Collects all the data, verifies that all fields are complete for the transaction and calls the function
     /// that pushes them to the database.
-    pub fn add_tx(&mut self) -> String {
+    pub fn add_tx(&mut self, conn: &Connection) -> String {
         // Checks that none of the ui fields are not empty
         if self.date.is_empty() {
             return "Date: Date cannot be empty".to_string();
@@ -180,41 +198,34 @@ impl TransferData {
         let tx_method = format!("{} to {}", self.from, self.to);
 
         if self.editing_tx {
-            // if we are editing a tx delete the selected transaction so we can create it again
-            // with the new details
+            // rewrite the selected transaction with the new details
             self.editing_tx = false;
-            let status = delete_tx(self.id_num as usize, "data.sqlite");
-            match status {
-                Ok(_) => {}
-                Err(e) => {
-                    return format!(
-                        "Edit Transfer: Something went wrong while editing transaction {}",
-                        e
-                    )
-                }
-            }
-            let status_add = add_new_tx(
+            let status = update_tx(
+                conn,
+                self.id_num as usize,
                 &self.date,
                 &self.details,
                 &tx_method,
                 &self.amount,
                 &self.tx_type,
-                "data.sqlite",
-                Some(&self.id_num.to_string()),
+                "",
+                &self.tags,
             );
 
-            match status_add {
+            match status {
                 Ok(_) => String::new(),
                 Err(e) => format!("Edit Transfer: Something went wrong {}", e),
             }
         } else {
             let status = add_new_tx(
+                conn,
                 &self.date,
                 &self.details,
                 &tx_method,
                 &self.amount,
                 &self.tx_type,
-                "data.sqlite",
+                "",
+                &self.tags,
                 None,
             );
             match status {
@@ -287,4 +298,14 @@ impl TransferData {
         self.amount = user_amount;
         Ok(status)
     }
+
+    /// Checks the inputted Tags by the user upon pressing Enter/Esc for various error.
+    pub fn check_tags(&mut self) -> Result<String, Box<dyn Error>> {
+        let mut tags = self.tags.clone();
+
+        let status = self.verify_tags(&mut tags)?;
+
+        self.tags = tags;
+        Ok(status)
+    }
 }
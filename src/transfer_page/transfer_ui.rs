@@ -14,10 +14,9 @@ use tui::{
 ///
 /// - input_data : Contains all the data for all field that has been inserted by the user so far for the transaction
 ///
-/// Example input_data : `["2020-10-10", "", "", "", "Expense"]`
+/// Example input_data : `["2020-10-10", "", "", "", "100.00", "Transfer", "work"]`
 /// - cu_selected : For verifying the current selected widget to add a block box
 /// - status_data : Contains all the String to push into the Status widget
-
 pub fn transfer_ui<B: Backend>(
     f: &mut Frame<B>,
     input_data: Vec<&str>,
@@ -32,7 +31,7 @@ pub fn transfer_ui<B: Backend>(
         .margin(2)
         .constraints(
             [
-                Constraint::Length(12),
+                Constraint::Length(13),
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Length(3),
@@ -86,6 +85,7 @@ pub fn transfer_ui<B: Backend>(
     Spans::from("'3' : From Method  Example: Cash, Bank, Card"),
     Spans::from("'4' : To Method    Example: Cash, Bank, Card"),
     Spans::from("'5' : Amount       Example: 1000, 100+50"),
+    Spans::from("'6' : Tags         Example: work, trip-2026. Optional"),
     Spans::from("'S' : Save the inputted data as a Transaction"),
     Spans::from("'Enter' : Submit field and continue"),
     Spans::from("'Esc' : Stop editing filed"),
@@ -122,6 +122,8 @@ pub fn transfer_ui<B: Backend>(
 
     let amount_text = vec![Spans::from(input_data[4])];
 
+    let tags_text = vec![Spans::from(input_data[6])];
+
     let arrow_text = vec![Spans::from(""), Spans::from("➞ ➞ ➞")];
 
     let create_block = |title| {
@@ -201,6 +203,15 @@ pub fn transfer_ui<B: Backend>(
         .block(create_block("Amount"))
         .alignment(Alignment::Center);
 
+    let tags_sec = Paragraph::new(tags_text)
+        .style(
+            Style::default()
+                .bg(Color::Rgb(255, 255, 255))
+                .fg(Color::Rgb(50, 205, 50)),
+        )
+        .block(create_block("Tags"))
+        .alignment(Alignment::Left);
+
     let details_sec = Paragraph::new(details_text)
         .style(
             Style::default()
@@ -231,6 +242,10 @@ pub fn transfer_ui<B: Backend>(
         ),
         // The text of this goes into the middle so couldn't find a better place to insert the input box
         TransferTab::Amount => f.set_cursor(third_chunk[1].x + 1, third_chunk[1].y + 1),
+        TransferTab::Tags => f.set_cursor(
+            third_chunk[2].x + input_data[6].len() as u16 + 1,
+            third_chunk[2].y + 1,
+        ),
         TransferTab::Nothing => {}
     }
 
@@ -243,4 +258,5 @@ pub fn transfer_ui<B: Backend>(
     f.render_widget(to_sec, second_chunk[2]);
     f.render_widget(arrow_sec, second_chunk[1]);
     f.render_widget(amount_sec, third_chunk[1]);
+    f.render_widget(tags_sec, third_chunk[2]);
 }
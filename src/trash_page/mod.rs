@@ -0,0 +1,5 @@
+mod trash_data;
+mod trash_ui;
+
+pub use trash_data::TrashData;
+pub use trash_ui::trash_ui;
@@ -0,0 +1,36 @@
+use crate::db::{get_trash_txs, purge_tx, restore_tx};
+use rusqlite::{Connection, Result as sqlResult};
+use std::error::Error;
+
+/// Stores the deleted transactions that are shown on the Trash page along with their id_num
+///
+/// all_txs : `[[deleted_at, date, details, tx_method, amount, tx_type],]`
+///
+/// all_id : `[1, 2, 3]` required to restore or purge a transaction
+pub struct TrashData {
+    pub all_txs: Vec<Vec<String>>,
+    all_id: Vec<i32>,
+}
+
+impl TrashData {
+    /// Calls the db to fetch all the transactions in the trash
+    pub fn new(conn: &Connection) -> Self {
+        let (all_txs, all_id) = get_trash_txs(conn);
+        TrashData { all_txs, all_id }
+    }
+
+    /// Returns all the deleted transactions inside a vector for the table
+    pub fn get_txs(&self) -> Vec<Vec<String>> {
+        self.all_txs.clone()
+    }
+
+    /// Restores the transaction of the given index on its original date
+    pub fn restore(&self, index: usize, conn: &Connection) -> Result<(), Box<dyn Error>> {
+        restore_tx(conn, self.all_id[index])
+    }
+
+    /// Removes the transaction of the given index for good
+    pub fn purge(&self, index: usize, conn: &Connection) -> sqlResult<()> {
+        purge_tx(conn, self.all_id[index])
+    }
+}
@@ -0,0 +1,90 @@
+use crate::home_page::TableData;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+/// The UI function that draws the Trash page. Shows all the deleted transactions with their
+/// original dates in a table where a transaction can be selected to restore or purge it.
+pub fn trash_ui<B: Backend>(f: &mut Frame<B>, table: &mut TableData) {
+    let size = f.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([Constraint::Length(8), Constraint::Min(0)].as_ref())
+        .split(size);
+
+    let block = Block::default().style(
+        Style::default()
+            .bg(Color::Rgb(255, 255, 255))
+            .fg(Color::Rgb(50, 205, 50)),
+    );
+    f.render_widget(block, size);
+
+    let help_text = vec![
+        Spans::from(
+            "Deleted transactions do not count in any balance while they are in the trash.",
+        ),
+        Spans::from("'Arrow Up/Down' : Select a deleted transaction"),
+        Spans::from("'R' : Restore the selected transaction on its original date"),
+        Spans::from("'D' : Delete the selected transaction for good after confirming"),
+        Spans::from("'F' : Home Page"),
+        Spans::from("Old transactions are removed on startup based on the trash_days config"),
+    ];
+
+    let help_sec = Paragraph::new(help_text)
+        .style(
+            Style::default()
+                .bg(Color::Rgb(255, 255, 255))
+                .fg(Color::Rgb(50, 205, 50)),
+        )
+        .block(Block::default().borders(Borders::ALL).title(Span::styled(
+            "Help",
+            Style::default().add_modifier(Modifier::BOLD),
+        )))
+        .alignment(Alignment::Left);
+
+    let header_cells = ["Deleted", "Date", "Details", "TX Method", "Amount", "Type"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Rgb(255, 255, 255))));
+
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::LightBlue))
+        .height(1)
+        .bottom_margin(0);
+
+    let rows = table.items.iter().map(|item| {
+        let cells = item.iter().map(|c| Cell::from(c.to_string()));
+        Row::new(cells).height(1).bottom_margin(0)
+    });
+
+    let table_area = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Deleted Transactions"),
+        )
+        .widths(&[
+            Constraint::Percentage(12),
+            Constraint::Percentage(12),
+            Constraint::Percentage(34),
+            Constraint::Percentage(18),
+            Constraint::Percentage(12),
+            Constraint::Percentage(10),
+        ])
+        .highlight_style(
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::REVERSED),
+        )
+        .highlight_symbol(">> ");
+
+    f.render_widget(help_sec, chunks[0]);
+    f.render_stateful_widget(table_area, chunks[1], &mut table.state)
+}
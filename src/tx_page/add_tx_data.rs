@@ -1,5 +1,5 @@
 use crate::db::StatusChecker;
-use crate::db::{add_new_tx, delete_tx};
+use crate::db::{add_new_tx, update_tx};
 use chrono::prelude::Local;
 use rusqlite::Connection;
 use std::error::Error;
@@ -18,6 +18,8 @@ pub struct AddTxData {
     tx_method: String,
     amount: String,
     tx_type: String,
+    category: String,
+    tags: String,
     pub tx_status: Vec<String>,
     editing_tx: bool,
     id_num: i32,
@@ -25,6 +27,12 @@ pub struct AddTxData {
 
 impl StatusChecker for AddTxData {}
 
+impl Default for AddTxData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AddTxData {
     /// Creates an instance of the struct however the date field is
     /// edited with the current local date of the device.
@@ -37,6 +45,8 @@ impl AddTxData {
             tx_method: "".to_string(),
             amount: "".to_string(),
             tx_type: "".to_string(),
+            category: "".to_string(),
+            tags: "".to_string(),
             tx_status: Vec::new(),
             editing_tx: false,
             id_num: 0,
@@ -45,12 +55,15 @@ impl AddTxData {
 
     /// Used to adding custom pre-defined data inside the widgets of Add Transaction Page.
     /// Currently used on Editing transaction.
+    #[allow(clippy::too_many_arguments)]
     pub fn custom(
         date: &str,
         details: &str,
         tx_method: &str,
         amount: &str,
         tx_type: &str,
+        category: &str,
+        tags: &str,
         id_num: i32,
     ) -> Self {
         let splitted = date.split('-');
@@ -67,6 +80,8 @@ impl AddTxData {
             tx_method: tx_method.to_string(),
             amount: amount.to_string(),
             tx_type: tx_type.to_string(),
+            category: category.to_string(),
+            tags: tags.to_string(),
             tx_status: Vec::new(),
             editing_tx: true,
             id_num,
@@ -82,6 +97,8 @@ impl AddTxData {
             &self.tx_method,
             &self.amount,
             &self.tx_type,
+            &self.category,
+            &self.tags,
         ]
     }
 
@@ -153,9 +170,35 @@ impl AddTxData {
         }
     }
 
+    /// Used to add a new character to the category value that is being inputted by the
+    /// user following each key press. Takes a bool value to represent backspace pressing.
+    pub fn edit_category(&mut self, text: char, pop_last: bool) {
+        match pop_last {
+            true => {
+                if !self.category.is_empty() {
+                    self.category.pop().unwrap();
+                }
+            }
+            false => self.category = format!("{}{text}", self.category),
+        }
+    }
+
+    /// Used to add a new character to the tags value that is being inputted by the
+    /// user following each key press. Takes a bool value to represent backspace pressing.
+    pub fn edit_tags(&mut self, text: char, pop_last: bool) {
+        match pop_last {
+            true => {
+                if !self.tags.is_empty() {
+                    self.tags.pop().unwrap();
+                }
+            }
+            false => self.tags = format!("{}{text}", self.tags),
+        }
+    }
+
     /// Collects all the data for the transaction and calls the function
     /// that pushes them to the database.
-    pub fn add_tx(&mut self) -> String {
+    pub fn add_tx(&mut self, conn: &Connection) -> String {
         if self.date.is_empty() {
             return "Date: Date cannot be empty".to_string();
         } else if self.details.is_empty() {
@@ -170,39 +213,32 @@ impl AddTxData {
 
         if self.editing_tx {
             self.editing_tx = false;
-            let status = delete_tx(self.id_num as usize, "data.sqlite");
-            match status {
-                Ok(_) => {}
-                Err(e) => {
-                    return format!(
-                        "Edit Transaction: Something went wrong while editing transaction {}",
-                        e
-                    )
-                }
-            }
-
-            let status_add = add_new_tx(
+            let status = update_tx(
+                conn,
+                self.id_num as usize,
                 &self.date,
                 &self.details,
                 &self.tx_method,
                 &self.amount,
                 &self.tx_type,
-                "data.sqlite",
-                Some(&self.id_num.to_string()),
+                &self.category,
+                &self.tags,
             );
 
-            match status_add {
+            match status {
                 Ok(_) => String::new(),
                 Err(e) => format!("Edit Transaction: Something went wrong {}", e),
             }
         } else {
             let status = add_new_tx(
+                conn,
                 &self.date,
                 &self.details,
                 &self.tx_method,
                 &self.amount,
                 &self.tx_type,
-                "data.sqlite",
+                &self.category,
+                &self.tags,
                 None,
             );
             match status {
@@ -261,4 +297,24 @@ impl AddTxData {
         self.tx_type = tx_type;
         Ok(status)
     }
+
+    /// Checks the inputted Category by the user upon pressing Enter/Esc for various error.
+    pub fn check_category(&mut self, conn: &Connection) -> Result<String, Box<dyn Error>> {
+        let mut cu_category = self.category.clone();
+
+        let status = self.verify_category(&mut cu_category, conn)?;
+
+        self.category = cu_category;
+        Ok(status)
+    }
+
+    /// Checks the inputted Tags by the user upon pressing Enter/Esc for various error.
+    pub fn check_tags(&mut self) -> Result<String, Box<dyn Error>> {
+        let mut tags = self.tags.clone();
+
+        let status = self.verify_tags(&mut tags)?;
+
+        self.tags = tags;
+        Ok(status)
+    }
 }
@@ -14,10 +14,9 @@ use tui::{
 ///
 /// - input_data : Contains all the data for all field that has been inserted by the user so far for the transaction
 ///
-/// Example input_data : `["2020-10-10", "", "", "", "Expense"]`
+/// Example input_data : `["2020-10-10", "", "", "", "Expense", "Food", "work, trip"]`
 /// - cu_selected : For verifying the current selected widget to add a block box
 /// - status_data : Contains all the String to push into the Status widget
-
 pub fn tx_ui<B: Backend>(
     f: &mut Frame<B>,
     input_data: Vec<&str>,
@@ -32,7 +31,7 @@ pub fn tx_ui<B: Backend>(
         .margin(2)
         .constraints(
             [
-                Constraint::Length(12),
+                Constraint::Length(14),
                 Constraint::Length(3),
                 Constraint::Length(3),
                 Constraint::Percentage(25),
@@ -56,6 +55,19 @@ pub fn tx_ui<B: Backend>(
         )
         .split(chunks[1]);
 
+    // chunk[2] holds the Details, the Category and the Tags widgets
+    let details_chunk = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage(50),
+                Constraint::Percentage(20),
+                Constraint::Percentage(30),
+            ]
+            .as_ref(),
+        )
+        .split(chunks[2]);
+
     let block = Block::default().style(
         Style::default()
             .bg(Color::Rgb(255, 255, 255))
@@ -71,6 +83,8 @@ pub fn tx_ui<B: Backend>(
         Spans::from("'3' : TX Method    Example: Cash, Bank, Card"),
         Spans::from("'4' : Amount       Example: 1000, 100+50"),
         Spans::from("'5' : TX Type      Example: Income/Expense/I/E"),
+        Spans::from("'6' : Category     Example: Food, Rent. Optional"),
+        Spans::from("'7' : Tags         Example: work, trip-2026. Optional"),
         Spans::from("'S' : Save the inputted data as a Transaction"),
         Spans::from("'Enter' : Submit field and continue"),
         Spans::from("'Esc' : Stop editing filed"),
@@ -106,6 +120,10 @@ pub fn tx_ui<B: Backend>(
 
     let tx_type_text = vec![Spans::from(input_data[4])];
 
+    let category_text = vec![Spans::from(input_data[5])];
+
+    let tags_text = vec![Spans::from(input_data[6])];
+
     let create_block = |title| {
         Block::default()
             .borders(Borders::ALL)
@@ -184,6 +202,24 @@ pub fn tx_ui<B: Backend>(
         .block(create_block("Details"))
         .alignment(Alignment::Left);
 
+    let category_sec = Paragraph::new(category_text.clone())
+        .style(
+            Style::default()
+                .bg(Color::Rgb(255, 255, 255))
+                .fg(Color::Rgb(50, 205, 50)),
+        )
+        .block(create_block("Category"))
+        .alignment(Alignment::Left);
+
+    let tags_sec = Paragraph::new(tags_text.clone())
+        .style(
+            Style::default()
+                .bg(Color::Rgb(255, 255, 255))
+                .fg(Color::Rgb(50, 205, 50)),
+        )
+        .block(create_block("Tags"))
+        .alignment(Alignment::Left);
+
     // We will be adding a cursor/box based on which tab is selected.
     // This was created utilizing the tui-rs example named user_input.rs
     match cu_selected {
@@ -192,8 +228,8 @@ pub fn tx_ui<B: Backend>(
             another_chunk[0].y + 1,
         ),
         TxTab::Details => f.set_cursor(
-            chunks[2].x + input_data[1].len() as u16 + 1,
-            chunks[2].y + 1,
+            details_chunk[0].x + input_data[1].len() as u16 + 1,
+            details_chunk[0].y + 1,
         ),
         TxTab::TxMethod => f.set_cursor(
             another_chunk[1].x + input_data[2].len() as u16 + 1,
@@ -207,11 +243,21 @@ pub fn tx_ui<B: Backend>(
             another_chunk[3].x + input_data[4].len() as u16 + 1,
             another_chunk[3].y + 1,
         ),
+        TxTab::Category => f.set_cursor(
+            details_chunk[1].x + input_data[5].len() as u16 + 1,
+            details_chunk[1].y + 1,
+        ),
+        TxTab::Tags => f.set_cursor(
+            details_chunk[2].x + input_data[6].len() as u16 + 1,
+            details_chunk[2].y + 1,
+        ),
         TxTab::Nothing => {}
     }
 
     // render the previously generated data into an interface
-    f.render_widget(details_sec, chunks[2]);
+    f.render_widget(details_sec, details_chunk[0]);
+    f.render_widget(category_sec, details_chunk[1]);
+    f.render_widget(tags_sec, details_chunk[2]);
     f.render_widget(status_sec, chunks[3]);
     f.render_widget(help_sec, chunks[0]);
     f.render_widget(date_sec, another_chunk[0]);
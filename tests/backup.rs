@@ -0,0 +1,88 @@
+extern crate rex;
+use chrono::{Duration, NaiveDate};
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+
+fn create_test_db(file_name: &Path) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+fn add_test_tx(conn: &Connection, details: &str) {
+    add_new_tx(
+        conn,
+        "2022-07-19",
+        details,
+        "test1",
+        "10.00",
+        "Expense",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn check_backup_rotation() {
+    let data_dir = Path::new("backup_rotation");
+    let db_path = data_dir.join("personal.sqlite");
+    fs::create_dir_all(data_dir).unwrap();
+    let mut conn = create_test_db(&db_path);
+
+    let start_time = NaiveDate::from_ymd(2022, 7, 19).and_hms(10, 30, 0);
+    let no_backups = get_all_backups(&db_path, None).unwrap();
+
+    for index in 0..4 {
+        add_test_tx(&conn, "Testing transaction");
+        backup_db(&conn, &db_path, None, start_time + Duration::days(index)).unwrap();
+    }
+    // a file that is not a backup of the database is never touched
+    fs::write(
+        get_backup_dir(&db_path).join("business-20220719-103000.sqlite"),
+        "",
+    )
+    .unwrap();
+
+    let total_removed = remove_old_backups(&db_path, 3).unwrap();
+    let all_backups = get_all_backups(&db_path, None).unwrap();
+
+    // the oldest backup that is left had 2 transactions
+    add_test_tx(&conn, "Testing transaction");
+    restore_backup(&mut conn, &all_backups[2].path, None).unwrap();
+    let restored_txs = get_all_txs(&conn, 6, 2022).0.len();
+    let restore_missing =
+        restore_backup(&mut conn, &data_dir.join("missing.sqlite"), None).is_err();
+    let other_file = get_backup_dir(&db_path)
+        .join("business-20220719-103000.sqlite")
+        .exists();
+
+    conn.close().unwrap();
+    fs::remove_dir_all(data_dir).unwrap();
+
+    assert!(no_backups.is_empty());
+    assert_eq!(total_removed, 1);
+    assert_eq!(
+        all_backups
+            .iter()
+            .map(|a| (a.created_at, a.total_txs))
+            .collect::<Vec<_>>(),
+        vec![
+            (start_time + Duration::days(3), Some(4)),
+            (start_time + Duration::days(2), Some(3)),
+            (start_time + Duration::days(1), Some(2)),
+        ]
+    );
+    assert_eq!(
+        all_backups[0].path,
+        data_dir
+            .join("backups")
+            .join("personal-20220722-103000.sqlite")
+    );
+    assert_eq!(restored_txs, 2);
+    assert!(restore_missing);
+    assert!(other_file);
+}
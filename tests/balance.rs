@@ -6,8 +6,9 @@ use std::collections::HashMap;
 use std::fs;
 
 fn create_test_db(file_name: &str) -> Connection {
-    create_db(file_name, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
-    Connection::open(file_name).unwrap()
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
 }
 
 #[test]
@@ -16,7 +17,7 @@ fn check_last_balances_1() {
     let conn = create_test_db(file_name);
     let tx_methods = get_all_tx_methods(&conn);
     let data = get_last_balances(&conn, &tx_methods);
-    let expected_data = vec!["0.00".to_string(), "0.00".to_string()];
+    let expected_data = vec![0, 0];
     conn.close().unwrap();
 
     fs::remove_file(file_name).unwrap();
@@ -31,34 +32,38 @@ fn check_last_balances_2() {
     let tx_methods = get_all_tx_methods(&conn);
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test1",
         "159.00",
         "Expense",
-        file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test 2",
         "159.19",
         "Income",
-        file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     let data = get_last_balances(&conn, &tx_methods);
-    let expected_data = vec!["-159.00".to_string(), "159.19".to_string()];
+    let expected_data = vec![-15900, 15919];
 
-    delete_tx(1, file_name).unwrap();
+    delete_tx(&conn, 1).unwrap();
 
     let data_2 = get_last_balances(&conn, &tx_methods);
-    let expected_data_2 = vec!["0.00".to_string(), "159.19".to_string()];
+    let expected_data_2 = vec![0, 15919];
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -74,34 +79,38 @@ fn check_last_balances_3() {
     let tx_methods = get_all_tx_methods(&conn);
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test1 to test 2",
         "159.00",
         "Transfer",
-        file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test 2 to test1",
         "159.00",
         "Transfer",
-        file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     let data = get_last_balances(&conn, &tx_methods);
-    let expected_data = vec!["0.00".to_string(), "0.00".to_string()];
+    let expected_data = vec![0, 0];
 
-    delete_tx(1, file_name).unwrap();
+    delete_tx(&conn, 1).unwrap();
 
     let data_2 = get_last_balances(&conn, &tx_methods);
-    let expected_data_2 = vec!["159.00".to_string(), "-159.00".to_string()];
+    let expected_data_2 = vec![15900, -15900];
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -116,8 +125,8 @@ fn check_last_month_balance_1() {
     let conn = create_test_db(&file_name);
     let tx_methods = get_all_tx_methods(&conn);
 
-    let data = get_last_time_balance(&conn, 6, 1, &tx_methods);
-    let expected_data = HashMap::from([("test1".to_string(), 0.0), ("test 2".to_string(), 0.0)]);
+    let data = get_last_time_balance(&conn, 6, 2023, &tx_methods);
+    let expected_data = HashMap::from([("test1".to_string(), 0), ("test 2".to_string(), 0)]);
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -131,7 +140,7 @@ fn check_last_balance_id() {
     let conn = create_test_db(&file_name);
 
     let data = get_last_balance_id(&conn);
-    let expected_data: sqlResult<i32> = Ok(49);
+    let expected_data: sqlResult<i32> = Ok(0);
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -146,70 +155,79 @@ fn check_last_month_balance_2() {
     let tx_methods = get_all_tx_methods(&conn);
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test1",
         "100.00",
         "Income",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test 2",
         "100.00",
         "Income",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-08-19",
         "Testing transaction",
         "test1",
         "100.00",
         "Income",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-09-19",
         "Testing transaction",
         "test1",
         "100.00",
         "Income",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-10-19",
         "Testing transaction",
         "test1",
         "100.00",
         "Income",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
-    let data_1 = get_last_time_balance(&conn, 8, 0, &tx_methods);
+    let data_1 = get_last_time_balance(&conn, 8, 2022, &tx_methods);
     let expected_data_1 =
-        HashMap::from([("test 2".to_string(), 100.0), ("test1".to_string(), 200.0)]);
+        HashMap::from([("test 2".to_string(), 10000), ("test1".to_string(), 20000)]);
 
-    delete_tx(1, &file_name).unwrap();
-    delete_tx(2, &file_name).unwrap();
+    delete_tx(&conn, 1).unwrap();
+    delete_tx(&conn, 2).unwrap();
 
-    let data_2 = get_last_time_balance(&conn, 10, 3, &tx_methods);
-    let expected_data_2 =
-        HashMap::from([("test 2".to_string(), 0.0), ("test1".to_string(), 300.0)]);
+    let data_2 = get_last_time_balance(&conn, 10, 2025, &tx_methods);
+    let expected_data_2 = HashMap::from([("test 2".to_string(), 0), ("test1".to_string(), 30000)]);
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -228,7 +246,7 @@ fn check_balance_all_day() {
     let mut current_date = NaiveDate::parse_from_str("2022-01-01", "%Y-%m-%d").unwrap();
     let ending_date = NaiveDate::parse_from_str("2025-12-31", "%Y-%m-%d").unwrap();
 
-    let total_days: f64 = (ending_date - current_date).num_days() as f64 + 1.0;
+    let total_days = (ending_date - current_date).num_days() + 1;
 
     let details = "Test Transaction";
     let amount = "1.0";
@@ -240,12 +258,14 @@ fn check_balance_all_day() {
             break;
         }
         add_new_tx(
+            &conn,
             &current_date.to_string(),
             details,
             tx_method,
             amount,
             tx_type,
-            &file_name,
+            "",
+            "",
             None,
         )
         .unwrap();
@@ -253,7 +273,7 @@ fn check_balance_all_day() {
     }
 
     let data = get_last_balances(&conn, &tx_methods);
-    let expected = vec![format!("{total_days:.2}"), "0.00".to_string()];
+    let expected = vec![total_days * 100, 0];
     assert_eq!(data, expected);
 
     let mut delete_id_num = total_days as usize;
@@ -262,23 +282,133 @@ fn check_balance_all_day() {
         if delete_id_num == 0 {
             break;
         }
-        delete_tx(delete_id_num, &file_name).unwrap();
+        delete_tx(&conn, delete_id_num).unwrap();
         delete_id_num -= 1;
     }
 
     let data_1 = get_last_balances(&conn, &tx_methods);
-    let data_2 = get_last_time_balance(&conn, 12, 3, &tx_methods);
+    let data_2 = get_last_time_balance(&conn, 12, 2025, &tx_methods);
 
-    let expected_data_1 = vec!["0.00".to_string(), "0.00".to_string()];
+    let expected_data_1 = vec![0, 0];
     let mut expected_data_2 = HashMap::new();
-    for (i, _x) in &data_2 {
-        expected_data_2.insert(i.to_string(), 0.0);
+    for i in data_2.keys() {
+        expected_data_2.insert(i.to_string(), 0);
     }
-    
+
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
-    
 
     assert_eq!(data_1, expected_data_1);
     assert_eq!(data_2, expected_data_2);
 }
+
+#[test]
+fn check_balance_any_year() {
+    let file_name = "balance_any_year.sqlite".to_string();
+    let conn = create_test_db(&file_name);
+    let tx_methods = get_all_tx_methods(&conn);
+
+    add_new_tx(
+        &conn,
+        "2019-03-10",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    add_new_tx(
+        &conn,
+        "2031-11-05",
+        "Testing transaction",
+        "test1",
+        "50.00",
+        "Expense",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    let all_years = get_all_years(&conn);
+    let data_1 = get_last_time_balance(&conn, 0, 2031, &tx_methods);
+    let data_2 = get_last_time_balance(&conn, 11, 2031, &tx_methods);
+    let data_3 = get_last_balances(&conn, &tx_methods);
+
+    delete_tx(&conn, 1).unwrap();
+
+    let data_4 = get_last_time_balance(&conn, 11, 2031, &tx_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(all_years.contains(&"2019".to_string()));
+    assert!(all_years.contains(&"2031".to_string()));
+    assert_eq!(all_years.first().unwrap(), "2019");
+    assert_eq!(all_years.last().unwrap(), "2031");
+
+    assert_eq!(
+        data_1,
+        HashMap::from([("test1".to_string(), 10000), ("test 2".to_string(), 0)])
+    );
+    assert_eq!(
+        data_2,
+        HashMap::from([("test1".to_string(), 5000), ("test 2".to_string(), 0)])
+    );
+    assert_eq!(data_3, vec![5000, 0]);
+    assert_eq!(
+        data_4,
+        HashMap::from([("test1".to_string(), -5000), ("test 2".to_string(), 0)])
+    );
+}
+
+#[test]
+fn check_zero_month_balance() {
+    let file_name = "zero_month_balance.sqlite".to_string();
+    let conn = create_test_db(&file_name);
+    let tx_methods = get_all_tx_methods(&conn);
+
+    let add_tx = |date: &str, amount: &str, tx_type: &str| {
+        add_new_tx(
+            &conn,
+            date,
+            "Testing transaction",
+            "test1",
+            amount,
+            tx_type,
+            "",
+            "",
+            None,
+        )
+        .unwrap();
+    };
+
+    // the balance of February adds up to exactly 0 but is still carried to the later months
+    add_tx("2022-01-10", "100.00", "Income");
+    add_tx("2022-02-10", "100.00", "Expense");
+    add_tx("2022-04-10", "5.00", "Income");
+    add_tx("2022-01-20", "10.00", "Income");
+
+    let data_1 = get_last_time_balance(&conn, 2, 2022, &tx_methods);
+    let data_2 = get_last_time_balance(&conn, 4, 2022, &tx_methods);
+    let data_3 = get_last_balances(&conn, &tx_methods);
+    let differences = rebuild_balances(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        data_1,
+        HashMap::from([("test1".to_string(), 1000), ("test 2".to_string(), 0)])
+    );
+    assert_eq!(
+        data_2,
+        HashMap::from([("test1".to_string(), 1500), ("test 2".to_string(), 0)])
+    );
+    assert_eq!(data_3, vec![1500, 0]);
+    assert!(differences.is_empty());
+}
@@ -0,0 +1,156 @@
+extern crate rex;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+fn add_tx(
+    conn: &Connection,
+    date: &str,
+    tx_method: &str,
+    amount: &str,
+    tx_type: &str,
+    category: &str,
+) {
+    add_new_tx(
+        conn,
+        date,
+        "Testing transaction",
+        tx_method,
+        amount,
+        tx_type,
+        category,
+        "",
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn check_category_budgets() {
+    let file_name = "category_budgets.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_categories(&conn, vec!["Food".to_string(), "Fun".to_string()]).unwrap();
+    set_budget(&conn, "Category", "Food", "300", false, "2022-08").unwrap();
+    set_budget(&conn, "Category", "Fun", "50", false, "2022-08").unwrap();
+
+    add_tx(&conn, "2022-08-01", "test1", "200", "Expense", "Food");
+    add_tx(&conn, "2022-08-15", "test 2", "120.50", "Expense", "Food");
+    // income and other months are not counted as spent
+    add_tx(&conn, "2022-08-16", "test1", "500", "Income", "Food");
+    add_tx(&conn, "2022-09-01", "test1", "10", "Expense", "Food");
+
+    let data_1 = get_budgets(&conn, 7, 2022);
+    // budgets are not shown before their start month
+    let data_2 = get_budgets(&conn, 6, 2022);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        data_1,
+        vec![
+            vec!["Category", "Food", "300.00", "320.50", "-20.50", "No"],
+            vec!["Category", "Fun", "50.00", "0.00", "50.00", "No"]
+        ]
+    );
+    assert!(data_2.is_empty());
+}
+
+#[test]
+fn check_budget_carry_over() {
+    let file_name = "budget_carry_over.sqlite";
+    let conn = create_test_db(file_name);
+
+    set_budget(&conn, "Method", "test1", "100", true, "2022-06").unwrap();
+    set_budget(&conn, "Method", "test 2", "100", false, "2022-06").unwrap();
+
+    add_tx(&conn, "2022-06-10", "test1", "40", "Expense", "");
+    add_tx(&conn, "2022-06-10", "test 2", "40", "Expense", "");
+    add_tx(&conn, "2022-07-10", "test1", "100", "Expense", "");
+    add_tx(&conn, "2022-08-10", "test1", "200", "Expense", "");
+    // transfers are not counted as spent
+    add_new_tx(
+        &conn,
+        "2022-08-11",
+        "Testing transfer",
+        "test1 to test 2",
+        "50",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    let data_1 = get_budgets(&conn, 6, 2022);
+    let data_2 = get_budgets(&conn, 7, 2022);
+    // overspending does not take away from the next month
+    let data_3 = get_budgets(&conn, 8, 2022);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        data_1,
+        vec![
+            vec!["Method", "test1", "160.00", "100.00", "60.00", "Yes"],
+            vec!["Method", "test 2", "100.00", "0.00", "100.00", "No"]
+        ]
+    );
+    assert_eq!(
+        data_2[0],
+        vec!["Method", "test1", "160.00", "200.00", "-40.00", "Yes"]
+    );
+    assert_eq!(
+        data_3[0],
+        vec!["Method", "test1", "100.00", "0.00", "100.00", "Yes"]
+    );
+}
+
+#[test]
+fn check_managing_budgets() {
+    let file_name = "managing_budgets.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_categories(&conn, vec!["Food".to_string(), "Rent".to_string()]).unwrap();
+    set_budget(&conn, "Category", "Food", "300", false, "2022-08").unwrap();
+    set_budget(&conn, "Category", "Rent", "1000", false, "2022-08").unwrap();
+    // setting again changes the amount but keeps the start month
+    set_budget(&conn, "Category", "Food", "250", true, "2022-10").unwrap();
+
+    let status_1 = set_budget(&conn, "Category", "Nothing", "100", false, "2022-08");
+    let status_2 = set_budget(&conn, "Method", "test1", "0", false, "2022-08");
+    let status_3 = set_budget(&conn, "Method", "test1", "100", false, "2022-13");
+
+    rename_category(&conn, "Food", "Grocery").unwrap();
+    let data_1 = get_budgets(&conn, 7, 2022);
+
+    // deleting a category removes its budget
+    delete_category(&conn, "Rent").unwrap();
+    delete_budget(&conn, "Category", "Grocery").unwrap();
+    let status_4 = delete_budget(&conn, "Category", "Grocery");
+    let data_2 = get_budgets(&conn, 7, 2022);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(status_1.is_err());
+    assert!(status_2.is_err());
+    assert!(status_3.is_err());
+    assert!(status_4.is_err());
+    assert_eq!(
+        data_1,
+        vec![
+            vec!["Category", "Grocery", "250.00", "0.00", "250.00", "Yes"],
+            vec!["Category", "Rent", "1000.00", "0.00", "1000.00", "No"]
+        ]
+    );
+    assert!(data_2.is_empty());
+}
@@ -0,0 +1,165 @@
+extern crate rex;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+struct Testing {}
+
+impl StatusChecker for Testing {}
+
+#[test]
+fn check_managing_categories() {
+    let file_name = "managing_categories.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_categories(&conn, vec!["Food".to_string(), "Rent".to_string()]).unwrap();
+    // names are unique
+    let status_1 = add_new_categories(&conn, vec!["Food".to_string()]);
+    let status_4 = add_new_categories(&conn, vec!["  ".to_string()]);
+    let status_5 = rename_category(&conn, "Food", "Rent");
+    let status_6 = rename_category(&conn, "Food", "");
+    let data_1 = get_all_categories(&conn);
+
+    rename_category(&conn, "Food", "Grocery").unwrap();
+    let status_2 = rename_category(&conn, "Food", "Grocery");
+    delete_category(&conn, "Rent").unwrap();
+    let status_3 = delete_category(&conn, "Rent");
+    let data_2 = get_all_categories(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        status_1.map_err(|e| e.to_string()),
+        Err("Food is already a category".to_string())
+    );
+    assert_eq!(
+        status_4.map_err(|e| e.to_string()),
+        Err("The category name cannot be empty".to_string())
+    );
+    assert_eq!(
+        status_5.map_err(|e| e.to_string()),
+        Err("Rent is already a category".to_string())
+    );
+    assert!(status_6.is_err());
+    assert_eq!(data_1, vec!["Food".to_string(), "Rent".to_string()]);
+    assert_eq!(
+        status_2.map_err(|e| e.to_string()),
+        Err("Food is not a category".to_string())
+    );
+    assert!(status_3.is_err());
+    assert_eq!(data_2, vec!["Grocery".to_string()]);
+}
+
+#[test]
+fn check_tx_categories() {
+    let file_name = "tx_categories.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_categories(&conn, vec!["Food".to_string(), "Salary".to_string()]).unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Food",
+        "",
+        None,
+    )
+    .unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-08-20",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    // unknown categories are refused and nothing is saved
+    let status = add_new_tx(
+        &conn,
+        "2022-08-21",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Unknown",
+        "",
+        None,
+    );
+
+    update_tx(
+        &conn,
+        2,
+        "2022-08-20",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Salary",
+        "",
+    )
+    .unwrap();
+
+    let (txs_1, _, _) = get_all_txs(&conn, 7, 2022);
+
+    rename_category(&conn, "Salary", "Wage").unwrap();
+    delete_category(&conn, "Food").unwrap();
+
+    let (txs_2, _, _) = get_all_txs(&conn, 7, 2022);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(status.is_err());
+    assert_eq!(txs_1.len(), 2);
+    assert_eq!(txs_1[0][5], "Food".to_string());
+    assert_eq!(txs_1[1][5], "Salary".to_string());
+    assert_eq!(txs_2[0][5], "".to_string());
+    assert_eq!(txs_2[1][5], "Wage".to_string());
+}
+
+#[test]
+fn check_verifying_categories() {
+    let file_name = "verifying_categories.sqlite";
+    let conn = create_test_db(file_name);
+
+    let mut no_category = "Food".to_string();
+    let status_1 = Testing {}.verify_category(&mut no_category, &conn).unwrap();
+
+    add_new_categories(&conn, vec!["Food".to_string(), "Rent".to_string()]).unwrap();
+
+    let mut empty = "".to_string();
+    let status_2 = Testing {}.verify_category(&mut empty, &conn).unwrap();
+
+    let mut exact = " Rent ".to_string();
+    let status_3 = Testing {}.verify_category(&mut exact, &conn).unwrap();
+
+    let mut wrong = "fod".to_string();
+    let status_4 = Testing {}.verify_category(&mut wrong, &conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(status_1, "Category: No category has been added yet");
+    assert_eq!(status_2, "Category: Nothing to check");
+    assert_eq!(status_3, "Category: Category Accepted");
+    assert_eq!(exact, "Rent".to_string());
+    assert_eq!(status_4, "Category: Category not found");
+    assert_eq!(wrong, "Food".to_string());
+}
@@ -5,8 +5,9 @@ use std::fs;
 //use std::collections::HashMap;
 
 fn create_test_db(file_name: &str) -> Connection {
-    create_db(file_name, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
-    Connection::open(file_name).unwrap()
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
 }
 
 #[test]
@@ -32,8 +33,8 @@ fn check_empty_changes() {
 fn check_getting_all_changes() {
     let file_name = "getting_changes_1.sqlite";
     let conn = create_test_db(file_name);
-    let data = get_all_changes(&conn, 5, 6);
-    let empty_data: Vec<Vec<String>> = Vec::new();
+    let data = get_all_changes(&conn, 5, 2028);
+    let empty_data: Vec<Vec<i64>> = Vec::new();
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -47,53 +48,56 @@ fn check_getting_all_changes_2() {
     let conn = create_test_db(&file_name);
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test1",
         "159.00",
         "Expense",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test 2",
         "159.00",
         "Expense",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-05-01",
         "Testing transaction",
         "test 2",
         "753.00",
         "Expense",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
-    // This is the index of the interface. year 0 = 2022, month 0 = January
-    let data_1 = get_all_changes(&conn, 6, 0);
-    let expected_data_1: Vec<Vec<String>> = vec![
-        vec!["↓159.00".to_string(), "0.00".to_string()],
-        vec!["0.00".to_string(), "↓159.00".to_string()],
-    ];
+    // month is the index of the interface. month 0 = January
+    let data_1 = get_all_changes(&conn, 6, 2022);
+    let expected_data_1: Vec<Vec<i64>> = vec![vec![-15900, 0], vec![0, -15900]];
 
-    let another_data = get_all_changes(&conn, 4, 0);
+    let another_data = get_all_changes(&conn, 4, 2022);
 
-    let another_expected = vec![vec!["0.00".to_string(), "↓753.00".to_string()]];
+    let another_expected = vec![vec![0, -75300]];
 
-    delete_tx(2, &file_name).unwrap();
+    delete_tx(&conn, 2).unwrap();
 
-    let data_2 = get_all_changes(&conn, 6, 0);
-    let expected_data_2: Vec<Vec<String>> = vec![vec!["↓159.00".to_string(), "0.00".to_string()]];
+    let data_2 = get_all_changes(&conn, 6, 2022);
+    let expected_data_2: Vec<Vec<i64>> = vec![vec![-15900, 0]];
 
     conn.close().unwrap();
     fs::remove_file(file_name).unwrap();
@@ -0,0 +1,212 @@
+extern crate rex;
+use rex::config::*;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn to_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn check_config_sources() {
+    let args_config =
+        DbConfig::from_args(&to_args(&["--ledger", "business", "--data-dir", "ledgers"]));
+    let wrong_days = DbConfig::from_args(&to_args(&["--trash-days", "-5"]));
+    let backups_config = DbConfig::from_args(&to_args(&["--backups", "0"]));
+    let wrong_backups = DbConfig::from_args(&to_args(&["--backups", "many"]));
+    let encrypt_config = DbConfig::from_args(&to_args(&["--encrypt", "--ledger", "business"]));
+    let wrong_flag = DbConfig::from_args(&to_args(&["--database", "data.sqlite"]));
+    let missing_value = DbConfig::from_args(&to_args(&["--db"]));
+
+    let file_config = DbConfig::from_config_text(
+        "# the ledgers are kept here\n\ndb = \"/tmp/rex.sqlite\"\nledger = personal\ntrash_days = 7\nbackups = 10\nencrypt = false\n",
+    );
+    let wrong_key = DbConfig::from_config_text("database = data.sqlite");
+    let wrong_line = DbConfig::from_config_text("ledger personal");
+
+    assert_eq!(
+        args_config,
+        Ok(DbConfig {
+            db_path: None,
+            ledger: Some("business".to_string()),
+            data_dir: Some(PathBuf::from("ledgers")),
+            trash_days: None,
+            backups: None,
+            encrypt: None,
+        })
+    );
+    assert!(wrong_flag.is_err());
+    assert!(wrong_days.is_err());
+    assert_eq!(backups_config.unwrap().get_backups(), 0);
+    assert!(wrong_backups.is_err());
+    let encrypt_config = encrypt_config.unwrap();
+    assert!(encrypt_config.get_encrypt());
+    assert_eq!(encrypt_config.ledger, Some("business".to_string()));
+    assert!(missing_value.is_err());
+
+    let file_config = file_config.unwrap();
+    assert_eq!(file_config.db_path, Some(PathBuf::from("/tmp/rex.sqlite")));
+    assert_eq!(file_config.ledger, Some("personal".to_string()));
+    assert_eq!(file_config.get_trash_days(), 7);
+    assert_eq!(file_config.get_backups(), 10);
+    assert_eq!(file_config.encrypt, Some(false));
+    assert!(wrong_key.is_err());
+    assert!(wrong_line.is_err());
+
+    // the first config keeps its values and only the empty fields are filled
+    let merged = args_config.unwrap().or(file_config);
+    assert_eq!(
+        merged,
+        DbConfig {
+            db_path: Some(PathBuf::from("/tmp/rex.sqlite")),
+            ledger: Some("business".to_string()),
+            data_dir: Some(PathBuf::from("ledgers")),
+            trash_days: Some(7),
+            backups: Some(10),
+            encrypt: Some(false),
+        }
+    );
+}
+
+#[test]
+fn check_env_config() {
+    // no other test reads the environment so the variables can be changed here
+    env::set_var("REX_LEDGER", "business");
+    env::set_var("REX_TRASH_DAYS", "7");
+    env::set_var("REX_BACKUPS", "");
+    env::set_var("REX_ENCRYPT", "true");
+    let env_config = DbConfig::from_env();
+
+    env::set_var("REX_TRASH_DAYS", "-5");
+    let wrong_days = DbConfig::from_env();
+    env::set_var("REX_TRASH_DAYS", "7");
+    env::set_var("REX_BACKUPS", "many");
+    let wrong_backups = DbConfig::from_env();
+    env::set_var("REX_BACKUPS", "");
+    env::set_var("REX_ENCRYPT", "yes");
+    let wrong_encrypt = DbConfig::from_env();
+
+    for key in ["REX_LEDGER", "REX_TRASH_DAYS", "REX_BACKUPS", "REX_ENCRYPT"] {
+        env::remove_var(key);
+    }
+
+    let env_config = env_config.unwrap();
+    assert_eq!(env_config.ledger, Some("business".to_string()));
+    assert_eq!(env_config.trash_days, Some(7));
+    assert_eq!(env_config.backups, None);
+    assert_eq!(env_config.encrypt, Some(true));
+    assert_eq!(
+        wrong_days,
+        Err("Trash days must be 0 or a positive number, found -5 in REX_TRASH_DAYS".to_string())
+    );
+    assert_eq!(
+        wrong_backups,
+        Err("Backups must be 0 or a positive number, found many in REX_BACKUPS".to_string())
+    );
+    assert_eq!(
+        wrong_encrypt,
+        Err("Expected true or false, found yes in REX_ENCRYPT".to_string())
+    );
+}
+
+#[test]
+fn check_db_path_resolve() {
+    let data_dir = Path::new("resolve_ledgers");
+    let legacy_path = Path::new("resolve_legacy.sqlite");
+    fs::create_dir_all(data_dir).unwrap();
+
+    let dir_config = DbConfig {
+        data_dir: Some(data_dir.to_path_buf()),
+        ..Default::default()
+    };
+
+    // the default ledger is used when there is no ledger
+    let path_1 = resolve_db_path(&dir_config, None);
+
+    fs::write(data_dir.join("business.sqlite"), "").unwrap();
+    fs::write(data_dir.join("notes.txt"), "").unwrap();
+    // the only ledger is opened
+    let path_2 = resolve_db_path(&dir_config, None);
+
+    fs::write(data_dir.join("personal.sqlite"), "").unwrap();
+    // the user has to select one of the ledgers
+    let path_3 = resolve_db_path(&dir_config, None);
+    let all_ledgers = get_all_ledgers(data_dir);
+
+    // a database in the current directory from older versions is still opened
+    fs::write(legacy_path, "").unwrap();
+    let path_4 = resolve_db_path(&dir_config, Some(legacy_path));
+
+    let ledger_config = DbConfig {
+        ledger: Some("house".to_string()),
+        ..Default::default()
+    }
+    .or(DbConfig {
+        data_dir: Some(data_dir.to_path_buf()),
+        ..Default::default()
+    });
+    let path_5 = resolve_db_path(&ledger_config, Some(legacy_path));
+
+    let db_config = DbConfig {
+        db_path: Some(PathBuf::from("custom.sqlite")),
+        ..ledger_config
+    };
+    let path_6 = resolve_db_path(&db_config, Some(legacy_path));
+
+    fs::remove_dir_all(data_dir).unwrap();
+    fs::remove_file(legacy_path).unwrap();
+
+    assert_eq!(path_1, Some(data_dir.join("personal.sqlite")));
+    assert_eq!(path_2, Some(data_dir.join("business.sqlite")));
+    assert_eq!(path_3, None);
+    assert_eq!(all_ledgers, vec!["business", "personal"]);
+    assert_eq!(path_4, Some(legacy_path.to_path_buf()));
+    assert_eq!(path_5, Some(data_dir.join("house.sqlite")));
+    assert_eq!(path_6, Some(PathBuf::from("custom.sqlite")));
+}
+
+#[test]
+fn check_ledger_names() {
+    assert!(verify_ledger_name("personal"));
+    assert!(verify_ledger_name("side-business_2"));
+    assert!(!verify_ledger_name(""));
+    assert!(!verify_ledger_name("../data"));
+    assert!(!verify_ledger_name("my ledger"));
+}
+
+#[test]
+fn check_cli_commands() {
+    let args_1 = to_args(&["rebuild-balances", "--ledger", "business"]);
+    let args_2 = to_args(&["--ledger", "business"]);
+    let args_3 = to_args(&["rebuild-balances", "extra", "--db", "data.sqlite"]);
+    let args_4 = to_args(&["restore", "2", "--ledger", "business"]);
+
+    let (command_1, flags_1) = get_command(&args_1).unwrap();
+    let (command_2, flags_2) = get_command(&args_2).unwrap();
+    let (command_3, flags_3) = get_command(&args_3).unwrap();
+    let (command_4, flags_4) = get_command(&args_4).unwrap();
+    let wrong_command = get_command(&to_args(&["rebuild"])).is_err();
+
+    assert_eq!(
+        command_1,
+        Some(CliCommand {
+            name: "rebuild-balances".to_string(),
+            args: Vec::new(),
+        })
+    );
+    assert_eq!(flags_1, &args_1[1..]);
+    assert_eq!(command_2, None);
+    assert_eq!(flags_2, &args_2[..]);
+    assert_eq!(command_3.unwrap().args, vec!["extra".to_string()]);
+    assert_eq!(flags_3, &args_3[2..]);
+    assert_eq!(
+        command_4,
+        Some(CliCommand {
+            name: "restore".to_string(),
+            args: vec!["2".to_string()],
+        })
+    );
+    assert_eq!(flags_4, &args_4[2..]);
+    assert!(wrong_command);
+}
@@ -1,14 +1,14 @@
 extern crate rex;
-use rex::db::{add_new_tx_methods, create_db};
+use rex::db::*;
+use rusqlite::Connection;
 use std::fs;
 
 #[test]
 fn check_db_creation() {
-    create_db(
-        "test_data_1.sqlite",
-        vec!["test1".to_string(), "test 2".to_string()],
-    )
-    .unwrap();
+    let conn = Connection::open("test_data_1.sqlite").unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn.close().unwrap();
+
     let paths = fs::read_dir(".").unwrap();
     let mut db_found = false;
     for path in paths {
@@ -24,16 +24,132 @@ fn check_db_creation() {
 
 #[test]
 fn check_adding_new_tx_method() {
-    create_db(
-        "test_data_2.sqlite",
-        vec!["test1".to_string(), "test 2".to_string()],
+    let conn = Connection::open("test_data_2.sqlite").unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    let status = add_new_tx_methods(&conn, vec!["test3".to_string(), "test 4".to_string()]);
+    conn.close().unwrap();
+    fs::remove_file("test_data_2.sqlite").unwrap();
+
+    assert!(status.is_ok())
+}
+
+#[test]
+fn check_migrating_old_db() {
+    let file_name = "test_data_3.sqlite";
+    let conn = Connection::open(file_name).unwrap();
+
+    // the layout of a database that saved every amount as TEXT
+    conn.execute_batch(
+        r#"CREATE TABLE tx_all (date TEXT, details TEXT, tx_method TEXT, amount TEXT, tx_type TEXT,
+            id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT);
+        CREATE TABLE changes_all (date TEXT, id_num INTEGER NOT NULL PRIMARY KEY,
+            "test1" TEXT DEFAULT 0.00, "test 2" TEXT DEFAULT 0.00,
+            CONSTRAINT changes_all_FK FOREIGN KEY (id_num) REFERENCES tx_all(id_num) ON DELETE CASCADE);
+        CREATE TABLE balance_all (id_num INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+            "test1" TEXT DEFAULT 0.00, "test 2" TEXT DEFAULT 0.00);
+        CREATE UNIQUE INDEX all_tx_date_IDX ON tx_all (id_num);
+        CREATE UNIQUE INDEX changes_all_date_IDX ON changes_all (id_num);
+        CREATE UNIQUE INDEX balance_all_id_num_IDX ON balance_all (id_num);
+        INSERT INTO tx_all VALUES ('2022-07-19', 'Testing transaction', 'test1', '159.19', 'Expense', 1);
+        INSERT INTO tx_all VALUES ('2022-07-20', 'Testing transaction', 'test1 to test 2', '0.10', 'Transfer', 2);
+        INSERT INTO changes_all VALUES ('2022-07-19', 1, '↓159.19', '0.00');
+        INSERT INTO changes_all VALUES ('2022-07-20', 2, '↓0.10', '↑0.10');
+        INSERT INTO balance_all VALUES (7, '-159.29', '0.10');
+        INSERT INTO balance_all VALUES (49, '-159.29', '0.10');"#,
     )
     .unwrap();
-    let status = add_new_tx_methods(
-        "test_data_2.sqlite",
-        vec!["test3".to_string(), "test 4".to_string()],
-    );
-    fs::remove_file("test_data_2.sqlite").unwrap();
 
-    assert_eq!(status, Ok(()))
+    migrate_db(&conn).unwrap();
+    // running it again on an updated database does nothing
+    migrate_db(&conn).unwrap();
+    let db_version = get_schema_version(&conn).unwrap();
+
+    let tx_methods = get_all_tx_methods(&conn);
+    let (txs, balances, _) = get_all_txs(&conn, 6, 2022);
+    let changes = get_all_changes(&conn, 6, 2022);
+    let balance = get_last_balances(&conn, &tx_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(tx_methods, vec!["test1".to_string(), "test 2".to_string()]);
+    assert_eq!(txs[0][3], "159.19".to_string());
+    assert_eq!(txs[1][2], "test1 to test 2".to_string());
+    assert_eq!(txs[1][3], "0.10".to_string());
+    assert_eq!(balances, vec![vec![-15919, 0], vec![-15929, 10]]);
+    assert_eq!(changes, vec![vec![-15919, 0], vec![-10, 10]]);
+    assert_eq!(balance, vec![-15929, 10]);
+    assert_eq!(db_version, SCHEMA_VERSION);
+}
+
+#[test]
+fn check_newer_db_refused() {
+    let file_name = "test_data_4.sqlite";
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+
+    let created_version = get_schema_version(&conn).unwrap();
+    conn.execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION + 1))
+        .unwrap();
+
+    let status = migrate_db(&conn);
+    let db_version = get_schema_version(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(created_version, SCHEMA_VERSION);
+    assert!(status.is_err());
+    assert_eq!(db_version, SCHEMA_VERSION + 1);
+}
+
+#[test]
+fn check_renaming_tx_method() {
+    let file_name = "test_data_5.sqlite";
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-20",
+        "Testing transaction",
+        "test 2 to test1",
+        "50.00",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    let status = rename_tx_method(&conn, "test1", "test 3");
+    // an existing name cannot be used
+    let status_2 = rename_tx_method(&conn, "test 3", "test 2");
+
+    let tx_methods = get_all_tx_methods(&conn);
+    let data = get_all_txs(&conn, 6, 2022);
+    let balance = get_last_balances(&conn, &tx_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(status.is_ok());
+    assert!(status_2.is_err());
+    assert_eq!(tx_methods, vec!["test 3".to_string(), "test 2".to_string()]);
+    assert_eq!(data.0[0][2], "test 3".to_string());
+    assert_eq!(data.0[1][2], "test 2 to test 3".to_string());
+    assert_eq!(balance, vec![15000, -5000]);
 }
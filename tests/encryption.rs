@@ -0,0 +1,109 @@
+extern crate rex;
+use chrono::NaiveDate;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+
+fn add_test_tx(conn: &Connection) {
+    add_new_tx(
+        conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+}
+
+fn get_total_txs(conn: &Connection) -> i64 {
+    conn.query_row("SELECT COUNT(*) FROM tx_all", [], |row| row.get(0))
+        .unwrap()
+}
+
+#[test]
+fn check_encrypted_db() {
+    let data_dir = Path::new("encrypted_db");
+    let db_path = data_dir.join("personal.sqlite");
+    fs::create_dir_all(data_dir).unwrap();
+
+    // a new database is encrypted once the passphrase is given before creating it
+    let conn = open_db(&db_path.to_string_lossy()).unwrap();
+    unlock_db(&conn, "secret").unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    migrate_db(&conn).unwrap();
+    add_test_tx(&conn);
+
+    let backup_time = NaiveDate::from_ymd(2022, 7, 19).and_hms(10, 30, 0);
+    backup_db(&conn, &db_path, Some("secret"), backup_time).unwrap();
+    add_test_tx(&conn);
+    conn.close().unwrap();
+
+    let encrypted = is_db_encrypted(&db_path);
+    let plain_open = open_db(&db_path.to_string_lossy())
+        .unwrap()
+        .query_row("SELECT COUNT(*) FROM tx_all", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .is_err();
+    let wrong_unlock = unlock_db(&open_db(&db_path.to_string_lossy()).unwrap(), "wrong").is_err();
+
+    let mut conn = open_db(&db_path.to_string_lossy()).unwrap();
+    unlock_db(&conn, "secret").unwrap();
+    let total_txs = get_total_txs(&conn);
+    let tx_balance = get_last_balances(&conn, &vec!["test1".to_string()]);
+
+    // backups of an encrypted database are encrypted with the same passphrase
+    let backup_path = &get_all_backups(&db_path, Some("secret")).unwrap()[0].path;
+    let backup_encrypted = is_db_encrypted(backup_path);
+    let locked_backup = get_all_backups(&db_path, None).unwrap()[0].total_txs;
+    restore_backup(&mut conn, backup_path, Some("secret")).unwrap();
+    let restored_txs = get_total_txs(&conn);
+
+    change_passphrase(&conn, "new secret").unwrap();
+    conn.close().unwrap();
+    let old_passphrase =
+        unlock_db(&open_db(&db_path.to_string_lossy()).unwrap(), "secret").is_err();
+
+    let wrong_decrypt = decrypt_db(&db_path, "secret").is_err();
+    decrypt_db(&db_path, "new secret").unwrap();
+    let decrypted = !is_db_encrypted(&db_path);
+    let conn = open_db(&db_path.to_string_lossy()).unwrap();
+    let decrypted_txs = get_total_txs(&conn);
+    // the schema version is kept so the database is not migrated again
+    let migrated = migrate_db(&conn).is_ok();
+    let problems = verify_db(&conn).unwrap();
+    conn.close().unwrap();
+
+    let double_decrypt = decrypt_db(&db_path, "new secret").is_err();
+    encrypt_db(&db_path, "another secret").unwrap();
+    let double_encrypt = encrypt_db(&db_path, "another secret").is_err();
+    let conn = open_db(&db_path.to_string_lossy()).unwrap();
+    unlock_db(&conn, "another secret").unwrap();
+    let encrypted_txs = get_total_txs(&conn);
+    conn.close().unwrap();
+
+    fs::remove_dir_all(data_dir).unwrap();
+
+    assert!(encrypted);
+    assert!(plain_open);
+    assert!(wrong_unlock);
+    assert_eq!(total_txs, 2);
+    assert_eq!(tx_balance, vec![20000]);
+    assert!(backup_encrypted);
+    assert_eq!(locked_backup, None);
+    assert_eq!(restored_txs, 1);
+    assert!(old_passphrase);
+    assert!(wrong_decrypt);
+    assert!(decrypted);
+    assert_eq!(decrypted_txs, 1);
+    assert!(migrated);
+    assert!(problems.is_empty());
+    assert!(double_decrypt);
+    assert!(double_encrypt);
+    assert_eq!(encrypted_txs, 1);
+}
@@ -0,0 +1,344 @@
+extern crate rex;
+use chrono::NaiveDate;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+use std::path::Path;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+#[test]
+fn check_date_ranges() {
+    let date = |y, m, d| NaiveDate::from_ymd(y, m, d);
+
+    assert_eq!(
+        parse_date_range("2022"),
+        Ok((date(2022, 1, 1), date(2022, 12, 31)))
+    );
+    assert_eq!(
+        parse_date_range("2024-02"),
+        Ok((date(2024, 2, 1), date(2024, 2, 29)))
+    );
+    assert_eq!(
+        parse_date_range("2022-12"),
+        Ok((date(2022, 12, 1), date(2022, 12, 31)))
+    );
+    assert_eq!(
+        parse_date_range("2022-07-19"),
+        Ok((date(2022, 7, 19), date(2022, 7, 19)))
+    );
+    assert_eq!(
+        parse_date_range("2022-07-01..2022-09-30"),
+        Ok((date(2022, 7, 1), date(2022, 9, 30)))
+    );
+    assert!(parse_date_range("2022-13").is_err());
+    assert!(parse_date_range("2022-09-30..2022-07-01").is_err());
+    assert!(parse_date_range("July").is_err());
+    assert!(parse_date_range("2022-07-01..").is_err());
+}
+
+#[test]
+fn check_csv_export() {
+    let file_name = "csv_export.sqlite";
+    let csv_path = Path::new("csv_export.csv");
+    let conn = create_test_db(file_name);
+    add_new_categories(&conn, vec!["Food".to_string()]).unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Lunch, with \"friends\"",
+        "test1",
+        "25.50",
+        "Expense",
+        "Food",
+        "work",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-08-01",
+        "Moving money",
+        "test1 to test 2",
+        "100.00",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    add_split_tx(
+        &conn,
+        "2022-08-10",
+        "Shopping",
+        "Expense",
+        &[
+            vec!["test1".to_string(), "10".to_string(), "".to_string()],
+            vec!["test 2".to_string(), "5".to_string(), "".to_string()],
+        ],
+        "",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-09-01",
+        "Salary",
+        "test1",
+        "500.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    let (start, end) = parse_date_range("2022-07-01..2022-08-31").unwrap();
+    let total_txs = export_csv(&conn, csv_path, start, end).unwrap();
+    let exported = fs::read_to_string(csv_path).unwrap();
+
+    let (start, end) = parse_date_range("2021").unwrap();
+    let empty_total = export_csv(&conn, csv_path, start, end).unwrap();
+    let empty_export = fs::read_to_string(csv_path).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(csv_path).unwrap();
+
+    let header = "Date,Details,TX Method,Amount,TX Type,Category,Tags,test1,test 2\n";
+
+    assert_eq!(total_txs, 3);
+    assert_eq!(
+        exported,
+        header.to_string()
+            + "2022-07-19,\"Lunch, with \"\"friends\"\"\",test1,25.50,Expense,Food,work,-25.50,0.00\n"
+            + "2022-08-01,Moving money,test1 to test 2,100.00,Transfer,,,-100.00,100.00\n"
+            + "2022-08-10,Shopping,Split,15.00,Expense,,,-10.00,-5.00\n"
+    );
+    assert_eq!(empty_total, 0);
+    assert_eq!(empty_export, header);
+}
+
+#[test]
+fn check_qif_export() {
+    let file_name = "qif_export.sqlite";
+    let new_file_name = "qif_export_new.sqlite";
+    let qif_path = Path::new("qif_export.qif");
+    let conn = create_test_db(file_name);
+    let new_conn = create_test_db(new_file_name);
+    add_new_categories(&conn, vec!["Food".to_string()]).unwrap();
+    add_new_categories(&new_conn, vec!["Food".to_string()]).unwrap();
+    let tx_methods = vec!["test1".to_string(), "test 2".to_string()];
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Lunch",
+        "test1",
+        "25.50",
+        "Expense",
+        "Food",
+        "",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-08-01",
+        "Moving money",
+        "test1 to test 2",
+        "100.00",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-08-02",
+        "Salary",
+        "test 2",
+        "500.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    let (start, end) = parse_date_range("2022").unwrap();
+    let total_txs = export_qif(&conn, qif_path, start, end).unwrap();
+    let exported = fs::read_to_string(qif_path).unwrap();
+
+    // reading the file back gives the same balances
+    let statement = StatementImport::from_qif(&new_conn, &exported, "", "%m/%d/%Y").unwrap();
+    let total_imported = import_rows(&new_conn, &statement).unwrap();
+    let balances = get_last_balances(&conn, &tx_methods);
+    let new_balances = get_last_balances(&new_conn, &tx_methods);
+
+    conn.close().unwrap();
+    new_conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(new_file_name).unwrap();
+    fs::remove_file(qif_path).unwrap();
+
+    assert_eq!(total_txs, 3);
+    assert_eq!(
+        exported,
+        "!Account\nNtest1\nTBank\n^\n!Type:Bank\n\
+        D07/19/2022\nT-25.50\nPLunch\nLFood\n^\n\
+        D08/01/2022\nT-100.00\nPMoving money\nL[test 2]\n^\n\
+        !Account\nNtest 2\nTBank\n^\n!Type:Bank\n\
+        D08/01/2022\nT100.00\nPMoving money\nL[test1]\n^\n\
+        D08/02/2022\nT500.00\nPSalary\n^\n"
+    );
+    assert_eq!(total_imported, 3);
+    assert_eq!(new_balances, balances);
+}
+
+#[test]
+fn check_journal_export() {
+    let file_name = "journal_export.sqlite";
+    let journal_path = Path::new("journal_export.journal");
+    let conn = create_test_db(file_name);
+    add_new_categories(&conn, vec!["Eating out".to_string()]).unwrap();
+
+    // the transactions before the range become the opening balances
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Salary",
+        "test1",
+        "500.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-08-02",
+        "Moving money",
+        "test1 to test 2",
+        "100.00",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-08-10",
+        "Lunch \"at work\"",
+        "test1",
+        "25.50",
+        "Expense",
+        "Eating out",
+        "",
+        None,
+    )
+    .unwrap();
+    add_split_tx(
+        &conn,
+        "2022-08-12",
+        "Shopping",
+        "Expense",
+        &[
+            vec![
+                "test1".to_string(),
+                "10".to_string(),
+                "Eating out".to_string(),
+            ],
+            vec!["test 2".to_string(), "5".to_string(), "".to_string()],
+        ],
+        "",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-08-15",
+        "Savings",
+        "test 2 to test1",
+        "20.00",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    let (start, end) = parse_date_range("2022-08-05..2022-08-31").unwrap();
+    let total_txs =
+        export_journal(&conn, journal_path, start, end, JournalFormat::Hledger).unwrap();
+    let hledger = fs::read_to_string(journal_path).unwrap();
+    export_journal(&conn, journal_path, start, end, JournalFormat::Beancount).unwrap();
+    let beancount = fs::read_to_string(journal_path).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+    fs::remove_file(journal_path).unwrap();
+
+    assert_eq!(total_txs, 3);
+    assert_eq!(
+        hledger,
+        "2022-08-05 Opening Balances\n\
+        \x20   Assets:test1  400.00\n\
+        \x20   Assets:test 2  100.00\n\
+        \x20   Equity:Opening Balances  -500.00\n\
+        \n\
+        2022-08-10 Lunch \"at work\"\n\
+        \x20   Assets:test1  -25.50\n\
+        \x20   Expenses:Eating out  25.50\n\
+        \n\
+        2022-08-12 Shopping\n\
+        \x20   Assets:test1  -10.00\n\
+        \x20   Assets:test 2  -5.00\n\
+        \x20   Expenses:Eating out  10.00\n\
+        \x20   Expenses:Uncategorized  5.00\n\
+        \n\
+        2022-08-15 Savings\n\
+        \x20   Assets:test1  20.00\n\
+        \x20   Assets:test 2  -20.00\n"
+    );
+    assert_eq!(
+        beancount,
+        "2022-08-05 open Assets:Test-2\n\
+        2022-08-05 open Assets:Test1\n\
+        2022-08-05 open Equity:Opening-Balances\n\
+        2022-08-05 open Expenses:Eating-out\n\
+        2022-08-05 open Expenses:Uncategorized\n\
+        \n\
+        2022-08-05 * \"Opening Balances\"\n\
+        \x20 Assets:Test1  400.00 USD\n\
+        \x20 Assets:Test-2  100.00 USD\n\
+        \x20 Equity:Opening-Balances  -500.00 USD\n\
+        \n\
+        2022-08-10 * \"Lunch \\\"at work\\\"\"\n\
+        \x20 Assets:Test1  -25.50 USD\n\
+        \x20 Expenses:Eating-out  25.50 USD\n\
+        \n\
+        2022-08-12 * \"Shopping\"\n\
+        \x20 Assets:Test1  -10.00 USD\n\
+        \x20 Assets:Test-2  -5.00 USD\n\
+        \x20 Expenses:Eating-out  10.00 USD\n\
+        \x20 Expenses:Uncategorized  5.00 USD\n\
+        \n\
+        2022-08-15 * \"Savings\"\n\
+        \x20 Assets:Test1  20.00 USD\n\
+        \x20 Assets:Test-2  -20.00 USD\n"
+    );
+    assert_eq!(
+        JournalFormat::from_name("Ledger"),
+        Ok(JournalFormat::Ledger)
+    );
+    assert!(JournalFormat::from_name("gnucash").is_err());
+}
@@ -0,0 +1,465 @@
+extern crate rex;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+fn bank_profile() -> ImportProfile {
+    ImportProfile {
+        name: "bank".to_string(),
+        has_header: true,
+        date_column: 1,
+        details_column: 2,
+        amount_column: 3,
+        sign_column: Some(4),
+        expense_value: "Debit".to_string(),
+        date_format: "%d/%m/%Y".to_string(),
+        decimal_separator: '.',
+        tx_method: "test1".to_string(),
+    }
+}
+
+#[test]
+fn check_csv_parsing() {
+    let text = "Date,Details,Amount\r\n19/07/2022,\"Lunch, with \"\"friends\"\"\",25.50\n\n20/07/2022,\"Two\nlines\",10";
+
+    assert_eq!(
+        parse_csv(text),
+        vec![
+            (
+                1,
+                vec![
+                    "Date".to_string(),
+                    "Details".to_string(),
+                    "Amount".to_string()
+                ]
+            ),
+            (
+                2,
+                vec![
+                    "19/07/2022".to_string(),
+                    "Lunch, with \"friends\"".to_string(),
+                    "25.50".to_string()
+                ]
+            ),
+            (3, vec!["".to_string()]),
+            (
+                4,
+                vec![
+                    "20/07/2022".to_string(),
+                    "Two\nlines".to_string(),
+                    "10".to_string()
+                ]
+            ),
+        ]
+    );
+    assert!(parse_csv("").is_empty());
+}
+
+#[test]
+fn check_import_profiles() {
+    let file_name = "import_profiles.sqlite";
+    let conn = create_test_db(file_name);
+
+    let mut card = bank_profile();
+    card.name = "card".to_string();
+    card.has_header = false;
+    card.sign_column = None;
+    card.expense_value = "".to_string();
+    card.decimal_separator = ',';
+
+    save_import_profile(&conn, &bank_profile()).unwrap();
+    save_import_profile(&conn, &card).unwrap();
+
+    // saving with the same name replaces the mapping
+    let mut new_bank = bank_profile();
+    new_bank.tx_method = "test 2".to_string();
+    save_import_profile(&conn, &new_bank).unwrap();
+
+    let profiles = get_import_profiles(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(profiles, vec![new_bank, card]);
+}
+
+#[test]
+fn check_import_profile_methods() {
+    let file_name = "import_profile_methods.sqlite";
+    let conn = create_test_db(file_name);
+
+    save_import_profile(&conn, &bank_profile()).unwrap();
+    rename_tx_method(&conn, "test1", "Bank").unwrap();
+    let renamed_method = get_import_profiles(&conn).unwrap()[0].tx_method.clone();
+    let delete_status = delete_tx_method(&conn, "Bank").map_err(|e| e.to_string());
+
+    // the mapping no longer uses the method once it is saved with another one
+    let mut new_bank = bank_profile();
+    new_bank.tx_method = "test 2".to_string();
+    save_import_profile(&conn, &new_bank).unwrap();
+    let deleted = delete_tx_method(&conn, "Bank").is_ok();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(renamed_method, "Bank");
+    assert_eq!(
+        delete_status,
+        Err("Bank is used by 1 saved import mappings and cannot be deleted".to_string())
+    );
+    assert!(deleted);
+}
+
+#[test]
+fn check_csv_import() {
+    let file_name = "csv_import.sqlite";
+    let conn = create_test_db(file_name);
+
+    let text = "Date,Details,Amount,Type
+19/07/2022,Lunch,\"1,025.50\",Debit
+20/07/2022,Salary,$500,Credit
+2022-07-21,Wrong date,10.00,Debit
+22/07/2022,,10.00,Debit
+23/07/2022,Zero,0,Debit
+24/07/2022,Missing columns
+";
+
+    let csv_import = StatementImport::new(&conn, text, &bank_profile()).unwrap();
+    let statuses = csv_import
+        .rows
+        .iter()
+        .map(|a| (a.line, a.problems.len()))
+        .collect::<Vec<(usize, usize)>>();
+
+    let total_added = import_rows(&conn, &csv_import).unwrap();
+    let all_txs = get_all_txs(&conn, 6, 2022).0;
+    let balances = get_last_balances(&conn, &["test1".to_string(), "test 2".to_string()].to_vec());
+
+    // a method that does not exist makes every row fail
+    let mut wrong_method = bank_profile();
+    wrong_method.tx_method = "test".to_string();
+    let failed_import = StatementImport::new(&conn, text, &wrong_method).unwrap();
+
+    // without a sign column negative amounts are expenses
+    let mut no_sign = bank_profile();
+    no_sign.has_header = false;
+    no_sign.sign_column = None;
+    no_sign.date_format = "%Y-%m-%d".to_string();
+    let signed_import = StatementImport::new(
+        &conn,
+        "2022-07-25,Refund,15\n2022-07-26,Fee,(2.50)",
+        &no_sign,
+    )
+    .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        statuses,
+        vec![(2, 0), (3, 0), (4, 1), (5, 1), (6, 1), (7, 3)]
+    );
+    assert_eq!(csv_import.rows[0].date, "2022-07-19");
+    assert_eq!(csv_import.rows[0].amount, "1025.50");
+    assert_eq!(csv_import.rows[0].tx_type, "Expense");
+    assert_eq!(csv_import.rows[1].amount, "500.00");
+    assert_eq!(csv_import.rows[1].tx_type, "Income");
+    assert_eq!(csv_import.total_valid(), 2);
+
+    assert_eq!(total_added, 2);
+    assert_eq!(all_txs.len(), 2);
+    assert_eq!(balances, vec![-52550, 0]);
+
+    assert_eq!(failed_import.total_valid(), 0);
+
+    assert_eq!(signed_import.total_valid(), 2);
+    assert_eq!(signed_import.rows[0].tx_type, "Income");
+    assert_eq!(signed_import.rows[1].tx_type, "Expense");
+    assert_eq!(signed_import.rows[1].amount, "2.50");
+}
+
+#[test]
+fn check_csv_decimal_separators() {
+    let file_name = "csv_decimal_separators.sqlite";
+    let conn = create_test_db(file_name);
+
+    let text = "Date,Details,Amount,Type
+19/07/2022,Lunch,\"12,50\",Debit
+20/07/2022,Rent,\"1.234,56\",Debit
+21/07/2022,Salary,\"1,025.50\",Credit
+";
+
+    let dot_import = StatementImport::new(&conn, text, &bank_profile()).unwrap();
+
+    let mut comma_profile = bank_profile();
+    comma_profile.decimal_separator = ',';
+    let comma_import = StatementImport::new(&conn, text, &comma_profile).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // a decimal comma is never taken as a thousands separator
+    assert_eq!(
+        dot_import.rows[0].problems,
+        vec!["Amount: 12,50 does not use . as the decimal separator".to_string()]
+    );
+    assert_eq!(
+        dot_import.rows[1].problems,
+        vec!["Amount: 1.234,56 does not use . as the decimal separator".to_string()]
+    );
+    assert_eq!(dot_import.rows[2].amount, "1025.50");
+    assert_eq!(dot_import.total_valid(), 1);
+
+    assert_eq!(comma_import.rows[0].amount, "12.50");
+    assert_eq!(comma_import.rows[1].amount, "1234.56");
+    assert_eq!(
+        comma_import.rows[2].problems,
+        vec!["Amount: 1,025.50 does not use , as the decimal separator".to_string()]
+    );
+    assert_eq!(comma_import.total_valid(), 2);
+}
+
+const OFX_SGML: &str = "OFXHEADER:100
+DATA:OFXSGML
+VERSION:102
+
+<OFX>
+<BANKMSGSRSV1><STMTTRNRS><STMTRS>
+<BANKTRANLIST>
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20220719120000.000[-5:EST]
+<TRNAMT>-25.50
+<FITID>1001
+<NAME>Lunch &amp; Drinks
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20220720
+<TRNAMT>500,00
+<FITID>1002
+<MEMO>Salary
+</STMTTRN>
+</BANKTRANLIST>
+</STMTRS></STMTTRNRS></BANKMSGSRSV1>
+</OFX>
+";
+
+const OFX_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<?OFX OFXHEADER="200" VERSION="220"?>
+<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS><BANKTRANLIST>
+<STMTTRN><TRNTYPE>DEBIT</TRNTYPE><DTPOSTED>20220721</DTPOSTED><TRNAMT>-10.00</TRNAMT><FITID>1003</FITID><NAME>Bus</NAME></STMTTRN>
+<stmttrn><trntype>DEBIT</trntype><dtposted>20220722</dtposted><trnamt>-5</trnamt><fitid>1003</fitid><name>Bus again</name></stmttrn>
+</BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>
+"#;
+
+#[test]
+fn check_ofx_parsing() {
+    let sgml_entries = parse_ofx(OFX_SGML).unwrap();
+    let xml_entries = parse_ofx(OFX_XML).unwrap();
+
+    assert_eq!(
+        sgml_entries,
+        vec![
+            OfxEntry {
+                line: 8,
+                fitid: "1001".to_string(),
+                date_posted: "20220719120000.000[-5:EST]".to_string(),
+                amount: "-25.50".to_string(),
+                details: "Lunch & Drinks".to_string(),
+            },
+            OfxEntry {
+                line: 15,
+                fitid: "1002".to_string(),
+                date_posted: "20220720".to_string(),
+                amount: "500.00".to_string(),
+                details: "Salary".to_string(),
+            },
+        ]
+    );
+    assert_eq!(xml_entries.len(), 2);
+    assert_eq!(xml_entries[0].details, "Bus");
+    assert_eq!(xml_entries[1].line, 5);
+    assert!(parse_ofx("Date,Details,Amount").is_err());
+    assert!(is_ofx_file(std::path::Path::new("statement.QFX")));
+    assert!(!is_ofx_file(std::path::Path::new("statement.csv")));
+}
+
+#[test]
+fn check_ofx_import() {
+    let file_name = "ofx_import.sqlite";
+    let conn = create_test_db(file_name);
+
+    let statement = StatementImport::from_ofx(&conn, OFX_SGML, "test1").unwrap();
+    let total_added = import_rows(&conn, &statement).unwrap();
+
+    // importing the same file again does not add anything
+    let statement_again = StatementImport::from_ofx(&conn, OFX_SGML, "test1").unwrap();
+    let added_again = import_rows(&conn, &statement).unwrap();
+
+    // the same FITID on another method is a different entry and a repeated FITID is skipped
+    let other_method = StatementImport::from_ofx(&conn, OFX_XML, "test 2").unwrap();
+    let other_added = import_rows(&conn, &other_method).unwrap();
+
+    let all_txs = get_all_txs(&conn, 6, 2022).0;
+    let balances = get_last_balances(&conn, &["test1".to_string(), "test 2".to_string()].to_vec());
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(statement.total_valid(), 2);
+    assert_eq!(statement.rows[0].date, "2022-07-19");
+    assert_eq!(statement.rows[0].tx_type, "Expense");
+    assert_eq!(statement.rows[1].tx_type, "Income");
+    assert_eq!(statement.rows[1].fitid, Some("1002".to_string()));
+    assert_eq!(total_added, 2);
+
+    assert_eq!(statement_again.total_valid(), 0);
+    assert_eq!(added_again, 0);
+
+    assert_eq!(other_method.total_valid(), 1);
+    assert_eq!(other_added, 1);
+
+    assert_eq!(all_txs.len(), 3);
+    assert_eq!(balances, vec![47450, -1000]);
+}
+
+const QIF_FILE: &str = "!Option:AutoSwitch
+!Account
+Ntest1
+TBank
+^
+Ntest 2
+TCash
+^
+!Clear:AutoSwitch
+!Account
+Ntest1
+TBank
+^
+!Type:Bank
+D07/19/2022
+T-1,025.50
+PLunch
+LFood/Work
+^
+D7/20'22
+T-100.00
+MMoving money
+L[test 2]
+^
+D07/21/2022
+T-30.00
+PShopping
+SFood
+$-20.00
+SGifts
+$-10.00
+^
+!Type:Invst
+D07/21/2022
+NBuy
+^
+!Account
+Ntest 2
+TCash
+^
+!Type:Cash
+D07/20/2022
+T100.00
+PMoving money
+L[test1]
+^
+D07/22/2022
+T5.00
+PFound
+LUnknown
+^
+";
+
+#[test]
+fn check_qif_parsing() {
+    let entries = parse_qif(QIF_FILE).unwrap();
+
+    assert_eq!(entries.len(), 5);
+    assert_eq!(
+        entries[0],
+        QifEntry {
+            line: 15,
+            account: Some("test1".to_string()),
+            date: "07/19/2022".to_string(),
+            amount: "-1,025.50".to_string(),
+            details: "Lunch".to_string(),
+            category: "Food".to_string(),
+            transfer: None,
+        }
+    );
+    assert_eq!(entries[1].details, "Moving money");
+    assert_eq!(entries[1].transfer, Some("test 2".to_string()));
+    assert_eq!(entries[2].amount, "-30.00");
+    assert_eq!(entries[3].account, Some("test 2".to_string()));
+    assert_eq!(entries[3].transfer, Some("test1".to_string()));
+    assert!(parse_qif("Date,Details,Amount").is_err());
+    assert!(is_qif_file(std::path::Path::new("money.qif")));
+}
+
+#[test]
+fn check_qif_import() {
+    let file_name = "qif_import.sqlite";
+    let conn = create_test_db(file_name);
+    add_new_categories(&conn, vec!["Food".to_string()]).unwrap();
+
+    let statement = StatementImport::from_qif(&conn, QIF_FILE, "", "%m/%d/%Y").unwrap();
+    let total_added = import_rows(&conn, &statement).unwrap();
+
+    let all_txs = get_all_txs(&conn, 6, 2022).0;
+    let balances = get_last_balances(&conn, &["test1".to_string(), "test 2".to_string()].to_vec());
+
+    // the records without an account fail without a method
+    let no_account = StatementImport::from_qif(
+        &conn,
+        "!Type:Bank\nD07/19/2022\nT-5\nPBus\n^\n",
+        "",
+        "%m/%d/%Y",
+    )
+    .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let rows = statement
+        .rows
+        .iter()
+        .map(|a| {
+            (
+                a.date.as_str(),
+                a.tx_method.as_str(),
+                a.amount.as_str(),
+                a.tx_type.as_str(),
+                a.category.as_str(),
+                a.problems.len(),
+            )
+        })
+        .collect::<Vec<(&str, &str, &str, &str, &str, usize)>>();
+
+    assert_eq!(
+        rows,
+        vec![
+            ("2022-07-19", "test1", "1025.50", "Expense", "Food", 0),
+            ("2022-07-20", "test1 to test 2", "100.00", "Transfer", "", 0),
+            ("2022-07-21", "test1", "30.00", "Expense", "", 0),
+            ("2022-07-20", "test1 to test 2", "100.00", "Transfer", "", 1),
+            ("2022-07-22", "test 2", "5.00", "Income", "", 0),
+        ]
+    );
+    assert_eq!(total_added, 4);
+    assert_eq!(all_txs.len(), 4);
+    assert_eq!(balances, vec![-115550, 10500]);
+    assert_eq!(no_account.total_valid(), 0);
+}
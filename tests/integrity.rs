@@ -0,0 +1,182 @@
+extern crate rex;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+fn add_test_txs(conn: &Connection) {
+    for (date, tx_method, amount, tx_type) in [
+        ("2022-07-19", "test1", "100.00", "Income"),
+        ("2022-07-25", "test1 to test 2", "40.00", "Transfer"),
+        ("2022-09-01", "test 2", "15.50", "Expense"),
+    ] {
+        add_new_tx(
+            conn,
+            date,
+            "Testing transaction",
+            tx_method,
+            amount,
+            tx_type,
+            "",
+            "",
+            None,
+        )
+        .unwrap();
+    }
+    add_split_tx(
+        conn,
+        "2022-09-10",
+        "Testing transaction",
+        "Expense",
+        &[
+            vec!["test1".to_string(), "10".to_string(), "".to_string()],
+            vec!["test 2".to_string(), "5".to_string(), "".to_string()],
+        ],
+        "",
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn check_verify_clean_db() {
+    let file_name = "verify_clean_db.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_test_txs(&conn);
+    let problems = verify_db(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(problems.is_empty());
+}
+
+#[test]
+fn check_verify_broken_db() {
+    let file_name = "verify_broken_db.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_test_txs(&conn);
+    conn.execute_batch(
+        "PRAGMA foreign_keys = OFF;
+        UPDATE tx_all SET tx_method = 'Bank' WHERE id_num = 1;
+        UPDATE tx_all SET tx_method = 'test1 to Bank' WHERE id_num = 2;
+        UPDATE tx_all SET date = '1999-01-05' WHERE id_num = 3;
+        UPDATE tx_all SET amount = 2000 WHERE id_num = 4;
+        INSERT INTO tx_all (date, details, tx_method, amount, tx_type, id_num)
+            VALUES ('2022-13-01', 'Testing transaction', 'test1', 100, 'Expense', 5);
+        INSERT INTO changes_all (id_num, method_id, amount) VALUES (9, 1, 100);
+        UPDATE changes_all SET amount = 'abc' WHERE id_num = 1;
+        UPDATE balance_all SET balance = '12.50' WHERE id_num = 0 AND method_id = 2;",
+    )
+    .unwrap();
+
+    let problems = verify_db(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        problems,
+        vec![
+            "Row 1 of changes_all has amount \"abc\" that is not a number",
+            "Row 2 of balance_all has balance 12.5 that is not saved as 12.50 in cents",
+            "Transaction 1 uses Bank which is not a Transaction Method",
+            "Transfer 2 uses Bank which is not a Transaction Method",
+            "Transaction 3 is on 1999-01-05 which does not have a monthly balance row",
+            "Split transaction 4 does not match the total of its legs 15.00",
+            "Transaction 5 has \"2022-13-01\" which is not a valid date",
+            "Changes of transaction 9 were found but the transaction does not exist",
+        ]
+    );
+}
+
+#[test]
+fn check_verify_split_method() {
+    let file_name = "verify_split_method.sqlite";
+    let conn = Connection::open(file_name).unwrap();
+    // older versions could create a Transaction Method named Split
+    create_db(&conn, vec!["test1".to_string(), "Split".to_string()]).unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "Split",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    add_split_tx(
+        &conn,
+        "2022-07-20",
+        "Testing transaction",
+        "Expense",
+        &[
+            vec!["test1".to_string(), "10".to_string(), "".to_string()],
+            vec!["Split".to_string(), "5".to_string(), "".to_string()],
+        ],
+        "",
+        None,
+    )
+    .unwrap();
+
+    let problems = verify_db(&conn).unwrap();
+    let status = add_new_tx_methods(&conn, vec!["Split".to_string()]);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // the transaction of the Split method is not taken as a split transaction without legs
+    assert_eq!(
+        problems,
+        vec!["Split is a Transaction Method but the name is used by split transactions. Rename it"]
+    );
+    assert!(status.is_err());
+}
+
+#[test]
+fn check_verify_schema_version() {
+    let file_name = "verify_schema_version.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_test_txs(&conn);
+
+    conn.execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION - 1))
+        .unwrap();
+    let old_problems = verify_db(&conn).unwrap();
+    // verifying does not migrate the database
+    let old_version = get_schema_version(&conn).unwrap();
+
+    conn.execute_batch(&format!("PRAGMA user_version = {}", SCHEMA_VERSION + 1))
+        .unwrap();
+    let new_problems = verify_db(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        old_problems,
+        vec![format!(
+            "The database schema version is {} but this version of the app expects {SCHEMA_VERSION}. Open the database once to migrate it and verify again",
+            SCHEMA_VERSION - 1
+        )]
+    );
+    assert_eq!(old_version, SCHEMA_VERSION - 1);
+    assert_eq!(
+        new_problems,
+        vec![format!(
+            "The database schema version is {} but this version of the app supports up to {SCHEMA_VERSION}. Update the app to verify this database",
+            SCHEMA_VERSION + 1
+        )]
+    );
+}
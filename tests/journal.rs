@@ -0,0 +1,295 @@
+extern crate rex;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+/// Collects every row that a change can touch so two states of the database can be compared. Methods are
+/// compared by their names as a deleted method comes back with a new id. Any monthly balance that had to
+/// be fixed shows up in the state as well.
+fn get_db_state(conn: &Connection) -> Vec<String> {
+    let mut state = Vec::new();
+    for query in [
+        "SELECT name, archived FROM tx_methods ORDER BY name",
+        "SELECT id_num, date, details, tx_method, amount, tx_type, category_id FROM tx_all ORDER BY id_num",
+        "SELECT id_num, tag FROM tx_tags ORDER BY id_num, tag",
+        "SELECT tx_legs.id_num, tx_legs.leg_num, tx_methods.name, tx_legs.amount FROM tx_legs
+        INNER JOIN tx_methods ON tx_methods.id = tx_legs.method_id ORDER BY 1, 2",
+        "SELECT tx_methods.name, changes_all.id_num, changes_all.amount FROM changes_all
+        INNER JOIN tx_methods ON tx_methods.id = changes_all.method_id ORDER BY 1, 2",
+        "SELECT tx_methods.name, balance_all.balance FROM balance_all
+        INNER JOIN tx_methods ON tx_methods.id = balance_all.method_id
+        WHERE balance_all.id_num = 0 ORDER BY 1",
+    ] {
+        let mut statement = conn.prepare(query).unwrap();
+        let total_columns = statement.column_count();
+        let rows = statement
+            .query_map([], |row| {
+                let mut values = Vec::new();
+                for index in 0..total_columns {
+                    values.push(format!("{:?}", row.get_ref(index)?));
+                }
+                Ok(values.join("|"))
+            })
+            .unwrap();
+        state.extend(rows.map(|row| row.unwrap()));
+    }
+    // a month can hold 0 or the carried balance after a deletion so the monthly balances are
+    // checked to be correct instead of being compared
+    state.extend(rebuild_balances(conn).unwrap());
+    state
+}
+
+#[test]
+fn check_undo_redo_txs() {
+    let file_name = "undo_redo_txs.sqlite";
+    let conn = create_test_db(file_name);
+    add_new_categories(&conn, vec!["Food".to_string()]).unwrap();
+
+    let empty_state = get_db_state(&conn);
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Food",
+        "work",
+        None,
+    )
+    .unwrap();
+    add_split_tx(
+        &conn,
+        "2022-08-10",
+        "Testing transaction",
+        "Expense",
+        &[
+            vec!["test1".to_string(), "10".to_string(), "Food".to_string()],
+            vec!["test 2".to_string(), "5".to_string(), "".to_string()],
+        ],
+        "trip",
+        None,
+    )
+    .unwrap();
+    let added_state = get_db_state(&conn);
+
+    update_tx(
+        &conn,
+        1,
+        "2022-09-01",
+        "Edited transaction",
+        "test 2",
+        "50.00",
+        "Expense",
+        "",
+        "",
+    )
+    .unwrap();
+    let edited_state = get_db_state(&conn);
+
+    delete_tx(&conn, 2).unwrap();
+    let deleted_state = get_db_state(&conn);
+
+    let undo_1 = undo_last_change(&conn).unwrap();
+    let state_1 = get_db_state(&conn);
+    let undo_2 = undo_last_change(&conn).unwrap();
+    let state_2 = get_db_state(&conn);
+    let redo_1 = redo_last_change(&conn).unwrap();
+    let state_3 = get_db_state(&conn);
+    let redo_2 = redo_last_change(&conn).unwrap();
+    let state_4 = get_db_state(&conn);
+    let redo_3 = redo_last_change(&conn).unwrap();
+
+    for _ in 0..4 {
+        undo_last_change(&conn).unwrap();
+    }
+    let state_5 = get_db_state(&conn);
+    let undo_3 = undo_last_change(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(undo_1, Some("Undid deleting transaction 2".to_string()));
+    assert_eq!(state_1, edited_state);
+    assert_eq!(undo_2, Some("Undid editing transaction 1".to_string()));
+    assert_eq!(state_2, added_state);
+    assert_eq!(redo_1, Some("Redid editing transaction 1".to_string()));
+    assert_eq!(state_3, edited_state);
+    assert_eq!(redo_2, Some("Redid deleting transaction 2".to_string()));
+    assert_eq!(state_4, deleted_state);
+    assert_eq!(redo_3, None);
+    assert_eq!(state_5, empty_state);
+    assert_eq!(undo_3, None);
+}
+
+#[test]
+fn check_undo_redo_methods() {
+    let file_name = "undo_redo_methods.sqlite";
+    let conn = create_test_db(file_name);
+
+    let state_1 = get_db_state(&conn);
+
+    add_new_tx_methods(&conn, vec!["test 3".to_string(), "test 4".to_string()]).unwrap();
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1 to test 3",
+        "100.00",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    rename_tx_method(&conn, "test 3", "Bank").unwrap();
+    archive_tx_method(&conn, "test 4").unwrap();
+    let state_2 = get_db_state(&conn);
+    delete_tx_method(&conn, "test 4").unwrap();
+
+    let undo_1 = undo_last_change(&conn).unwrap();
+    let state_3 = get_db_state(&conn);
+    let undo_2 = undo_last_change(&conn).unwrap();
+    let undo_3 = undo_last_change(&conn).unwrap();
+    let renamed_back = get_all_txs(&conn, 6, 2022).0[0][2].clone();
+    let redo_1 = redo_last_change(&conn).unwrap();
+    let renamed_again = get_all_txs(&conn, 6, 2022).0[0][2].clone();
+
+    let undo_4 = undo_last_change(&conn).unwrap();
+    let undo_5 = undo_last_change(&conn).unwrap();
+    let undo_6 = undo_last_change(&conn).unwrap();
+    let state_5 = get_db_state(&conn);
+
+    // a new change removes the changes that could be redone
+    add_new_tx_methods(&conn, vec!["test 5".to_string()]).unwrap();
+    let redo_2 = redo_last_change(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        undo_1,
+        Some("Undid deleting Transaction Method test 4".to_string())
+    );
+    assert_eq!(state_3, state_2);
+    assert_eq!(
+        undo_2,
+        Some("Undid archiving Transaction Method test 4".to_string())
+    );
+    assert_eq!(
+        undo_3,
+        Some("Undid renaming Transaction Method test 3 to Bank".to_string())
+    );
+    assert_eq!(renamed_back, "test1 to test 3");
+    assert_eq!(
+        redo_1,
+        Some("Redid renaming Transaction Method test 3 to Bank".to_string())
+    );
+    assert_eq!(renamed_again, "test1 to Bank");
+    assert_eq!(
+        undo_4,
+        Some("Undid renaming Transaction Method test 3 to Bank".to_string())
+    );
+    assert_eq!(undo_5, Some("Undid adding transaction 1".to_string()));
+    assert_eq!(
+        undo_6,
+        Some("Undid adding Transaction Methods test 3, test 4".to_string())
+    );
+    assert_eq!(state_5, state_1);
+    assert_eq!(redo_2, None);
+}
+
+#[test]
+fn check_undo_redo_categories() {
+    let file_name = "undo_redo_categories.sqlite";
+    let conn = create_test_db(file_name);
+    add_new_categories(&conn, vec!["Food".to_string(), "Rent".to_string()]).unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Food",
+        "",
+        None,
+    )
+    .unwrap();
+    add_split_tx(
+        &conn,
+        "2022-08-10",
+        "Testing transaction",
+        "Expense",
+        &[
+            vec!["test1".to_string(), "10".to_string(), "Food".to_string()],
+            vec!["test 2".to_string(), "5".to_string(), "".to_string()],
+        ],
+        "",
+        None,
+    )
+    .unwrap();
+    update_tx(
+        &conn,
+        1,
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "Rent",
+        "",
+    )
+    .unwrap();
+
+    // neither change is in the journal so the saved transactions must follow them
+    rename_category(&conn, "Food", "Grocery").unwrap();
+    delete_category(&conn, "Rent").unwrap();
+
+    let get_category = |conn: &Connection| -> Option<String> {
+        conn.query_row(
+            "SELECT categories.name FROM tx_all
+            LEFT JOIN categories ON categories.id = tx_all.category_id WHERE id_num = 1",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+
+    let undo_1 = undo_last_change(&conn).unwrap();
+    let undone_category = get_category(&conn);
+    let undo_2 = undo_last_change(&conn).unwrap();
+    let redo_1 = redo_last_change(&conn).unwrap();
+    let redone_legs = get_tx_legs(&conn, 2);
+    let redo_2 = redo_last_change(&conn).unwrap();
+    let redone_category = get_category(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(undo_1, Some("Undid editing transaction 1".to_string()));
+    assert_eq!(undone_category, Some("Grocery".to_string()));
+    assert_eq!(undo_2, Some("Undid adding transaction 2".to_string()));
+    assert_eq!(redo_1, Some("Redid adding transaction 2".to_string()));
+    assert_eq!(
+        redone_legs,
+        vec![
+            vec![
+                "test1".to_string(),
+                "10.00".to_string(),
+                "Grocery".to_string()
+            ],
+            vec!["test 2".to_string(), "5.00".to_string(), "".to_string()],
+        ]
+    );
+    assert_eq!(redo_2, Some("Redid editing transaction 1".to_string()));
+    assert_eq!(redone_category, None);
+}
@@ -15,13 +15,14 @@ struct Testing {
 impl StatusChecker for Testing {}
 
 fn create_test_db(file_name: &str) -> Connection {
-    create_db(file_name, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
-    Connection::open(file_name).unwrap()
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
 }
 
 #[test]
 fn check_sql_dates() {
-    let data = get_sql_dates(11, 2);
+    let data = get_sql_dates(11, 2024);
     let expected_data = ("2024-11-01".to_string(), "2024-11-31".to_string());
     assert_eq!(data, expected_data);
 }
@@ -154,10 +155,21 @@ fn check_verifier_date_3() {
     let mut to_verify = test_struct.data.clone();
 
     let result = test_struct.verify_date(&mut to_verify).unwrap();
-    let expected_data = "Date: Year must be between 2022-2025".to_string();
+    let expected_data = "Date: Date Accepted".to_string();
+
+    assert_eq!(result, expected_data);
+    assert_eq!(to_verify, "2026-01-31".to_string());
+
+    let test_struct = Testing {
+        data: "1999-12-31".to_string(),
+    };
+    let mut to_verify = test_struct.data.clone();
+
+    let result = test_struct.verify_date(&mut to_verify).unwrap();
+    let expected_data = "Date: Date Accepted".to_string();
 
     assert_eq!(result, expected_data);
-    assert_eq!(to_verify, "2025-01-31".to_string());
+    assert_eq!(to_verify, "1999-12-31".to_string());
 }
 
 #[test]
@@ -444,3 +456,49 @@ fn check_verifier_tx_type() {
     assert_eq!(result, expected_data);
     assert_eq!(to_verify, "Income".to_string());
 }
+
+#[test]
+fn check_amount_to_cents() {
+    assert_eq!(amount_to_cents("159.19").unwrap(), 15919);
+    assert_eq!(amount_to_cents("  0.1 ").unwrap(), 10);
+    assert_eq!(amount_to_cents("1.").unwrap(), 100);
+    assert_eq!(amount_to_cents("100").unwrap(), 10000);
+    assert_eq!(amount_to_cents("-5.05").unwrap(), -505);
+    assert_eq!(amount_to_cents("1000000000.52").unwrap(), 100000000052);
+
+    assert!(amount_to_cents("").is_err());
+    assert!(amount_to_cents("1.234").is_err());
+    assert!(amount_to_cents("1.2.3").is_err());
+    assert!(amount_to_cents("abc").is_err());
+    assert!(amount_to_cents("--5").is_err());
+}
+
+#[test]
+fn check_amount_too_large() {
+    assert_eq!(amount_to_cents("92233720368547758.07").unwrap(), i64::MAX);
+    assert_eq!(amount_to_cents("-92233720368547758.07").unwrap(), -i64::MAX);
+
+    let error = amount_to_cents("92233720368547758.08").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Amount 92233720368547758.08 is too large"
+    );
+    assert!(amount_to_cents("92233720368547759").is_err());
+    let error = amount_to_cents("18446744073709551616").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Amount 18446744073709551616 is too large"
+    );
+}
+
+#[test]
+fn check_cents_to_amount() {
+    assert_eq!(cents_to_amount(15919), "159.19".to_string());
+    assert_eq!(cents_to_amount(5), "0.05".to_string());
+    assert_eq!(cents_to_amount(0), "0.00".to_string());
+    assert_eq!(cents_to_amount(-505), "-5.05".to_string());
+
+    assert_eq!(cents_to_change(15900), "↑159.00".to_string());
+    assert_eq!(cents_to_change(-15900), "↓159.00".to_string());
+    assert_eq!(cents_to_change(0), "0.00".to_string());
+}
@@ -0,0 +1,288 @@
+extern crate rex;
+use chrono::NaiveDate;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+fn get_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+/// Returns the dates of all the transactions in the database in the order they were added
+fn get_tx_dates(conn: &Connection) -> Vec<String> {
+    let mut statement = conn
+        .prepare("SELECT date FROM tx_all ORDER BY id_num")
+        .unwrap();
+    statement
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .map(|a| a.unwrap())
+        .collect()
+}
+
+#[test]
+fn check_monthly_recurring_txs() {
+    let file_name = "monthly_recurring.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_categories(&conn, vec!["Housing".to_string()]).unwrap();
+    add_recurring_tx(
+        &conn,
+        "Rent",
+        "test1",
+        "500",
+        "Expense",
+        "Housing",
+        "home, fixed",
+        "Monthly",
+        "2022-01-31",
+        None,
+    )
+    .unwrap();
+
+    let (added_1, _) = add_due_recurring_txs(&conn, get_date("2022-04-15")).unwrap();
+    // running again on the same day adds nothing new
+    let (added_2, _) = add_due_recurring_txs(&conn, get_date("2022-04-15")).unwrap();
+    let (added_3, _) = add_due_recurring_txs(&conn, get_date("2022-04-30")).unwrap();
+
+    let dates = get_tx_dates(&conn);
+    let (txs, _, _) = get_all_txs(&conn, 1, 2022);
+    let balances = get_last_balances(&conn, &get_all_tx_methods(&conn));
+    let (templates, _) = get_all_recurring_txs(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(added_1, 3);
+    assert_eq!(added_2, 0);
+    assert_eq!(added_3, 1);
+    assert_eq!(
+        dates,
+        vec!["2022-01-31", "2022-02-28", "2022-03-31", "2022-04-30"]
+    );
+    assert_eq!(
+        txs,
+        vec![vec![
+            "28-02-2022",
+            "Rent",
+            "test1",
+            "500.00",
+            "Expense",
+            "Housing",
+            "fixed, home"
+        ]]
+    );
+    assert_eq!(balances, vec![-200000, 0]);
+    assert_eq!(
+        templates,
+        vec![vec![
+            "Rent",
+            "test1",
+            "500.00",
+            "Expense",
+            "Monthly",
+            "2022-01-31",
+            "-",
+            "2022-05-31",
+            "Active"
+        ]]
+    );
+}
+
+#[test]
+fn check_weekly_and_yearly_recurring_txs() {
+    let file_name = "weekly_yearly_recurring.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_recurring_tx(
+        &conn,
+        "Allowance",
+        "test1 to test 2",
+        "10",
+        "Transfer",
+        "",
+        "",
+        "Weekly",
+        "2022-03-01",
+        Some("2022-03-20"),
+    )
+    .unwrap();
+    add_recurring_tx(
+        &conn,
+        "Bonus",
+        "test 2",
+        "1000.50",
+        "Income",
+        "",
+        "",
+        "Yearly",
+        "2024-02-29",
+        None,
+    )
+    .unwrap();
+
+    let (added, _) = add_due_recurring_txs(&conn, get_date("2026-06-01")).unwrap();
+
+    let dates = get_tx_dates(&conn);
+    let balances = get_last_balances(&conn, &get_all_tx_methods(&conn));
+    let (templates, _) = get_all_recurring_txs(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    // the end date stops the weekly template after the third week
+    assert_eq!(added, 6);
+    assert_eq!(
+        dates,
+        vec![
+            "2022-03-01",
+            "2022-03-08",
+            "2022-03-15",
+            "2024-02-29",
+            "2025-02-28",
+            "2026-02-28"
+        ]
+    );
+    assert_eq!(balances, vec![-3000, 303150]);
+    assert_eq!(templates[0][7], "-");
+    assert_eq!(templates[1][7], "2027-02-28");
+}
+
+#[test]
+fn check_managing_recurring_txs() {
+    let file_name = "managing_recurring.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_recurring_tx(
+        &conn,
+        "Subscription",
+        "test1",
+        "9.99",
+        "Expense",
+        "",
+        "",
+        "Monthly",
+        "2022-01-05",
+        None,
+    )
+    .unwrap();
+    let status_1 = add_recurring_tx(
+        &conn,
+        "Wrong",
+        "test1",
+        "1",
+        "Expense",
+        "",
+        "",
+        "Monthly",
+        "2022-05-01",
+        Some("2022-04-01"),
+    );
+    let (_, ids) = get_all_recurring_txs(&conn);
+
+    add_due_recurring_txs(&conn, get_date("2022-02-10")).unwrap();
+    pause_recurring_tx(&conn, ids[0]).unwrap();
+    // nothing is added while paused
+    let (added_1, _) = add_due_recurring_txs(&conn, get_date("2022-04-10")).unwrap();
+    let (templates_1, _) = get_all_recurring_txs(&conn);
+
+    // the dates missed while paused are skipped
+    resume_recurring_tx(&conn, ids[0], get_date("2022-04-10")).unwrap();
+    let (added_2, _) = add_due_recurring_txs(&conn, get_date("2022-05-10")).unwrap();
+
+    // templates follow renamed methods and keep their methods from being deleted
+    rename_tx_method(&conn, "test1", "Bank").unwrap();
+    let status_2 = delete_tx_method(&conn, "Bank");
+    let (templates_2, _) = get_all_recurring_txs(&conn);
+
+    // deleting a template keeps the transactions added from it
+    delete_recurring_tx(&conn, ids[0]).unwrap();
+    let (templates_3, _) = get_all_recurring_txs(&conn);
+    let dates = get_tx_dates(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(status_1.is_err());
+    assert_eq!(added_1, 0);
+    assert_eq!(templates_1[0][8], "Paused");
+    assert_eq!(added_2, 1);
+    assert!(status_2.is_err());
+    assert_eq!(templates_2[0][1], "Bank");
+    assert_eq!(templates_2[0][8], "Active");
+    assert!(templates_3.is_empty());
+    assert_eq!(dates, vec!["2022-01-05", "2022-02-05", "2022-05-05"]);
+}
+
+#[test]
+fn check_invalid_recurring_txs() {
+    let file_name = "invalid_recurring.sqlite";
+    let conn = create_test_db(file_name);
+
+    let add_template = |tx_method: &str, tx_type: &str, category: &str| {
+        add_recurring_tx(
+            &conn,
+            "Rent",
+            tx_method,
+            "500",
+            tx_type,
+            category,
+            "",
+            "Monthly",
+            "2022-01-01",
+            None,
+        )
+    };
+
+    let status_1 = add_template("test 3", "Expense", "");
+    let status_2 = add_template("test1", "Expense", "Housing");
+    let status_3 = add_template("test1", "Refund", "");
+    let status_4 = add_template("test1 to test1", "Transfer", "");
+    let status_5 = add_template("test1", "Expense", "");
+
+    // a template whose method was archived is skipped until the method is restored
+    archive_tx_method(&conn, "test1").unwrap();
+    let status_6 = add_template("test1", "Expense", "");
+    let (added_1, skipped_1) = add_due_recurring_txs(&conn, get_date("2022-02-10")).unwrap();
+
+    restore_tx_method(&conn, "test1").unwrap();
+    let (added_2, skipped_2) = add_due_recurring_txs(&conn, get_date("2022-02-10")).unwrap();
+    let dates = get_tx_dates(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        status_1.unwrap_err().to_string(),
+        "TX Method: Transaction Method not found: test 3"
+    );
+    assert_eq!(
+        status_2.unwrap_err().to_string(),
+        "Category: No category has been added yet: Housing"
+    );
+    assert_eq!(
+        status_3.unwrap_err().to_string(),
+        "TX Type: Refund is not acceptable. Values: Expense/Income/Transfer"
+    );
+    assert!(status_4.is_err());
+    assert!(status_5.is_ok());
+    assert_eq!(
+        status_6.unwrap_err().to_string(),
+        "TX Method: Transaction Method is archived: test1"
+    );
+
+    assert_eq!(added_1, 0);
+    assert_eq!(
+        skipped_1,
+        vec!["Rent was skipped. TX Method: Transaction Method is archived: test1"]
+    );
+    assert_eq!(added_2, 2);
+    assert!(skipped_2.is_empty());
+    assert_eq!(dates, vec!["2022-01-01", "2022-02-01"]);
+}
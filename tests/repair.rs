@@ -0,0 +1,135 @@
+extern crate rex;
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+fn add_tx(conn: &Connection, date: &str, tx_method: &str, amount: &str, tx_type: &str) {
+    add_new_tx(
+        conn,
+        date,
+        "Testing transaction",
+        tx_method,
+        amount,
+        tx_type,
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+}
+
+fn get_all_balances(conn: &Connection) -> Vec<(i32, i32, i64)> {
+    let mut statement = conn
+        .prepare("SELECT id_num, method_id, balance FROM balance_all ORDER BY id_num, method_id")
+        .unwrap();
+    statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .map(|row| row.unwrap())
+        .collect()
+}
+
+fn add_test_txs(conn: &Connection) {
+    add_tx(conn, "2022-07-19", "test1", "100.00", "Income");
+    add_tx(conn, "2022-07-25", "test1 to test 2", "40.00", "Transfer");
+    add_tx(conn, "2022-09-01", "test 2", "15.50", "Expense");
+    add_split_tx(
+        conn,
+        "2022-09-10",
+        "Testing transaction",
+        "Expense",
+        &[
+            vec!["test1".to_string(), "10".to_string(), "".to_string()],
+            vec!["test 2".to_string(), "5".to_string(), "".to_string()],
+        ],
+        "",
+        None,
+    )
+    .unwrap();
+    add_tx(conn, "2023-01-05", "test 2", "200.00", "Income");
+}
+
+#[test]
+fn check_rebuild_without_drift() {
+    let file_name = "rebuild_without_drift.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_test_txs(&conn);
+    // deleting leaves the balances in a different but correct state
+    delete_tx(&conn, 3).unwrap();
+
+    let balances_1 = get_all_balances(&conn);
+    let differences = rebuild_balances(&conn).unwrap();
+    let balances_2 = get_all_balances(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(differences.is_empty());
+    assert_eq!(balances_1, balances_2);
+}
+
+#[test]
+fn check_rebuild_with_drift() {
+    let file_name = "rebuild_with_drift.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_test_txs(&conn);
+    let balances_1 = get_all_balances(&conn);
+    let changes_1 = get_all_changes(&conn, 8, 2022);
+
+    conn.execute_batch(
+        "UPDATE balance_all SET balance = balance + 500 WHERE id_num = 0 AND method_id = 1;
+        UPDATE balance_all SET balance = 1 WHERE id_num = 202209 AND method_id = 2;
+        UPDATE balance_all SET balance = 999 WHERE id_num = 202208 AND method_id = 1;
+        DELETE FROM changes_all WHERE id_num = 4 AND method_id = 2;",
+    )
+    .unwrap();
+
+    let differences_1 = rebuild_balances(&conn).unwrap();
+    let balances_2 = get_all_balances(&conn);
+    let changes_2 = get_all_changes(&conn, 8, 2022);
+    let differences_2 = rebuild_balances(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        differences_1,
+        vec![
+            "Changes of transaction 4 were test1 -10.00, fixed to test1 -10.00, test 2 -5.00",
+            "Final balance of test1 was 55.00, fixed to 50.00",
+            "Balance of test1 on 2022-08 was 9.99, fixed to 60.00",
+            "Balance of test 2 on 2022-09 was 0.01, fixed to 19.50",
+        ]
+    );
+    assert_eq!(balances_1, balances_2);
+    assert_eq!(changes_1, changes_2);
+    assert!(differences_2.is_empty());
+}
+
+#[test]
+fn check_earlier_tx_without_drift() {
+    let file_name = "earlier_tx_without_drift.sqlite";
+    let conn = create_test_db(file_name);
+
+    // transactions saved on an earlier month than the latest saved one update the later months
+    add_tx(&conn, "2022-09-01", "test1", "100.00", "Income");
+    add_tx(&conn, "2022-07-19", "test1", "20.00", "Expense");
+    add_tx(&conn, "2022-08-25", "test1 to test 2", "40.00", "Transfer");
+    delete_tx(&conn, 2).unwrap();
+    add_tx(&conn, "2022-06-10", "test 2", "15.50", "Income");
+
+    let differences = rebuild_balances(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(differences.is_empty());
+}
@@ -0,0 +1,250 @@
+extern crate rex;
+use rex::db::*;
+use rex::home_page::TransactionData;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+fn get_legs(legs: &[[&str; 3]]) -> Vec<Vec<String>> {
+    legs.iter()
+        .map(|leg| leg.iter().map(|a| a.to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn check_split_txs() {
+    let file_name = "split_txs.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_categories(&conn, vec!["Food".to_string()]).unwrap();
+    add_split_tx(
+        &conn,
+        "2022-08-19",
+        "Groceries",
+        "Expense",
+        &get_legs(&[["test1", "30", "Food"], ["test 2", "12.50", ""]]),
+        "shop",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-08-20",
+        "Testing transaction",
+        "test 2",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    let (txs, balances, id_nums) = get_all_txs(&conn, 7, 2022);
+    let changes = get_all_changes(&conn, 7, 2022);
+    let legs = get_tx_legs(&conn, 1);
+    let last_balance_1 = get_last_balances(&conn, &get_all_tx_methods(&conn));
+
+    delete_tx(&conn, 1).unwrap();
+
+    let last_balance_2 = get_last_balances(&conn, &get_all_tx_methods(&conn));
+    let total_legs: i32 = conn
+        .query_row("SELECT COUNT(*) FROM tx_legs", [], |row| row.get(0))
+        .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        txs[0],
+        vec![
+            "19-08-2022",
+            "Groceries",
+            "Split",
+            "42.50",
+            "Expense",
+            "",
+            "shop"
+        ]
+    );
+    assert_eq!(balances, vec![vec![-3000, -1250], vec![-3000, 8750]]);
+    assert_eq!(id_nums, vec!["1", "2"]);
+    assert_eq!(changes[0], vec![-3000, -1250]);
+    assert_eq!(
+        legs,
+        vec![vec!["test1", "30.00", "Food"], vec!["test 2", "12.50", ""]]
+    );
+    assert_eq!(last_balance_1, vec![-3000, 8750]);
+    assert_eq!(last_balance_2, vec![0, 10000]);
+    assert_eq!(total_legs, 0);
+}
+
+#[test]
+fn check_invalid_split_txs() {
+    let file_name = "invalid_split_txs.sqlite";
+    let conn = create_test_db(file_name);
+
+    let status_1 = add_split_tx(
+        &conn,
+        "2022-08-19",
+        "One leg",
+        "Expense",
+        &get_legs(&[["test1", "30", ""]]),
+        "",
+        None,
+    );
+    let status_2 = add_split_tx(
+        &conn,
+        "2022-08-19",
+        "Transfer",
+        "Transfer",
+        &get_legs(&[["test1", "30", ""], ["test 2", "30", ""]]),
+        "",
+        None,
+    );
+    let status_3 = add_split_tx(
+        &conn,
+        "2022-08-19",
+        "Unknown method",
+        "Expense",
+        &get_legs(&[["test1", "30", ""], ["test 3", "30", ""]]),
+        "",
+        None,
+    );
+    // legs on the same method are saved as one change
+    add_split_tx(
+        &conn,
+        "2022-08-19",
+        "Same method",
+        "Income",
+        &get_legs(&[["test1", "10", ""], ["test1", "5", ""]]),
+        "",
+        None,
+    )
+    .unwrap();
+
+    let (txs, _, _) = get_all_txs(&conn, 7, 2022);
+    let changes = get_all_changes(&conn, 7, 2022);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(status_1.is_err());
+    assert!(status_2.is_err());
+    assert!(status_3.is_err());
+    assert_eq!(txs.len(), 1);
+    assert_eq!(changes, vec![vec![1500, 0]]);
+}
+
+#[test]
+fn check_split_legs_usage() {
+    let file_name = "split_legs_usage.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_tx_methods(&conn, vec!["test 3".to_string()]).unwrap();
+    add_new_categories(&conn, vec!["Food".to_string()]).unwrap();
+    set_budget(&conn, "Category", "Food", "100", false, "2022-08").unwrap();
+    set_budget(&conn, "Method", "test 3", "100", false, "2022-08").unwrap();
+
+    add_split_tx(
+        &conn,
+        "2022-08-19",
+        "Groceries",
+        "Expense",
+        &get_legs(&[["test1", "30", "Food"], ["test 3", "5", ""]]),
+        "",
+        None,
+    )
+    .unwrap();
+    add_split_tx(
+        &conn,
+        "2022-08-20",
+        "Refund",
+        "Income",
+        &get_legs(&[["test1", "10", ""], ["test 3", "5", ""]]),
+        "",
+        None,
+    )
+    .unwrap();
+
+    let budgets = get_budgets(&conn, 7, 2022);
+    // test 3 has no balance left but its legs keep it from being deleted
+    let status = delete_tx_method(&conn, "test 3");
+    rename_tx_method(&conn, "test 3", "Wallet").unwrap();
+    let legs = get_tx_legs(&conn, 1);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        budgets,
+        vec![
+            vec!["Category", "Food", "100.00", "30.00", "70.00", "No"],
+            vec!["Method", "test 3", "100.00", "5.00", "95.00", "No"]
+        ]
+    );
+    assert!(status.is_err());
+    assert_eq!(legs[1][0], "Wallet");
+}
+
+#[test]
+fn check_showing_split_legs() {
+    let file_name = "showing_split_legs.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_tx(
+        &conn,
+        "2022-08-18",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    add_split_tx(
+        &conn,
+        "2022-08-19",
+        "Groceries",
+        "Expense",
+        &get_legs(&[["test1", "30", ""], ["test 2", "12.50", ""]]),
+        "",
+        None,
+    )
+    .unwrap();
+
+    let mut all_data = TransactionData::new(&conn, 7, 2022);
+    let split_index_1 = all_data.toggle_legs(&conn, 1);
+    let shown_txs = all_data.get_txs();
+    let leg_changes = all_data.get_changes(3);
+    let leg_balance = all_data.get_balance(3);
+    let total_expense = all_data.get_total_expense(&conn, None);
+    let is_split = (0..4).map(|a| all_data.is_split(a)).collect::<Vec<bool>>();
+
+    // hiding works from a leg as well
+    let split_index_2 = all_data.toggle_legs(&conn, 2);
+    let hidden_txs = all_data.get_txs();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(split_index_1, 1);
+    assert_eq!(shown_txs.len(), 4);
+    assert_eq!(
+        shown_txs[3],
+        vec!["", "↳ Leg 2", "test 2", "12.50", "Expense", "", ""]
+    );
+    assert_eq!(leg_changes, vec!["Changes", "0.00", "↓12.50"]);
+    assert_eq!(leg_balance, vec!["Balance", "70.00", "-12.50", "57.50"]);
+    assert_eq!(total_expense[3], "42.50");
+    assert_eq!(is_split, vec![false, true, true, true]);
+    assert_eq!(split_index_2, 1);
+    assert_eq!(hidden_txs.len(), 2);
+}
@@ -0,0 +1,149 @@
+extern crate rex;
+use rex::db::*;
+use rex::home_page::TransactionData;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+struct Testing {}
+
+impl StatusChecker for Testing {}
+
+#[test]
+fn check_splitting_tags() {
+    let mut tags = " work,trip-2026,, work ".to_string();
+    let status = Testing {}.verify_tags(&mut tags).unwrap();
+
+    let mut empty_tags = " , ".to_string();
+    let status_2 = Testing {}.verify_tags(&mut empty_tags).unwrap();
+
+    assert_eq!(
+        split_tags("work, trip,,work "),
+        vec!["work".to_string(), "trip".to_string()]
+    );
+    assert_eq!(status, "Tags: Tags Accepted");
+    assert_eq!(tags, "work, trip-2026".to_string());
+    assert_eq!(status_2, "Tags: Nothing to check");
+    assert_eq!(empty_tags, "".to_string());
+}
+
+#[test]
+fn check_tx_tags() {
+    let file_name = "tx_tags.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_tx(
+        &conn,
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "",
+        "work, reimbursable",
+        None,
+    )
+    .unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-08-20",
+        "Testing transaction",
+        "test1 to test 2",
+        "50.00",
+        "Transfer",
+        "",
+        "trip-2026",
+        None,
+    )
+    .unwrap();
+
+    let (txs_1, _, _) = get_all_txs(&conn, 7, 2022);
+    let tags_1 = get_all_tags(&conn);
+
+    update_tx(
+        &conn,
+        1,
+        "2022-08-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "",
+        "work",
+    )
+    .unwrap();
+    delete_tx(&conn, 2).unwrap();
+
+    let (txs_2, _, _) = get_all_txs(&conn, 7, 2022);
+    let tags_2 = get_all_tags(&conn);
+    let total_tags: i32 = conn
+        .query_row("SELECT COUNT(*) FROM tx_tags", [], |row| row.get(0))
+        .unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(txs_1[0][6], "reimbursable, work".to_string());
+    assert_eq!(txs_1[1][6], "trip-2026".to_string());
+    assert_eq!(
+        tags_1,
+        vec![
+            "reimbursable".to_string(),
+            "trip-2026".to_string(),
+            "work".to_string()
+        ]
+    );
+
+    assert_eq!(txs_2.len(), 1);
+    assert_eq!(txs_2[0][6], "work".to_string());
+    assert_eq!(tags_2, vec!["work".to_string()]);
+    assert_eq!(total_tags, 1);
+}
+
+#[test]
+fn check_filtering_by_tag() {
+    let file_name = "filtering_by_tag.sqlite";
+    let conn = create_test_db(file_name);
+
+    for (details, tags) in [("first", "work"), ("second", ""), ("third", "trip, work")] {
+        add_new_tx(
+            &conn,
+            "2022-08-19",
+            details,
+            "test1",
+            "100.00",
+            "Income",
+            "",
+            tags,
+            None,
+        )
+        .unwrap();
+    }
+
+    let mut all_data = TransactionData::new(&conn, 7, 2022);
+    all_data.filter_by_tag(Some("work".to_string()));
+
+    let mut all_data_2 = TransactionData::new(&conn, 7, 2022);
+    all_data_2.filter_by_tag(None);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let details = all_data
+        .get_txs()
+        .iter()
+        .map(|tx| tx[1].clone())
+        .collect::<Vec<String>>();
+
+    assert_eq!(details, vec!["first".to_string(), "third".to_string()]);
+    assert_eq!(all_data.get_id_num(1), 3);
+    assert_eq!(all_data.get_balance(1)[1], "300.00".to_string());
+    assert_eq!(all_data.get_tag(), &Some("work".to_string()));
+    assert_eq!(all_data_2.get_txs().len(), 3);
+}
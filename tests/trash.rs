@@ -0,0 +1,315 @@
+extern crate rex;
+use chrono::{Duration, Local};
+use rex::db::*;
+use rusqlite::Connection;
+use std::fs;
+
+fn create_test_db(file_name: &str) -> Connection {
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
+}
+
+#[test]
+fn check_trash_restore() {
+    let file_name = "trash_restore.sqlite";
+    let conn = create_test_db(file_name);
+    let tx_methods = vec!["test1".to_string(), "test 2".to_string()];
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "",
+        "work",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-08-10",
+        "Testing transaction 2",
+        "test 2",
+        "50.00",
+        "Expense",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    let saved_balances = get_last_balances(&conn, &tx_methods);
+
+    delete_tx(&conn, 1).unwrap();
+    let deleted_balances = get_last_balances(&conn, &tx_methods);
+    let deleted_txs = get_all_txs(&conn, 6, 2022).0;
+    let trash = get_trash_txs(&conn);
+
+    // a new transaction never takes the id_num of a transaction in the trash
+    add_new_tx(
+        &conn,
+        "2022-08-11",
+        "Testing transaction 3",
+        "test1",
+        "10.00",
+        "Expense",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    delete_tx(&conn, 3).unwrap();
+
+    restore_tx(&conn, 1).unwrap();
+    let restored_balances = get_last_balances(&conn, &tx_methods);
+    let restored_txs = get_all_txs(&conn, 6, 2022).0;
+    let restored_tags = get_all_tags(&conn);
+    let restore_again = restore_tx(&conn, 1).is_err();
+    let problems = verify_db(&conn).unwrap();
+
+    let undo = undo_last_change(&conn).unwrap();
+    let undone_balances = get_last_balances(&conn, &tx_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let today = Local::today().naive_local().to_string();
+
+    assert_eq!(saved_balances, vec![10000, -5000]);
+    assert_eq!(deleted_balances, vec![0, -5000]);
+    assert!(deleted_txs.is_empty());
+    assert_eq!(
+        trash,
+        (
+            vec![vec![
+                today,
+                "2022-07-19".to_string(),
+                "Testing transaction".to_string(),
+                "test1".to_string(),
+                "100.00".to_string(),
+                "Income".to_string(),
+            ]],
+            vec![1]
+        )
+    );
+    assert_eq!(restored_balances, saved_balances);
+    assert_eq!(restored_txs[0][0], "19-07-2022");
+    assert_eq!(restored_tags, vec!["work".to_string()]);
+    assert!(restore_again);
+    assert!(problems.is_empty());
+    assert_eq!(undo, Some("Undid restoring transaction 1".to_string()));
+    assert_eq!(undone_balances, deleted_balances);
+}
+
+#[test]
+fn check_trash_purge() {
+    let file_name = "trash_purge.sqlite";
+    let conn = create_test_db(file_name);
+
+    for details in ["First", "Second", "Third"] {
+        add_new_tx(
+            &conn,
+            "2022-07-19",
+            details,
+            "test1",
+            "10.00",
+            "Expense",
+            "",
+            "",
+            None,
+        )
+        .unwrap();
+    }
+    for id_num in 1..=3 {
+        delete_tx(&conn, id_num).unwrap();
+    }
+
+    purge_tx(&conn, 2).unwrap();
+    let purge_again = purge_tx(&conn, 2).is_err();
+    let left_after_purge = get_trash_txs(&conn).1;
+
+    let today = Local::today().naive_local();
+    let not_old_enough = purge_old_trash(&conn, 30, today).unwrap();
+    let disabled = purge_old_trash(&conn, 0, today + Duration::days(60)).unwrap();
+    let old_enough = purge_old_trash(&conn, 30, today + Duration::days(31)).unwrap();
+    let left_after_old = get_trash_txs(&conn).1;
+
+    // the method of a deleted transaction can only be removed once the transaction is purged
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Fourth",
+        "test 2",
+        "10.00",
+        "Expense",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    delete_tx(&conn, 4).unwrap();
+    let delete_error = delete_tx_method(&conn, "test 2").unwrap_err().to_string();
+    purge_tx(&conn, 4).unwrap();
+    delete_tx_method(&conn, "test 2").unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(purge_again);
+    assert_eq!(left_after_purge, vec![3, 1]);
+    assert_eq!(not_old_enough, 0);
+    assert_eq!(disabled, 0);
+    assert_eq!(old_enough, 2);
+    assert!(left_after_old.is_empty());
+    assert_eq!(
+        delete_error,
+        "test 2 is used by 1 transactions in the trash and cannot be deleted"
+    );
+}
+
+#[test]
+fn check_trash_renames() {
+    let file_name = "trash_renames.sqlite";
+    let conn = create_test_db(file_name);
+    add_new_categories(&conn, vec!["Food".to_string(), "Rent".to_string()]).unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1 to test 2",
+        "100.00",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    add_new_tx(
+        &conn,
+        "2022-07-20",
+        "Testing transaction 2",
+        "test 2",
+        "50.00",
+        "Expense",
+        "Food",
+        "",
+        None,
+    )
+    .unwrap();
+    add_split_tx(
+        &conn,
+        "2022-07-21",
+        "Testing transaction 3",
+        "Expense",
+        &[
+            vec!["test1".to_string(), "10".to_string(), "Food".to_string()],
+            vec!["test 2".to_string(), "5".to_string(), "Rent".to_string()],
+        ],
+        "",
+        None,
+    )
+    .unwrap();
+    for id_num in 1..=3 {
+        delete_tx(&conn, id_num).unwrap();
+    }
+
+    let delete_status = delete_tx_method(&conn, "test1").map_err(|e| e.to_string());
+
+    rename_tx_method(&conn, "test1", "Bank").unwrap();
+    rename_category(&conn, "Food", "Grocery").unwrap();
+    delete_category(&conn, "Rent").unwrap();
+    let renamed_methods: Vec<String> = get_trash_txs(&conn)
+        .0
+        .iter()
+        .map(|tx| tx[3].clone())
+        .collect();
+
+    for id_num in 1..=3 {
+        restore_tx(&conn, id_num).unwrap();
+    }
+    let restored_category: Option<String> = conn
+        .query_row(
+            "SELECT categories.name FROM tx_all
+            LEFT JOIN categories ON categories.id = tx_all.category_id WHERE id_num = 2",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    let restored_legs = get_tx_legs(&conn, 3);
+    let problems = verify_db(&conn).unwrap();
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        delete_status,
+        Err("test1 is used by 2 transactions in the trash and cannot be deleted".to_string())
+    );
+    assert_eq!(renamed_methods, vec!["Split", "test 2", "Bank to test 2"]);
+    assert_eq!(restored_category, Some("Grocery".to_string()));
+    assert_eq!(
+        restored_legs,
+        vec![
+            vec![
+                "Bank".to_string(),
+                "10.00".to_string(),
+                "Grocery".to_string()
+            ],
+            vec!["test 2".to_string(), "5.00".to_string(), "".to_string()],
+        ]
+    );
+    assert!(problems.is_empty());
+}
+
+#[test]
+fn check_trash_broken_tx() {
+    let file_name = "trash_broken_tx.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    delete_tx(&conn, 1).unwrap();
+    conn.execute("UPDATE trash SET tx = 'broken' WHERE id_num = 1", [])
+        .unwrap();
+
+    let trash = get_trash_txs(&conn);
+    let restore_status = restore_tx(&conn, 1).is_err();
+    purge_tx(&conn, 1).unwrap();
+    let left_after_purge = get_trash_txs(&conn).1;
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    let today = Local::today().naive_local().to_string();
+
+    // the broken transaction is still listed so it can be purged
+    assert_eq!(
+        trash,
+        (
+            vec![vec![
+                today,
+                "".to_string(),
+                "Transaction 1 cannot be read".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ]],
+            vec![1]
+        )
+    );
+    assert!(restore_status);
+    assert!(left_after_purge.is_empty());
+}
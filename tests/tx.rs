@@ -5,8 +5,9 @@ use std::fs;
 //use std::collections::HashMap;
 
 fn create_test_db(file_name: &str) -> Connection {
-    create_db(file_name, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
-    Connection::open(file_name).unwrap()
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
 }
 
 #[test]
@@ -29,12 +30,14 @@ fn check_last_tx_id_2() {
     let conn = create_test_db(&file_name);
 
     add_new_tx(
+        &conn,
         "2022-09-19",
         "Testing transaction",
         "test1",
         "100.00",
         "Income",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
@@ -53,7 +56,7 @@ fn check_getting_all_tx_1() {
     let file_name = "getting_tx_1.sqlite".to_string();
     let conn = create_test_db(&file_name);
 
-    let data = get_all_txs(&conn, 6, 0);
+    let data = get_all_txs(&conn, 6, 2022);
     let expected_data = (Vec::new(), Vec::new(), Vec::new());
 
     conn.close().unwrap();
@@ -68,51 +71,59 @@ fn check_getting_all_tx_2() {
     let conn = create_test_db(&file_name);
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test1",
         "100.00",
         "Expense",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-07-19",
         "Testing transaction",
         "test 2",
         "100.00",
         "Expense",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-05-15",
         "Testing transaction",
         "test 2",
         "100.00",
         "Expense",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
     add_new_tx(
+        &conn,
         "2022-05-20",
         "Testing transaction",
         "test 2",
         "100.00",
         "Income",
-        &file_name,
+        "",
+        "",
         None,
     )
     .unwrap();
 
-    let data = get_all_txs(&conn, 6, 0);
-    let data_2 = get_all_txs(&conn, 4, 0);
+    let data = get_all_txs(&conn, 6, 2022);
+    let data_2 = get_all_txs(&conn, 4, 2022);
 
     let expected_data = (
         vec![
@@ -122,6 +133,8 @@ fn check_getting_all_tx_2() {
                 "test1".to_string(),
                 "100.00".to_string(),
                 "Expense".to_string(),
+                "".to_string(),
+                "".to_string(),
             ],
             vec![
                 "19-07-2022".to_string(),
@@ -129,12 +142,11 @@ fn check_getting_all_tx_2() {
                 "test 2".to_string(),
                 "100.00".to_string(),
                 "Expense".to_string(),
+                "".to_string(),
+                "".to_string(),
             ],
         ],
-        vec![
-            vec!["-100.00".to_string(), "0.00".to_string()],
-            vec!["-100.00".to_string(), "-100.00".to_string()],
-        ],
+        vec![vec![-10000, 0], vec![-10000, -10000]],
         vec!["1".to_string(), "2".to_string()],
     );
 
@@ -146,6 +158,8 @@ fn check_getting_all_tx_2() {
                 "test 2".to_string(),
                 "100.00".to_string(),
                 "Expense".to_string(),
+                "".to_string(),
+                "".to_string(),
             ],
             vec![
                 "20-05-2022".to_string(),
@@ -153,12 +167,11 @@ fn check_getting_all_tx_2() {
                 "test 2".to_string(),
                 "100.00".to_string(),
                 "Income".to_string(),
+                "".to_string(),
+                "".to_string(),
             ],
         ],
-        vec![
-            vec!["0.00".to_string(), "-100.00".to_string()],
-            vec!["0.00".to_string(), "0.00".to_string()],
-        ],
+        vec![vec![0, -10000], vec![0, 0]],
         vec!["3".to_string(), "4".to_string()],
     );
 
@@ -168,3 +181,146 @@ fn check_getting_all_tx_2() {
     assert_eq!(data, expected_data);
     assert_eq!(data_2, expected_data_2);
 }
+
+#[test]
+fn check_updating_tx() {
+    let file_name = "updating_tx.sqlite".to_string();
+    let conn = create_test_db(&file_name);
+    let tx_methods = get_all_tx_methods(&conn);
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Expense",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    update_tx(
+        &conn,
+        1,
+        "2022-08-19",
+        "Edited transaction",
+        "test1 to test 2",
+        "50.00",
+        "Transfer",
+        "",
+        "",
+    )
+    .unwrap();
+
+    let data_1 = get_all_txs(&conn, 6, 2022);
+    let data_2 = get_all_txs(&conn, 7, 2022);
+    let changes = get_all_changes(&conn, 7, 2022);
+    let balance = get_last_balances(&conn, &tx_methods);
+
+    // a failed edit must leave the previous transaction untouched
+    let status = update_tx(
+        &conn,
+        1,
+        "2022-08-19",
+        "Edited transaction",
+        "test1",
+        "not an amount",
+        "Income",
+        "",
+        "",
+    );
+    let data_3 = get_all_txs(&conn, 7, 2022);
+    let balance_2 = get_last_balances(&conn, &tx_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(data_1, (Vec::new(), Vec::new(), Vec::new()));
+    assert_eq!(
+        data_2.0,
+        vec![vec![
+            "19-08-2022".to_string(),
+            "Edited transaction".to_string(),
+            "test1 to test 2".to_string(),
+            "50.00".to_string(),
+            "Transfer".to_string(),
+            "".to_string(),
+            "".to_string()
+        ]]
+    );
+    assert_eq!(data_2.2, vec!["1".to_string()]);
+    assert_eq!(changes, vec![vec![-5000, 5000]]);
+    assert_eq!(balance, vec![-5000, 5000]);
+
+    assert!(status.is_err());
+    assert_eq!(data_3, data_2);
+    assert_eq!(balance_2, balance);
+}
+
+#[test]
+fn check_tx_inside_transaction() {
+    let file_name = "tx_inside_transaction.sqlite".to_string();
+    let mut conn = create_test_db(&file_name);
+
+    let changes_1 = get_total_changes(&conn);
+
+    // the functions work inside a transaction of the caller and follow its rollback
+    let db_tx = conn.transaction().unwrap();
+    add_new_tx(
+        &db_tx,
+        "2022-09-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    let data_1 = get_all_txs(&db_tx, 8, 2022);
+    db_tx.rollback().unwrap();
+
+    let data_2 = get_all_txs(&conn, 8, 2022);
+
+    // a failed transaction leaves nothing behind
+    let status = add_new_tx(
+        &conn,
+        "2022-09-19",
+        "Testing transaction",
+        "test1",
+        "100.00",
+        "Income",
+        "Missing Category",
+        "",
+        None,
+    );
+    let data_3 = get_all_txs(&conn, 8, 2022);
+    let balances = get_last_balances(&conn, &get_all_tx_methods(&conn));
+
+    add_new_tx(
+        &conn,
+        "2022-09-20",
+        "Testing transaction",
+        "test 2",
+        "50.00",
+        "Expense",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    let changes_2 = get_total_changes(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(data_1.0.len(), 1);
+    assert!(data_2.0.is_empty());
+    assert!(status.is_err());
+    assert!(data_3.0.is_empty());
+    assert_eq!(balances, vec![0, 0]);
+    assert!(changes_2 > changes_1);
+}
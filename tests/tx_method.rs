@@ -5,8 +5,9 @@ use std::fs;
 //use std::collections::HashMap;
 
 fn create_test_db(file_name: &str) -> Connection {
-    create_db(file_name, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
-    Connection::open(file_name).unwrap()
+    let conn = Connection::open(file_name).unwrap();
+    create_db(&conn, vec!["test1".to_string(), "test 2".to_string()]).unwrap();
+    conn
 }
 
 #[test]
@@ -27,7 +28,7 @@ fn check_getting_tx_methods_2() {
     let conn = create_test_db(file_name);
 
     add_new_tx_methods(
-        file_name,
+        &conn,
         vec!["new method 1".to_string(), "testing methods".to_string()],
     )
     .unwrap();
@@ -47,3 +48,212 @@ fn check_getting_tx_methods_2() {
         ]
     );
 }
+
+struct Testing {}
+
+impl StatusChecker for Testing {}
+
+#[test]
+fn check_archiving_tx_methods() {
+    let file_name = "archiving_tx_methods.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test 2",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    archive_tx_method(&conn, "test 2").unwrap();
+    // the last active method cannot be archived
+    let status = archive_tx_method(&conn, "test1");
+
+    let all_methods = get_all_tx_methods(&conn);
+    let active_methods = get_active_tx_methods(&conn);
+    let archived_methods = get_archived_tx_methods(&conn);
+    let balance = get_last_balances(&conn, &all_methods);
+
+    let mut to_verify = "test 2".to_string();
+    let verify_status = Testing {}.verify_tx_method(&mut to_verify, &conn).unwrap();
+
+    restore_tx_method(&conn, "test 2").unwrap();
+    let active_methods_2 = get_active_tx_methods(&conn);
+    let status_2 = restore_tx_method(&conn, "test 2");
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(status.is_err());
+    assert_eq!(all_methods, vec!["test1".to_string(), "test 2".to_string()]);
+    assert_eq!(active_methods, vec!["test1".to_string()]);
+    assert_eq!(archived_methods, vec!["test 2".to_string()]);
+    assert_eq!(balance, vec![0, 10000]);
+
+    assert_eq!(verify_status, "TX Method: Transaction Method is archived");
+    assert_eq!(to_verify, "test1".to_string());
+
+    assert_eq!(
+        active_methods_2,
+        vec!["test1".to_string(), "test 2".to_string()]
+    );
+    assert!(status_2.is_err());
+}
+
+#[test]
+fn check_deleting_tx_methods() {
+    let file_name = "deleting_tx_methods.sqlite";
+    let conn = create_test_db(file_name);
+
+    add_new_tx_methods(&conn, vec!["test 3".to_string()]).unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test1 to test 2",
+        "100.00",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    // methods with a transaction cannot be deleted
+    let status_1 = delete_tx_method(&conn, "test 2");
+
+    // a transaction in the trash still uses its methods until it is purged
+    delete_tx(&conn, 1).unwrap();
+    purge_tx(&conn, 1).unwrap();
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        "test 3",
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+    let status_2 = delete_tx_method(&conn, "test 2");
+    let status_3 = delete_tx_method(&conn, "test 3");
+
+    let data = get_all_tx_methods(&conn);
+    let changes = get_all_changes(&conn, 6, 2022);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert!(status_1.is_err());
+    assert!(status_2.is_ok());
+    assert!(status_3.is_err());
+    assert_eq!(data, vec!["test1".to_string(), "test 3".to_string()]);
+    assert_eq!(changes, vec![vec![0, 10000]]);
+}
+
+#[test]
+fn check_quoted_tx_methods() {
+    let file_name = "quoted_tx_methods.sqlite";
+    let conn = Connection::open(file_name).unwrap();
+    create_db(
+        &conn,
+        vec![r#"Bank "A""#.to_string(), "Bob's Cash".to_string()],
+    )
+    .unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-19",
+        "Testing transaction",
+        r#"Bank "A""#,
+        "100.00",
+        "Income",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    add_new_tx(
+        &conn,
+        "2022-07-20",
+        "Testing transaction",
+        r#"Bank "A" to Bob's Cash"#,
+        "25.50",
+        "Transfer",
+        "",
+        "",
+        None,
+    )
+    .unwrap();
+
+    rename_tx_method(&conn, "Bob's Cash", r#"Bob's "Cash""#).unwrap();
+
+    let tx_methods = get_all_tx_methods(&conn);
+    let changes = get_all_changes(&conn, 6, 2022);
+    let balance = get_last_balances(&conn, &tx_methods);
+
+    delete_tx(&conn, 1).unwrap();
+    let balance_2 = get_last_balances(&conn, &tx_methods);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        tx_methods,
+        vec![r#"Bank "A""#.to_string(), r#"Bob's "Cash""#.to_string()]
+    );
+    assert_eq!(changes, vec![vec![10000, 0], vec![-2550, 2550]]);
+    assert_eq!(balance, vec![7450, 2550]);
+    assert_eq!(balance_2, vec![-2550, 2550]);
+}
+
+#[test]
+fn check_renaming_tx_methods() {
+    let file_name = "renaming_tx_methods.sqlite";
+    let conn = create_test_db(file_name);
+
+    let status_1 = rename_tx_method(&conn, "test1", "");
+    let status_2 = rename_tx_method(&conn, "test1", "Bank to Cash");
+    let status_3 = rename_tx_method(&conn, "test1", "Split");
+    let status_4 = rename_tx_method(&conn, "test1", "test 2");
+    let status_5 = rename_tx_method(&conn, "test 3", "Bank");
+    let status_6 = rename_tx_method(&conn, "test1", "Bank");
+
+    let tx_methods = get_all_tx_methods(&conn);
+
+    conn.close().unwrap();
+    fs::remove_file(file_name).unwrap();
+
+    assert_eq!(
+        status_1.unwrap_err().to_string(),
+        "The Transaction Method name cannot be empty"
+    );
+    assert_eq!(
+        status_2.unwrap_err().to_string(),
+        "Bank to Cash contains ' to ' and cannot be a Transaction Method"
+    );
+    assert_eq!(
+        status_3.unwrap_err().to_string(),
+        "Split is used by split transactions and cannot be a Transaction Method"
+    );
+    assert_eq!(
+        status_4.unwrap_err().to_string(),
+        "test 2 is already a Transaction Method"
+    );
+    assert_eq!(
+        status_5.unwrap_err().to_string(),
+        "test 3 is not a Transaction Method"
+    );
+    assert!(status_6.is_ok());
+    assert_eq!(tx_methods, vec!["Bank".to_string(), "test 2".to_string()]);
+}